@@ -3,7 +3,6 @@
 
 extern crate fastobo;
 extern crate pyo3;
-extern crate pest;
 extern crate libc;
 extern crate url;
 
@@ -29,6 +29,7 @@ use crate::error::Error;
 use crate::pyfile::PyFile;
 
 use super::header::frame::HeaderFrame;
+use super::instance::frame::InstanceFrame;
 use super::term::frame::TermFrame;
 use super::typedef::frame::TypedefFrame;
 
@@ -39,6 +40,7 @@ use super::typedef::frame::TypedefFrame;
 pub enum EntityFrame {
     Term(Py<TermFrame>),
     Typedef(Py<TypedefFrame>),
+    Instance(Py<InstanceFrame>),
 }
 
 impl FromPy<fastobo::ast::EntityFrame> for EntityFrame {
@@ -50,7 +52,9 @@ impl FromPy<fastobo::ast::EntityFrame> for EntityFrame {
             fastobo::ast::EntityFrame::Typedef(frame) =>
                 Py::new(py, TypedefFrame::from_py(frame, py))
                     .map(EntityFrame::Typedef),
-            _ => unimplemented!(),
+            fastobo::ast::EntityFrame::Instance(frame) =>
+                Py::new(py, InstanceFrame::from_py(frame, py))
+                    .map(EntityFrame::Instance),
         }.expect("could not allocate on Python heap")
     }
 }
@@ -1262,7 +1262,7 @@ impl TreatXrefsAsHasSubclassClause {
 
 impl From<TreatXrefsAsHasSubclassClause> for obo::HeaderClause {
     fn from(clause: TreatXrefsAsHasSubclassClause) -> Self {
-        obo::HeaderClause::TreatXrefsAsIsA(clause.idspace.into())
+        obo::HeaderClause::TreatXrefsAsHasSubclass(clause.idspace.into())
     }
 }
 
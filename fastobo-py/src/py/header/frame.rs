@@ -1,5 +1,6 @@
 use std::iter::FromIterator;
 use std::iter::IntoIterator;
+use std::str::FromStr;
 
 use fastobo::ast as obo;
 use pyo3::prelude::*;
@@ -21,17 +22,43 @@ use pyo3::AsPyPointer;
 
 use super::HeaderClause;
 use super::BaseHeaderClause;
+use crate::error::Error;
 use crate::utils::ClonePy;
 
+/// Convert the clause of a line, keeping its qualifiers, comment and location.
+fn map_line<T, U, F>(line: obo::Line<T>, f: F) -> obo::Line<U>
+where
+    F: FnOnce(T) -> U,
+{
+    let qualifiers = line.qualifiers().cloned();
+    let comment = line.comment().cloned();
+    let location = line.location().cloned();
+    obo::Line::from(f(line.into_inner()))
+        .and_qualifiers(qualifiers)
+        .and_comment(comment)
+        .and_location(location)
+}
+
 #[pyclass]
 #[derive(Debug)]
 pub struct HeaderFrame {
-    clauses: Vec<HeaderClause>
+    clauses: Vec<obo::Line<HeaderClause>>
 }
 
 impl HeaderFrame {
     pub fn new(clauses: Vec<HeaderClause>) -> Self {
-        Self { clauses }
+        Self {
+            clauses: clauses.into_iter().map(obo::Line::from).collect()
+        }
+    }
+
+    /// Check an index is in bounds, or raise an `IndexError`.
+    fn index(&self, index: isize) -> PyResult<usize> {
+        if index >= 0 && index < self.clauses.len() as isize {
+            Ok(index as usize)
+        } else {
+            IndexError::into("list index out of range")
+        }
     }
 }
 
@@ -54,24 +81,27 @@ impl FromIterator<HeaderClause> for HeaderFrame {
 
 impl FromPy<obo::HeaderFrame> for HeaderFrame {
     fn from_py(frame: fastobo::ast::HeaderFrame, py: Python) -> Self {
-        frame.into_iter()
-            .map(|clause| HeaderClause::from_py(clause, py))
-            .collect()
+        let clauses = frame.into_iter()
+            .map(|line| map_line(line, |clause| HeaderClause::from_py(clause, py)))
+            .collect();
+        Self { clauses }
     }
 }
 
 impl FromPy<HeaderFrame> for obo::HeaderFrame {
     fn from_py(frame: HeaderFrame, py: Python) -> Self {
-        frame.clauses
+        let clauses = frame.clauses
             .into_iter()
-            .map(|clause| obo::HeaderClause::from_py(clause, py))
-            .collect()
+            .map(|line| map_line(line, |clause| obo::HeaderClause::from_py(clause, py)))
+            .collect();
+        obo::HeaderFrame::new(clauses)
     }
 }
 
 impl ToPyObject for HeaderFrame {
     fn to_object(&self, py: Python) -> PyObject {
-        PyList::new(py, &self.clauses).into_object(py)
+        let clauses: Vec<&HeaderClause> = self.clauses.iter().map(|l| l.as_ref()).collect();
+        PyList::new(py, &clauses).into_object(py)
     }
 }
 
@@ -89,6 +119,43 @@ impl HeaderFrame {
             Ok(obj.init(Self::new(Vec::new())))
         }
     }
+
+    /// Get the comment of the clause at the given index, if any.
+    fn get_comment(&self, index: isize) -> PyResult<Option<String>> {
+        let line = &self.clauses[self.index(index)?];
+        Ok(line.comment().map(|comment| comment.as_str().to_string()))
+    }
+
+    /// Set the comment of the clause at the given index, or remove it with `None`.
+    fn set_comment(&mut self, index: isize, comment: Option<String>) -> PyResult<()> {
+        let index = self.index(index)?;
+        let line = self.clauses.remove(index);
+        let comment = comment.map(obo::Comment::new);
+        self.clauses.insert(index, line.and_comment(comment));
+        Ok(())
+    }
+
+    /// Get the qualifiers of the clause at the given index, if any.
+    ///
+    /// Qualifiers are returned as a string, e.g. ``{source="GOC"}``.
+    fn get_qualifiers(&self, index: isize) -> PyResult<Option<String>> {
+        let line = &self.clauses[self.index(index)?];
+        Ok(line.qualifiers().map(|qualifiers| qualifiers.to_string()))
+    }
+
+    /// Set the qualifiers of the clause at the given index, or remove them with `None`.
+    ///
+    /// Qualifiers are parsed from a string, e.g. ``{source="GOC"}``.
+    fn set_qualifiers(&mut self, index: isize, qualifiers: Option<&str>) -> PyResult<()> {
+        let index = self.index(index)?;
+        let qualifiers = match qualifiers {
+            Some(s) => Some(obo::QualifierList::from_str(s).map_err(Error::from)?),
+            None => None,
+        };
+        let line = self.clauses.remove(index);
+        self.clauses.insert(index, line.and_qualifiers(qualifiers));
+        Ok(())
+    }
 }
 
 #[pyproto]
@@ -119,7 +186,7 @@ impl PySequenceProtocol for HeaderFrame {
         };
 
         if index < self.clauses.len() as isize {
-            let item = &self.clauses[index as usize];
+            let item: &HeaderClause = self.clauses[index as usize].as_ref();
             Ok(item.to_object(py))
         } else {
             IndexError::into("list index out of range")
@@ -129,8 +196,9 @@ impl PySequenceProtocol for HeaderFrame {
         if index as usize > self.clauses.len() {
             return IndexError::into("list index out of range");
         }
+        // The qualifiers and comment of the replaced line are not kept.
         let clause = HeaderClause::extract(elem)?;
-        self.clauses[index as usize] = clause;
+        self.clauses[index as usize] = obo::Line::from(clause);
         Ok(())
     }
     fn __delitem__(&mut self, index: isize) -> PyResult<()> {
@@ -147,9 +215,9 @@ impl PySequenceProtocol for HeaderFrame {
         let iterator = PyIterator::from_object(py, other)?;
         let mut new_clauses = self.clauses.clone_py(py);
         for item in iterator {
-            new_clauses.push(HeaderClause::extract(item?)?);
+            new_clauses.push(obo::Line::from(HeaderClause::extract(item?)?));
         }
 
-        Ok(Self::new(new_clauses))
+        Ok(Self { clauses: new_clauses })
     }
 }
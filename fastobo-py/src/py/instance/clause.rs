@@ -0,0 +1,627 @@
+use std::str::FromStr;
+
+use pyo3::AsPyPointer;
+use pyo3::PyNativeType;
+use pyo3::PyObjectProtocol;
+use pyo3::PyTypeInfo;
+use pyo3::prelude::*;
+use pyo3::exceptions::TypeError;
+use pyo3::exceptions::ValueError;
+use pyo3::types::PyAny;
+use pyo3::types::PyString;
+
+use fastobo::ast;
+use fastobo::share::Share;
+use fastobo::share::Cow;
+use fastobo::share::Redeem;
+
+use crate::utils::AsGILRef;
+use crate::utils::ClonePy;
+use super::super::id::Ident;
+use super::super::pv::PropertyValue;
+use super::super::xref::Xref;
+use super::super::xref::XrefList;
+use super::super::syn::Synonym;
+
+// --- Conversion Wrapper ----------------------------------------------------
+
+#[derive(ClonePy, Debug, PartialEq, PyWrapper)]
+#[wraps(BaseInstanceClause)]
+pub enum InstanceClause {
+    IsAnonymous(Py<IsAnonymousClause>),
+    Name(Py<NameClause>),
+    Namespace(Py<NamespaceClause>),
+    AltId(Py<AltIdClause>),
+    Def(Py<DefClause>),
+    Comment(Py<CommentClause>),
+    Subset(Py<SubsetClause>),
+    Synonym(Py<SynonymClause>),
+    Xref(Py<XrefClause>),
+    PropertyValue(Py<PropertyValueClause>),
+    InstanceOf(Py<InstanceOfClause>),
+    Relationship(Py<RelationshipClause>),
+    CreatedBy(Py<CreatedByClause>),
+    CreationDate(Py<CreationDateClause>),
+    IsObsolete(Py<IsObsoleteClause>),
+    ReplacedBy(Py<ReplacedByClause>),
+    Consider(Py<ConsiderClause>),
+}
+
+impl FromPy<fastobo::ast::InstanceClause> for InstanceClause {
+    fn from_py(clause: fastobo::ast::InstanceClause, py: Python) -> Self {
+        use fastobo::ast::InstanceClause::*;
+        match clause {
+            IsAnonymous(b) =>
+                Py::new(py, IsAnonymousClause::new(py, b))
+                    .map(InstanceClause::IsAnonymous),
+            Name(n) =>
+                Py::new(py, NameClause::new(py, n))
+                    .map(InstanceClause::Name),
+            Namespace(ns) =>
+                Py::new(py, NamespaceClause::new(py, ns))
+                    .map(InstanceClause::Namespace),
+            AltId(id) =>
+                Py::new(py, AltIdClause::new(py, id))
+                    .map(InstanceClause::AltId),
+            Def(desc, xrefs) =>
+                Py::new(py, DefClause::new(py, desc, xrefs))
+                    .map(InstanceClause::Def),
+            Comment(c) =>
+                Py::new(py, CommentClause::new(py, c))
+                    .map(InstanceClause::Comment),
+            Subset(s) =>
+                Py::new(py, SubsetClause::new(py, s))
+                    .map(InstanceClause::Subset),
+            Synonym(s) =>
+                Py::new(py, SynonymClause::new(py, s))
+                    .map(InstanceClause::Synonym),
+            Xref(x) =>
+                Py::new(py, XrefClause::new(py, x))
+                    .map(InstanceClause::Xref),
+            PropertyValue(pv) =>
+                Py::new(py, PropertyValueClause::new(py, pv))
+                    .map(InstanceClause::PropertyValue),
+            InstanceOf(cls) =>
+                Py::new(py, InstanceOfClause::new(py, cls))
+                    .map(InstanceClause::InstanceOf),
+            Relationship(r, id) =>
+                Py::new(py, RelationshipClause::new(py, r, id))
+                    .map(InstanceClause::Relationship),
+            CreatedBy(name) =>
+                Py::new(py, CreatedByClause::new(py, name))
+                    .map(InstanceClause::CreatedBy),
+            CreationDate(dt) =>
+                Py::new(py, CreationDateClause::new(py, dt))
+                    .map(InstanceClause::CreationDate),
+            IsObsolete(b) =>
+                Py::new(py, IsObsoleteClause::new(py, b))
+                    .map(InstanceClause::IsObsolete),
+            ReplacedBy(id) =>
+                Py::new(py, ReplacedByClause::new(py, id))
+                    .map(InstanceClause::ReplacedBy),
+            Consider(id) =>
+                Py::new(py, ConsiderClause::new(py, id))
+                    .map(InstanceClause::Consider),
+        }.expect("could not allocate memory for `InstanceClause` in Python heap")
+    }
+}
+
+// --- Base ------------------------------------------------------------------
+
+/// An instance clause, appearing in an instance frame.
+#[pyclass(subclass)]
+pub struct BaseInstanceClause {}
+
+// --- IsAnonymous -----------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct IsAnonymousClause {
+    #[pyo3(get, set)]
+    anonymous: bool
+}
+
+impl IsAnonymousClause {
+    pub fn new(_py: Python, anonymous: bool) -> Self {
+        Self { anonymous }
+    }
+}
+
+impl FromPy<IsAnonymousClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: IsAnonymousClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::IsAnonymous(clause.anonymous)
+    }
+}
+
+// --- Name ------------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct NameClause {
+    name: fastobo::ast::UnquotedString,
+}
+
+impl NameClause {
+    pub fn new(_py: Python, name: fastobo::ast::UnquotedString) -> Self {
+        Self { name }
+    }
+}
+
+impl FromPy<NameClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: NameClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::Name(clause.name)
+    }
+}
+
+// --- Namespace -------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct NamespaceClause {
+    namespace: Ident
+}
+
+impl NamespaceClause {
+    pub fn new<I>(py: Python, ns: I) -> Self
+    where
+        I: IntoPy<Ident>
+    {
+        Self { namespace: ns.into_py(py) }
+    }
+}
+
+impl ClonePy for NamespaceClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            namespace: self.namespace.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<NamespaceClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: NamespaceClause, py: Python) -> Self {
+        let ns = fastobo::ast::NamespaceIdent::from_py(clause.namespace, py);
+        fastobo::ast::InstanceClause::Namespace(ns)
+    }
+}
+
+// --- AltId -----------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct AltIdClause {
+    id: Ident,
+}
+
+impl AltIdClause {
+    pub fn new<I>(py: Python, id: I) -> Self
+    where
+        I: IntoPy<Ident>,
+    {
+        Self { id: id.into_py(py) }
+    }
+}
+
+impl ClonePy for AltIdClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            id: self.id.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<AltIdClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: AltIdClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::AltId(clause.id.into_py(py))
+    }
+}
+
+// --- Def -------------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct DefClause {
+    definition: fastobo::ast::QuotedString,
+    xrefs: XrefList,
+}
+
+impl DefClause {
+    pub fn new<X>(py: Python, definition: fastobo::ast::QuotedString, xrefs: X) -> Self
+    where
+        X: IntoPy<XrefList>,
+    {
+        Self { definition, xrefs: xrefs.into_py(py) }
+    }
+}
+
+impl ClonePy for DefClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            definition: self.definition.clone(),
+            xrefs: self.xrefs.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<DefClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: DefClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::Def(
+            clause.definition,
+            clause.xrefs.into_py(py)
+        )
+    }
+}
+
+// --- Comment ---------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct CommentClause {
+    comment: fastobo::ast::UnquotedString
+}
+
+impl CommentClause {
+    pub fn new(_py: Python, comment: fastobo::ast::UnquotedString) -> Self {
+        Self { comment }
+    }
+}
+
+impl FromPy<CommentClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: CommentClause, _py: Python) -> Self {
+        fastobo::ast::InstanceClause::Comment(clause.comment)
+    }
+}
+
+// --- Subset ----------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct SubsetClause {
+    subset: Ident
+}
+
+impl SubsetClause {
+    pub fn new<I>(py: Python, subset: I) -> Self
+    where
+        I: IntoPy<Ident>
+    {
+        Self { subset: subset.into_py(py) }
+    }
+}
+
+impl ClonePy for SubsetClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            subset: self.subset.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<SubsetClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: SubsetClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::Subset(clause.subset.into_py(py))
+    }
+}
+
+// --- Synonym ---------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct SynonymClause {
+    synonym: Synonym,
+}
+
+impl SynonymClause {
+    pub fn new<S>(py: Python, synonym: S) -> Self
+    where
+        S: IntoPy<Synonym>,
+    {
+        Self {
+            synonym: synonym.into_py(py)
+        }
+    }
+}
+
+impl ClonePy for SynonymClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            synonym: self.synonym.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<SynonymClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: SynonymClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::Synonym(clause.synonym.into_py(py))
+    }
+}
+
+// --- Xref ------------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct XrefClause {
+    xref: Py<Xref>
+}
+
+impl XrefClause {
+    pub fn new<X>(py: Python, xref: X) -> Self
+    where
+        X: IntoPy<Xref>,
+    {
+        Self::from_py(xref.into_py(py), py)
+    }
+}
+
+impl ClonePy for XrefClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            xref: self.xref.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<XrefClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: XrefClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::Xref(
+            clause.xref.as_ref(py).clone_py(py).into_py(py)
+        )
+    }
+}
+
+impl From<Py<Xref>> for XrefClause {
+    fn from(xref: Py<Xref>) -> Self {
+        Self { xref }
+    }
+}
+
+impl FromPy<Xref> for XrefClause {
+    fn from_py(xref: Xref, py: Python) -> Self {
+        Self {
+            xref: Py::new(py, xref)
+                .expect("could not allocate memory on Python heap for XrefClause")
+        }
+    }
+}
+
+#[pymethods]
+impl XrefClause {
+    #[new]
+    fn __init__(obj: &PyRawObject, xref: &PyAny) -> PyResult<()> {
+        Xref::from_object(obj.py(), xref).map(|x| obj.init(Self::from(x)))
+    }
+
+    #[getter]
+    fn get_xref(&self) -> PyResult<Py<Xref>> {
+        let py = unsafe { Python::assume_gil_acquired() };
+        Ok(self.xref.clone_ref(py))
+    }
+
+    #[setter]
+    fn set_ref(&mut self, xref: &PyAny) -> PyResult<()> {
+        self.xref = Xref::from_object(xref.py(), xref)?;
+        Ok(())
+    }
+}
+
+// --- PropertyValue ---------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct PropertyValueClause {
+    inner: PropertyValue,
+}
+
+impl PropertyValueClause {
+    pub fn new<P>(py: Python, property_value: P) -> Self
+    where
+        P: IntoPy<PropertyValue>
+    {
+        Self { inner: property_value.into_py(py) }
+    }
+}
+
+impl ClonePy for PropertyValueClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            inner: self.inner.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<PropertyValueClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: PropertyValueClause, py: Python) -> ast::InstanceClause {
+        ast::InstanceClause::PropertyValue(clause.inner.into_py(py))
+    }
+}
+
+// --- InstanceOf ------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct InstanceOfClause {
+    class: Ident,
+}
+
+impl InstanceOfClause {
+    pub fn new<I>(py: Python, class: I) -> Self
+    where
+        I: IntoPy<Ident>
+    {
+        Self { class: class.into_py(py) }
+    }
+}
+
+impl ClonePy for InstanceOfClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            class: self.class.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<InstanceOfClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: InstanceOfClause, py: Python) -> fastobo::ast::InstanceClause {
+        ast::InstanceClause::InstanceOf(clause.class.into_py(py))
+    }
+}
+
+// --- Relationship ----------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct RelationshipClause {
+    relation: Ident,
+    instance: Ident
+}
+
+impl RelationshipClause {
+    pub fn new<R, T>(py: Python, relation: R, instance: T) -> Self
+    where
+        R: IntoPy<Ident>,
+        T: IntoPy<Ident>,
+    {
+        Self { relation: relation.into_py(py), instance: instance.into_py(py) }
+    }
+}
+
+impl ClonePy for RelationshipClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            relation: self.relation.clone_py(py),
+            instance: self.instance.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<RelationshipClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: RelationshipClause, py: Python) -> fastobo::ast::InstanceClause {
+        ast::InstanceClause::Relationship(
+            clause.relation.into_py(py),
+            clause.instance.into_py(py)
+        )
+    }
+}
+
+// --- CreatedBy -------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct CreatedByClause {
+    name: fastobo::ast::UnquotedString
+}
+
+impl CreatedByClause {
+    pub fn new(_py: Python, name: fastobo::ast::UnquotedString) -> Self {
+        Self { name }
+    }
+}
+
+impl FromPy<CreatedByClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: CreatedByClause, py: Python) -> fastobo::ast::InstanceClause {
+        fastobo::ast::InstanceClause::CreatedBy(clause.name)
+    }
+}
+
+// --- CreationDate ----------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct CreationDateClause {
+    date: fastobo::ast::IsoDateTime,
+}
+
+impl CreationDateClause {
+    pub fn new(_py: Python, date: fastobo::ast::IsoDateTime) -> Self {
+        Self { date }
+    }
+}
+
+impl FromPy<CreationDateClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: CreationDateClause, py: Python) -> fastobo::ast::InstanceClause {
+        fastobo::ast::InstanceClause::CreationDate(clause.date)
+    }
+}
+
+// --- IsObsolete ------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct IsObsoleteClause {
+    #[pyo3(get, set)]
+    obsolete: bool
+}
+
+impl IsObsoleteClause {
+    pub fn new(_py: Python, obsolete: bool) -> Self {
+        Self { obsolete }
+    }
+}
+
+impl FromPy<IsObsoleteClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: IsObsoleteClause, py: Python) -> Self {
+        fastobo::ast::InstanceClause::IsObsolete(clause.obsolete)
+    }
+}
+
+// --- ReplacedBy ------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct ReplacedByClause {
+    instance: Ident,
+}
+
+impl ReplacedByClause {
+    pub fn new<I>(py: Python, instance: I) -> Self
+    where
+        I: IntoPy<Ident>
+    {
+        Self { instance: instance.into_py(py) }
+    }
+}
+
+impl ClonePy for ReplacedByClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            instance: self.instance.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<ReplacedByClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: ReplacedByClause, py: Python) -> fastobo::ast::InstanceClause {
+        ast::InstanceClause::ReplacedBy(clause.instance.into_py(py))
+    }
+}
+
+// --- Consider --------------------------------------------------------------
+
+#[pyclass(extends=BaseInstanceClause)]
+#[derive(Debug)]
+pub struct ConsiderClause {
+    instance: Ident,
+}
+
+impl ConsiderClause {
+    pub fn new<I>(py: Python, instance: I) -> Self
+    where
+        I: IntoPy<Ident>
+    {
+        Self { instance: instance.into_py(py) }
+    }
+}
+
+impl ClonePy for ConsiderClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            instance: self.instance.clone_py(py)
+        }
+    }
+}
+
+impl FromPy<ConsiderClause> for fastobo::ast::InstanceClause {
+    fn from_py(clause: ConsiderClause, py: Python) -> fastobo::ast::InstanceClause {
+        ast::InstanceClause::Consider(clause.instance.into_py(py))
+    }
+}
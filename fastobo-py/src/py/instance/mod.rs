@@ -0,0 +1,28 @@
+pub mod clause;
+pub mod frame;
+
+use pyo3::prelude::*;
+
+#[pymodule(instance)]
+pub fn module(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<self::frame::InstanceFrame>()?;
+    m.add_class::<self::clause::BaseInstanceClause>()?;
+    m.add_class::<self::clause::IsAnonymousClause>()?;
+    m.add_class::<self::clause::NameClause>()?;
+    m.add_class::<self::clause::NamespaceClause>()?;
+    m.add_class::<self::clause::AltIdClause>()?;
+    m.add_class::<self::clause::DefClause>()?;
+    m.add_class::<self::clause::CommentClause>()?;
+    m.add_class::<self::clause::SubsetClause>()?;
+    m.add_class::<self::clause::SynonymClause>()?;
+    m.add_class::<self::clause::XrefClause>()?;
+    m.add_class::<self::clause::PropertyValueClause>()?;
+    m.add_class::<self::clause::InstanceOfClause>()?;
+    m.add_class::<self::clause::RelationshipClause>()?;
+    m.add_class::<self::clause::CreatedByClause>()?;
+    m.add_class::<self::clause::CreationDateClause>()?;
+    m.add_class::<self::clause::IsObsoleteClause>()?;
+    m.add_class::<self::clause::ReplacedByClause>()?;
+    m.add_class::<self::clause::ConsiderClause>()?;
+    Ok(())
+}
@@ -36,6 +36,7 @@ pub mod entity;
 pub mod doc;
 pub mod header;
 pub mod id;
+pub mod instance;
 pub mod term;
 pub mod typedef;
 pub mod pv;
@@ -46,6 +47,7 @@ use self::header::PyInit_header;
 use self::typedef::PyInit_typedef;
 use self::term::PyInit_term;
 use self::id::PyInit_id;
+use self::instance::PyInit_instance;
 use self::syn::PyInit_syn;
 use self::pv::PyInit_pv;
 use self::xref::PyInit_xref;
@@ -62,6 +64,7 @@ fn fastobo(py: Python, m: &PyModule) -> PyResult<()> {
 
     m.add_wrapped(pyo3::wrap_pymodule!(header))?;
     m.add_wrapped(pyo3::wrap_pymodule!(id))?;
+    m.add_wrapped(pyo3::wrap_pymodule!(instance))?;
     m.add_wrapped(pyo3::wrap_pymodule!(pv))?;
     m.add_wrapped(pyo3::wrap_pymodule!(syn))?;
     m.add_wrapped(pyo3::wrap_pymodule!(term))?;
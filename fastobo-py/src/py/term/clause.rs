@@ -49,7 +49,11 @@ pub enum TermClause {
     ReplacedBy(Py<ReplacedByClause>),
     Consider(Py<ConsiderClause>),
     CreatedBy(Py<CreatedByClause>),
-    CreationDate(Py<CreationDateClause>)
+    CreationDate(Py<CreationDateClause>),
+    ExpandAssertionTo(Py<ExpandAssertionToClause>),
+    ExpandExpressionTo(Py<ExpandExpressionToClause>),
+    IsMetadataTag(Py<IsMetadataTagClause>),
+    IsClassLevel(Py<IsClassLevelClause>),
 }
 
 impl FromPy<fastobo::ast::TermClause> for TermClause {
@@ -122,6 +126,18 @@ impl FromPy<fastobo::ast::TermClause> for TermClause {
             CreationDate(dt) =>
                 Py::new(py, CreationDateClause::new(py, dt))
                     .map(TermClause::CreationDate),
+            ExpandAssertionTo(d, xrefs) =>
+                Py::new(py, ExpandAssertionToClause::new(py, d, xrefs))
+                    .map(TermClause::ExpandAssertionTo),
+            ExpandExpressionTo(d, xrefs) =>
+                Py::new(py, ExpandExpressionToClause::new(py, d, xrefs))
+                    .map(TermClause::ExpandExpressionTo),
+            IsMetadataTag(b) =>
+                Py::new(py, IsMetadataTagClause::new(py, b))
+                    .map(TermClause::IsMetadataTag),
+            IsClassLevel(b) =>
+                Py::new(py, IsClassLevelClause::new(py, b))
+                    .map(TermClause::IsClassLevel),
         }.expect("could not allocate memory for `TermClause` in Python heap")
     }
 }
@@ -803,3 +819,121 @@ impl FromPy<CreationDateClause> for fastobo::ast::TermClause {
         fastobo::ast::TermClause::CreationDate(clause.date)
     }
 }
+
+// --- ExpandAssertionTo -----------------------------------------------------
+
+#[pyclass(extends=BaseTermClause)]
+#[derive(Debug)]
+pub struct ExpandAssertionToClause {
+    description: fastobo::ast::QuotedString,
+    xrefs: XrefList,
+}
+
+impl ExpandAssertionToClause {
+    pub fn new<X>(py: Python, desc: fastobo::ast::QuotedString, xrefs: X) -> Self
+    where
+        X: IntoPy<XrefList>
+    {
+        Self {
+            description: desc,
+            xrefs: xrefs.into_py(py),
+        }
+    }
+}
+
+impl ClonePy for ExpandAssertionToClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            description: self.description.clone(),
+            xrefs: self.xrefs.clone_py(py),
+        }
+    }
+}
+
+impl FromPy<ExpandAssertionToClause> for fastobo::ast::TermClause {
+    fn from_py(clause: ExpandAssertionToClause, py: Python) -> Self {
+        fastobo::ast::TermClause::ExpandAssertionTo(
+            clause.description,
+            clause.xrefs.into_py(py),
+        )
+    }
+}
+
+// --- ExpandExpressionTo ----------------------------------------------------
+
+#[pyclass(extends=BaseTermClause)]
+#[derive(Debug)]
+pub struct ExpandExpressionToClause {
+    description: fastobo::ast::QuotedString,
+    xrefs: XrefList,
+}
+
+impl ExpandExpressionToClause {
+    pub fn new<X>(py: Python, desc: fastobo::ast::QuotedString, xrefs: X) -> Self
+    where
+        X: IntoPy<XrefList>
+    {
+        Self {
+            description: desc,
+            xrefs: xrefs.into_py(py),
+        }
+    }
+}
+
+impl ClonePy for ExpandExpressionToClause {
+    fn clone_py(&self, py: Python) -> Self {
+        Self {
+            description: self.description.clone(),
+            xrefs: self.xrefs.clone_py(py),
+        }
+    }
+}
+
+impl FromPy<ExpandExpressionToClause> for fastobo::ast::TermClause {
+    fn from_py(clause: ExpandExpressionToClause, py: Python) -> Self {
+        fastobo::ast::TermClause::ExpandExpressionTo(
+            clause.description,
+            clause.xrefs.into_py(py),
+        )
+    }
+}
+
+// --- IsMetadataTag ---------------------------------------------------------
+
+#[pyclass(extends=BaseTermClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct IsMetadataTagClause {
+    metadata_tag: bool
+}
+
+impl IsMetadataTagClause {
+    pub fn new(_py: Python, metadata_tag: bool) -> Self {
+        Self { metadata_tag }
+    }
+}
+
+impl FromPy<IsMetadataTagClause> for fastobo::ast::TermClause {
+    fn from_py(clause: IsMetadataTagClause, py: Python) -> Self {
+        fastobo::ast::TermClause::IsMetadataTag(clause.metadata_tag)
+    }
+}
+
+// --- IsClassLevel ----------------------------------------------------------
+
+#[pyclass(extends=BaseTermClause)]
+#[derive(Clone, ClonePy, Debug)]
+pub struct IsClassLevelClause {
+    class_level: bool
+}
+
+impl IsClassLevelClause {
+    pub fn new(_py: Python, class_level: bool) -> Self {
+        Self { class_level }
+    }
+}
+
+impl FromPy<IsClassLevelClause> for fastobo::ast::TermClause {
+    fn from_py(clause: IsClassLevelClause, py: Python) -> Self {
+        fastobo::ast::TermClause::IsClassLevel(clause.class_level)
+    }
+}
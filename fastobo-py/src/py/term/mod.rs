@@ -29,5 +29,9 @@ pub fn module(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<self::clause::ConsiderClause>()?;
     m.add_class::<self::clause::CreatedByClause>()?;
     m.add_class::<self::clause::CreationDateClause>()?;
+    m.add_class::<self::clause::ExpandAssertionToClause>()?;
+    m.add_class::<self::clause::ExpandExpressionToClause>()?;
+    m.add_class::<self::clause::IsMetadataTagClause>()?;
+    m.add_class::<self::clause::IsClassLevelClause>()?;
     Ok(())
 }
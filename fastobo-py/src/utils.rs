@@ -38,6 +38,18 @@ where
     }
 }
 
+impl<T> ClonePy for fastobo::ast::Line<T>
+where
+    T: ClonePy
+{
+    fn clone_py(&self, py: Python) -> Self {
+        fastobo::ast::Line::from(self.as_ref().clone_py(py))
+            .and_qualifiers(self.qualifiers().cloned())
+            .and_comment(self.comment().cloned())
+            .and_location(self.location().cloned())
+    }
+}
+
 // ---
 
 pub trait AsGILRef<'p, T>: 'p {
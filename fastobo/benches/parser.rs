@@ -0,0 +1,38 @@
+//! Compare the `pest` parser and the hand-written parser on `ms.obo`.
+//!
+//! `OboDoc::from_str` always parses the document with `pest`, while
+//! `OboDoc::from_stream` parses it frame by frame with a `FrameReader`,
+//! which uses the hand-written parser when the `fast-parser` feature is
+//! enabled:
+//! ```console
+//! $ cargo bench --bench parser --features fast-parser
+//! ```
+
+extern crate criterion;
+extern crate fastobo;
+
+use std::str::FromStr;
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use criterion::Throughput;
+
+use fastobo::ast::OboDoc;
+
+fn ms(c: &mut Criterion) {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/data/ms.obo");
+    let text = std::fs::read_to_string(path).unwrap();
+
+    let mut group = c.benchmark_group("ms.obo");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("pest", |b| b.iter(|| OboDoc::from_str(&text).unwrap()));
+    group.bench_function("frames", |b| {
+        b.iter(|| OboDoc::from_stream(&mut text.as_bytes()).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ms);
+criterion_main!(benches);
@@ -0,0 +1,661 @@
+//! `Arbitrary` implementations for the AST, enabled by the `arbitrary` feature.
+//!
+//! Generated values are always syntactically valid: serializing them with
+//! `Display` and parsing them back with `FromStr` gives back the same value.
+//! Identifiers always start with a character of a restricted alphabet, since
+//! some of them could otherwise be read back as a different kind of identifier
+//! (for instance a prefixed identifier with an `http` prefix is parsed as an
+//! URL), but may contain characters that need escaping or are not ASCII.
+
+use ::arbitrary::Arbitrary;
+use ::arbitrary::Result;
+use ::arbitrary::Unstructured;
+use url::Url;
+
+use crate::ast::*;
+
+/// The maximum number of clauses generated in a single frame.
+const MAX_CLAUSES: usize = 8;
+/// The maximum number of entity frames generated in a single document.
+const MAX_FRAMES: usize = 4;
+/// The maximum number of items generated in a list (xrefs, qualifiers).
+const MAX_ITEMS: usize = 3;
+
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const ALNUM: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const PREFIX: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_";
+const LOCAL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_ ";
+const WORD: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789_";
+/// Characters that must be escaped in identifiers, or that are not ASCII.
+const SPECIAL: &[char] = &[' ', '\t', '\n', ':', '"', '!', '\\', 'é', 'λ', '→'];
+
+/// The header tags that cannot be used by unreserved header clauses.
+const RESERVED: &[&str] = &[
+    "format-version",
+    "data-version",
+    "date",
+    "saved-by",
+    "auto-generated-by",
+    "import",
+    "subsetdef",
+    "synonymtypedef",
+    "default-namespace",
+    "idspace",
+    "treat-xrefs-as-equivalent",
+    "treat-xrefs-as-genus-differentia",
+    "treat-xrefs-as-reverse-genus-differentia",
+    "treat-xrefs-as-relationship",
+    "treat-xrefs-as-is_a",
+    "treat-xrefs-as-has-subclass",
+    "property_value",
+    "remark",
+    "ontology",
+    "owl-axioms",
+];
+
+/// Generate a string of `min` to `max` characters from `alphabet`.
+fn chars(u: &mut Unstructured, alphabet: &[u8], min: usize, max: usize) -> Result<String> {
+    let len = u.int_in_range(min..=max)?;
+    (0..len).map(|_| u.choose(alphabet).map(|&c| char::from(c))).collect()
+}
+
+/// Generate a word starting with a character from `first`.
+fn word(u: &mut Unstructured, first: &[u8], rest: &[u8]) -> Result<String> {
+    let mut word = chars(u, first, 1, 1)?;
+    word.push_str(&chars(u, rest, 0, 8)?);
+    Ok(word)
+}
+
+/// Generate an identifier component starting with a character from `first`.
+///
+/// The other characters are mostly taken from `rest`, but sometimes from
+/// `SPECIAL`, so that escaped and non-ASCII characters are also generated.
+fn ident(u: &mut Unstructured, first: &[u8], rest: &[u8]) -> Result<String> {
+    let mut ident = chars(u, first, 1, 1)?;
+    for _ in 0..u.int_in_range(0..=8)? {
+        if u.ratio(1, 8)? {
+            ident.push(*u.choose(SPECIAL)?);
+        } else {
+            ident.push(char::from(*u.choose(rest)?));
+        }
+    }
+    Ok(ident)
+}
+
+/// Generate an URL that can be used as an identifier or an IRI.
+fn url(u: &mut Unstructured) -> Result<Url> {
+    let scheme = u.choose(&["http", "https"])?;
+    let host = word(u, LOWER, WORD)?;
+    let path = word(u, ALNUM, WORD)?;
+    let url = format!("{}://{}.org/{}", scheme, host, path);
+    Ok(Url::parse(&url).expect("generated URL is valid"))
+}
+
+/// Generate an unquoted string, which cannot start or end with whitespace.
+fn text(u: &mut Unstructured) -> Result<String> {
+    let s = String::arbitrary(u)?;
+    let s = s.trim_matches(&[' ', '\t'][..]);
+    if s.is_empty() {
+        word(u, ALNUM, WORD)
+    } else {
+        Ok(s.to_string())
+    }
+}
+
+/// Generate a vector of at most `max` values.
+fn vec<'a, T>(u: &mut Unstructured<'a>, min: usize, max: usize) -> Result<Vec<T>>
+where
+    T: Arbitrary<'a>,
+{
+    let len = u.int_in_range(min..=max)?;
+    (0..len).map(|_| T::arbitrary(u)).collect()
+}
+
+/// Generate the clause lines of a frame.
+///
+/// Qualifiers are removed from lines where `unquoted` returns `true`, since
+/// they would be parsed back as part of the unquoted string ending the clause.
+fn lines<'a, T, F>(u: &mut Unstructured<'a>, unquoted: F) -> Result<Vec<Line<T>>>
+where
+    T: Arbitrary<'a>,
+    F: Fn(&T) -> bool,
+{
+    vec::<Line<T>>(u, 0, MAX_CLAUSES)?
+        .into_iter()
+        .map(|line| match unquoted(line.as_ref()) {
+            true => Ok(line.and_qualifiers(None)),
+            false => Ok(line),
+        })
+        .collect()
+}
+
+// --- Identifiers -----------------------------------------------------------
+
+impl<'a> Arbitrary<'a> for IdentPrefix {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        ident(u, UPPER, PREFIX).map(IdentPrefix::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for IdentLocal {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        ident(u, ALNUM, LOCAL).map(IdentLocal::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for PrefixedIdent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let prefix = IdentPrefix::arbitrary(u)?;
+        let local = IdentLocal::arbitrary(u)?;
+        Ok(PrefixedIdent::new(prefix, local))
+    }
+}
+
+impl<'a> Arbitrary<'a> for UnprefixedIdent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        ident(u, LOWER, LOCAL).map(UnprefixedIdent::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Ident {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=2u8)? {
+            0 => PrefixedIdent::arbitrary(u).map(Ident::Prefixed),
+            1 => UnprefixedIdent::arbitrary(u).map(Ident::Unprefixed),
+            _ => url(u).map(Ident::Url),
+        }
+    }
+}
+
+macro_rules! arbitrary_subclass {
+    ($($name:ident),*) => {
+        $(
+            impl<'a> Arbitrary<'a> for $name {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    Ident::arbitrary(u).map($name::new)
+                }
+            }
+        )*
+    };
+}
+
+arbitrary_subclass!(
+    ClassIdent,
+    InstanceIdent,
+    NamespaceIdent,
+    RelationIdent,
+    SubsetIdent,
+    SynonymTypeIdent
+);
+
+// --- Strings and values ----------------------------------------------------
+
+impl<'a> Arbitrary<'a> for QuotedString {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        String::arbitrary(u).map(QuotedString::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for UnquotedString {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        text(u).map(UnquotedString::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Xref {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let id = Ident::arbitrary(u)?;
+        match Option::<QuotedString>::arbitrary(u)? {
+            Some(desc) => Ok(Xref::with_desc(id, desc)),
+            None => Ok(Xref::new(id)),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for XrefList {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        vec(u, 0, MAX_ITEMS).map(XrefList::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for SynonymScope {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[
+            SynonymScope::Exact,
+            SynonymScope::Broad,
+            SynonymScope::Narrow,
+            SynonymScope::Related,
+        ])
+        .cloned()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Synonym {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Synonym::with_type_and_xrefs(
+            QuotedString::arbitrary(u)?,
+            SynonymScope::arbitrary(u)?,
+            Option::<SynonymTypeIdent>::arbitrary(u)?,
+            XrefList::arbitrary(u)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for PropertyValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let relation = RelationIdent::arbitrary(u)?;
+        if u.arbitrary()? {
+            let value = QuotedString::arbitrary(u)?;
+            let local = word(u, ALNUM, ALNUM)?;
+            let datatype = PrefixedIdent::new(IdentPrefix::new("xsd"), IdentLocal::new(local));
+            Ok(PropertyValue::Typed(relation, value, Ident::from(datatype)))
+        } else {
+            Ident::arbitrary(u).map(|id| PropertyValue::Identified(relation, id))
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Import {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // NB: a prefixed identifier would be parsed back as an IRI.
+        if u.arbitrary()? {
+            url(u).map(Import::Url)
+        } else {
+            UnprefixedIdent::arbitrary(u).map(|id| Import::Abbreviated(Ident::from(id)))
+        }
+    }
+}
+
+// --- Dates -----------------------------------------------------------------
+
+impl<'a> Arbitrary<'a> for NaiveDateTime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let year = u.int_in_range(0..=9999)?;
+        let month = u.int_in_range(1..=12)?;
+        Ok(NaiveDateTime::new(
+            u.int_in_range(1..=days_in_month(year, month))?,
+            month,
+            year,
+            u.int_in_range(0..=23)?,
+            u.int_in_range(0..=59)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for IsoTimezone {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=2u8)? {
+            0 => Ok(IsoTimezone::Utc),
+            1 => Ok(IsoTimezone::Plus(u.int_in_range(0..=23)?, u.int_in_range(0..=59)?)),
+            _ => Ok(IsoTimezone::Minus(u.int_in_range(0..=23)?, u.int_in_range(0..=59)?)),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for IsoDateTime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let year = u.int_in_range(0..=9999)?;
+        let month = u.int_in_range(1..=12)?;
+        let datetime = IsoDateTime::new(
+            u.int_in_range(1..=days_in_month(year, month))?,
+            month,
+            year,
+            u.int_in_range(0..=23)?,
+            u.int_in_range(0..=59)?,
+            u.int_in_range(0..=59)?,
+        );
+        Ok(datetime.and_timezone(Option::<IsoTimezone>::arbitrary(u)?))
+    }
+}
+
+// --- Lines -----------------------------------------------------------------
+
+impl<'a> Arbitrary<'a> for Qualifier {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Qualifier::new(
+            RelationIdent::arbitrary(u)?,
+            QuotedString::arbitrary(u)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for QualifierList {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        vec(u, 1, MAX_ITEMS).map(QualifierList::new)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Comment {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let s = String::arbitrary(u)?;
+        let s = s.replace(&['\r', '\n', '\u{000c}'][..], "");
+        Ok(Comment::new(s.trim()))
+    }
+}
+
+/// Generate a line with optional qualifiers and comment.
+///
+/// Qualifiers following an unquoted string cannot be parsed back, so frames
+/// never generate them on clauses ending with one.
+impl<'a, T> Arbitrary<'a> for Line<T>
+where
+    T: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Line::from(T::arbitrary(u)?)
+            .and_qualifiers(Option::<QualifierList>::arbitrary(u)?)
+            .and_comment(Option::<Comment>::arbitrary(u)?))
+    }
+}
+
+// --- Header ----------------------------------------------------------------
+
+impl<'a> Arbitrary<'a> for HeaderClause {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::HeaderClause::*;
+        Ok(match u.int_in_range(0..=20u8)? {
+            0 => FormatVersion(u.arbitrary()?),
+            1 => DataVersion(u.arbitrary()?),
+            2 => Date(u.arbitrary()?),
+            3 => SavedBy(u.arbitrary()?),
+            4 => AutoGeneratedBy(u.arbitrary()?),
+            5 => Import(u.arbitrary()?),
+            6 => Subsetdef(u.arbitrary()?, u.arbitrary()?),
+            7 => SynonymTypedef(u.arbitrary()?, u.arbitrary()?, u.arbitrary()?),
+            8 => DefaultNamespace(u.arbitrary()?),
+            9 => Idspace(u.arbitrary()?, url(u)?, u.arbitrary()?),
+            10 => TreatXrefsAsEquivalent(u.arbitrary()?),
+            11 => TreatXrefsAsGenusDifferentia(u.arbitrary()?, u.arbitrary()?, u.arbitrary()?),
+            12 => TreatXrefsAsReverseGenusDifferentia(
+                u.arbitrary()?,
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ),
+            13 => TreatXrefsAsRelationship(u.arbitrary()?, u.arbitrary()?),
+            14 => TreatXrefsAsIsA(u.arbitrary()?),
+            15 => TreatXrefsAsHasSubclass(u.arbitrary()?),
+            16 => PropertyValue(u.arbitrary()?),
+            17 => Remark(u.arbitrary()?),
+            18 => Ontology(u.arbitrary()?),
+            19 => OwlAxioms(u.arbitrary()?),
+            _ => {
+                let mut tag = word(u, LOWER, b"abcdefghijklmnopqrstuvwxyz_-")?;
+                if RESERVED.contains(&tag.as_str()) {
+                    tag.insert_str(0, "x-");
+                }
+                Unreserved(UnquotedString::new(tag), u.arbitrary()?)
+            }
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for HeaderFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::HeaderClause::*;
+        let clauses = lines(u, |clause| {
+            matches!(
+                clause,
+                FormatVersion(_) | DataVersion(_) | SavedBy(_) | AutoGeneratedBy(_)
+                    | Remark(_) | Ontology(_) | OwlAxioms(_) | Unreserved(_, _)
+            )
+        })?;
+        // Keep only the first occurrence of the clauses that must be unique.
+        let mut kinds = Vec::new();
+        let clauses = clauses.into_iter().filter(|line| {
+            let kind = std::mem::discriminant(line.as_ref());
+            if is_unique_clause(line.as_ref()) && kinds.contains(&kind) {
+                false
+            } else {
+                kinds.push(kind);
+                true
+            }
+        });
+        Ok(HeaderFrame::new(clauses.collect()))
+    }
+}
+
+// --- Entities --------------------------------------------------------------
+
+impl<'a> Arbitrary<'a> for TermClause {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::TermClause::*;
+        Ok(match u.int_in_range(0..=25u8)? {
+            0 => IsAnonymous(u.arbitrary()?),
+            1 => Name(u.arbitrary()?),
+            2 => Namespace(u.arbitrary()?),
+            3 => AltId(u.arbitrary()?),
+            4 => Def(u.arbitrary()?, u.arbitrary()?),
+            5 => Comment(u.arbitrary()?),
+            6 => Subset(u.arbitrary()?),
+            7 => Synonym(u.arbitrary()?),
+            8 => Xref(u.arbitrary()?),
+            9 => Builtin(u.arbitrary()?),
+            10 => PropertyValue(u.arbitrary()?),
+            11 => IsA(u.arbitrary()?),
+            12 => IntersectionOf(u.arbitrary()?, u.arbitrary()?),
+            13 => UnionOf(u.arbitrary()?),
+            14 => EquivalentTo(u.arbitrary()?),
+            15 => DisjointFrom(u.arbitrary()?),
+            16 => Relationship(u.arbitrary()?, u.arbitrary()?),
+            17 => IsObsolete(u.arbitrary()?),
+            18 => ReplacedBy(u.arbitrary()?),
+            19 => Consider(u.arbitrary()?),
+            20 => CreatedBy(u.arbitrary()?),
+            21 => CreationDate(u.arbitrary()?),
+            22 => ExpandAssertionTo(u.arbitrary()?, u.arbitrary()?),
+            23 => ExpandExpressionTo(u.arbitrary()?, u.arbitrary()?),
+            24 => IsMetadataTag(u.arbitrary()?),
+            _ => IsClassLevel(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for TermFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::TermClause::*;
+        let id = Line::<ClassIdent>::arbitrary(u)?;
+        let clauses = lines(u, |clause| matches!(clause, Name(_) | Comment(_) | CreatedBy(_)))?;
+        Ok(TermFrame::with_clauses(id, clauses))
+    }
+}
+
+impl<'a> Arbitrary<'a> for TypedefClause {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::TypedefClause::*;
+        Ok(match u.int_in_range(0..=39u8)? {
+            0 => IsAnonymous(u.arbitrary()?),
+            1 => Name(u.arbitrary()?),
+            2 => Namespace(u.arbitrary()?),
+            3 => AltId(u.arbitrary()?),
+            4 => Def(u.arbitrary()?, u.arbitrary()?),
+            5 => Comment(u.arbitrary()?),
+            6 => Subset(u.arbitrary()?),
+            7 => Synonym(u.arbitrary()?),
+            8 => Xref(u.arbitrary()?),
+            9 => PropertyValue(u.arbitrary()?),
+            10 => Domain(u.arbitrary()?),
+            11 => Range(u.arbitrary()?),
+            12 => Builtin(u.arbitrary()?),
+            13 => HoldsOverChain(u.arbitrary()?, u.arbitrary()?),
+            14 => IsAntiSymmetric(u.arbitrary()?),
+            15 => IsCyclic(u.arbitrary()?),
+            16 => IsReflexive(u.arbitrary()?),
+            17 => IsSymmetric(u.arbitrary()?),
+            18 => IsTransitive(u.arbitrary()?),
+            19 => IsFunctional(u.arbitrary()?),
+            20 => IsInverseFunctional(u.arbitrary()?),
+            21 => IsA(u.arbitrary()?),
+            22 => IntersectionOf(u.arbitrary()?),
+            23 => UnionOf(u.arbitrary()?),
+            24 => EquivalentTo(u.arbitrary()?),
+            25 => DisjointFrom(u.arbitrary()?),
+            26 => InverseOf(u.arbitrary()?),
+            27 => TransitiveOver(u.arbitrary()?),
+            28 => EquivalentToChain(u.arbitrary()?, u.arbitrary()?),
+            29 => DisjointOver(u.arbitrary()?),
+            30 => Relationship(u.arbitrary()?, u.arbitrary()?),
+            31 => IsObsolete(u.arbitrary()?),
+            32 => ReplacedBy(u.arbitrary()?),
+            33 => Consider(u.arbitrary()?),
+            34 => CreatedBy(u.arbitrary()?),
+            35 => CreationDate(u.arbitrary()?),
+            36 => ExpandAssertionTo(u.arbitrary()?, u.arbitrary()?),
+            37 => ExpandExpressionTo(u.arbitrary()?, u.arbitrary()?),
+            38 => IsMetadataTag(u.arbitrary()?),
+            _ => IsClassLevel(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for TypedefFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::TypedefClause::*;
+        let id = Line::<RelationIdent>::arbitrary(u)?;
+        let clauses = lines(u, |clause| matches!(clause, Name(_) | Comment(_) | CreatedBy(_)))?;
+        Ok(TypedefFrame::with_clauses(id, clauses))
+    }
+}
+
+impl<'a> Arbitrary<'a> for InstanceClause {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::InstanceClause::*;
+        Ok(match u.int_in_range(0..=16u8)? {
+            0 => IsAnonymous(u.arbitrary()?),
+            1 => Name(u.arbitrary()?),
+            2 => Namespace(u.arbitrary()?),
+            3 => AltId(u.arbitrary()?),
+            4 => Def(u.arbitrary()?, u.arbitrary()?),
+            5 => Comment(u.arbitrary()?),
+            6 => Subset(u.arbitrary()?),
+            7 => Synonym(u.arbitrary()?),
+            8 => Xref(u.arbitrary()?),
+            9 => PropertyValue(u.arbitrary()?),
+            10 => InstanceOf(u.arbitrary()?),
+            11 => Relationship(u.arbitrary()?, u.arbitrary()?),
+            12 => CreatedBy(u.arbitrary()?),
+            13 => CreationDate(u.arbitrary()?),
+            14 => IsObsolete(u.arbitrary()?),
+            15 => ReplacedBy(u.arbitrary()?),
+            _ => Consider(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for InstanceFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        use self::InstanceClause::*;
+        let id = Line::<InstanceIdent>::arbitrary(u)?;
+        let clauses = lines(u, |clause| matches!(clause, Name(_) | Comment(_) | CreatedBy(_)))?;
+        Ok(InstanceFrame::with_clauses(id, clauses))
+    }
+}
+
+impl<'a> Arbitrary<'a> for EntityFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=2u8)? {
+            0 => TermFrame::arbitrary(u).map(EntityFrame::Term),
+            1 => TypedefFrame::arbitrary(u).map(EntityFrame::Typedef),
+            _ => InstanceFrame::arbitrary(u).map(EntityFrame::Instance),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for OboDoc {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let header = HeaderFrame::arbitrary(u)?;
+        let entities = vec::<EntityFrame>(u, 0, MAX_FRAMES)?;
+        Ok(OboDoc::with_entities(header, entities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use quickcheck::quickcheck;
+
+    use super::*;
+
+    /// Check that a value generated from `data` survives a roundtrip.
+    fn roundtrip<T>(data: Vec<u8>) -> bool
+    where
+        T: for<'a> Arbitrary<'a> + Display + FromStr + PartialEq,
+    {
+        let mut u = Unstructured::new(&data);
+        match T::arbitrary(&mut u) {
+            Ok(value) => T::from_str(&value.to_string()).ok() == Some(value),
+            Err(_) => true,
+        }
+    }
+
+    #[test]
+    fn special_characters() {
+        let data = (0..4096u32).map(|i| (i * 37 % 251) as u8).collect::<Vec<_>>();
+        let mut u = Unstructured::new(&data);
+        let ids = (0..64)
+            .map(|_| PrefixedIdent::arbitrary(&mut u).unwrap())
+            .collect::<Vec<_>>();
+        assert!(ids.iter().any(|id| id.to_string().contains('\\')));
+        assert!(ids.iter().any(|id| !id.to_string().is_ascii()));
+        for id in ids {
+            assert_eq!(PrefixedIdent::from_str(&id.to_string()).unwrap(), id);
+        }
+    }
+
+    quickcheck! {
+        fn ident(data: Vec<u8>) -> bool {
+            roundtrip::<Ident>(data)
+        }
+
+        fn ident_subclasses(data: Vec<u8>) -> bool {
+            roundtrip::<ClassIdent>(data.clone())
+                && roundtrip::<RelationIdent>(data.clone())
+                && roundtrip::<InstanceIdent>(data.clone())
+                && roundtrip::<SubsetIdent>(data.clone())
+                && roundtrip::<SynonymTypeIdent>(data.clone())
+                && roundtrip::<NamespaceIdent>(data)
+        }
+
+        fn ident_components(data: Vec<u8>) -> bool {
+            roundtrip::<PrefixedIdent>(data.clone())
+                && roundtrip::<UnprefixedIdent>(data.clone())
+                && roundtrip::<IdentPrefix>(data.clone())
+                && roundtrip::<IdentLocal>(data)
+        }
+
+        fn strings(data: Vec<u8>) -> bool {
+            roundtrip::<QuotedString>(data.clone()) && roundtrip::<UnquotedString>(data)
+        }
+
+        fn values(data: Vec<u8>) -> bool {
+            roundtrip::<Xref>(data.clone())
+                && roundtrip::<XrefList>(data.clone())
+                && roundtrip::<Synonym>(data.clone())
+                && roundtrip::<PropertyValue>(data.clone())
+                && roundtrip::<Import>(data.clone())
+                && roundtrip::<QualifierList>(data)
+        }
+
+        fn dates(data: Vec<u8>) -> bool {
+            roundtrip::<NaiveDateTime>(data.clone()) && roundtrip::<IsoDateTime>(data)
+        }
+
+        fn clauses(data: Vec<u8>) -> bool {
+            roundtrip::<HeaderClause>(data.clone())
+                && roundtrip::<TermClause>(data.clone())
+                && roundtrip::<TypedefClause>(data.clone())
+                && roundtrip::<InstanceClause>(data)
+        }
+
+        fn frames(data: Vec<u8>) -> bool {
+            roundtrip::<HeaderFrame>(data.clone()) && roundtrip::<EntityFrame>(data)
+        }
+
+        fn obo_doc(data: Vec<u8>) -> bool {
+            roundtrip::<OboDoc>(data)
+        }
+    }
+}
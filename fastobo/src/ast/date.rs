@@ -6,11 +6,34 @@ use std::str::FromStr;
 
 use pest::iterators::Pair;
 
+use crate::ast::SourceLocation;
 use crate::error::Error;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
 
+/// Get the number of days in a month of the Gregorian calendar.
+pub(crate) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => match (year % 4, year % 100, year % 400) {
+            (_, _, 0) => 29,
+            (0, r, _) if r != 0 => 29,
+            _ => 28,
+        },
+        _ => 0,
+    }
+}
+
+/// Create an `InvalidDate` error for the given pair.
+fn invalid_date(pair: &Pair<Rule>) -> Error {
+    Error::InvalidDate {
+        value: pair.as_str().to_string(),
+        location: SourceLocation::from(pair),
+    }
+}
+
 /// A naive datetime, as found in header frames.
 ///
 /// For historical reasons, OBO headers do not contain ISO datetimes but
@@ -71,20 +94,27 @@ impl Display for NaiveDateTime {
 impl<'i> FromPair<'i> for NaiveDateTime {
     const RULE: Rule = Rule::NaiveDateTime;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
-        let mut inner = pair.into_inner();
+        let mut inner = pair.clone().into_inner();
         let date = inner.next().unwrap();
         let time = inner.next().unwrap();
 
         let datestr = date.as_str();
         let timestr = time.as_str();
 
-        Ok(NaiveDateTime {
+        let datetime = NaiveDateTime {
             day: u8::from_str_radix(&datestr[..2], 10).unwrap(),
             month: u8::from_str_radix(&datestr[3..5], 10).unwrap(),
             year: u16::from_str_radix(&datestr[6..10], 10).unwrap(),
             hour: u8::from_str_radix(&timestr[..2], 10).unwrap(),
             minute: u8::from_str_radix(&timestr[3..5], 10).unwrap(),
-        })
+        };
+
+        // The grammar checks the ranges of every field but the day.
+        if datetime.day > days_in_month(datetime.year, datetime.month) {
+            Err(invalid_date(&pair))
+        } else {
+            Ok(datetime)
+        }
     }
 }
 impl_fromstr!(NaiveDateTime);
@@ -101,12 +131,74 @@ pub struct IsoDateTime {
     timezone: Option<IsoTimezone>,
 }
 
+impl IsoDateTime {
+    /// Create a new `IsoDateTime` without a timezone.
+    ///
+    /// The fields are not validated: use `IsoDateTime::from_str` to reject
+    /// invalid dates and times.
+    pub fn new(day: u8, month: u8, year: u16, hour: u8, minute: u8, second: u8) -> Self {
+        IsoDateTime {
+            day,
+            month,
+            year,
+            hour,
+            minute,
+            second,
+            timezone: None,
+        }
+    }
+
+    /// Set the timezone of the datetime.
+    pub fn and_timezone<T>(mut self, timezone: T) -> Self
+    where
+        T: Into<Option<IsoTimezone>>,
+    {
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// Get the day of the month, starting at 1.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Get the month of the year, starting at 1.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Get the year.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the hour of the day, from 0 to 24.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute of the hour.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Get the second of the minute.
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Get the timezone of the datetime, if any.
+    pub fn timezone(&self) -> Option<&IsoTimezone> {
+        self.timezone.as_ref()
+    }
+}
+
 impl Display for IsoDateTime {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         write!(
             f,
-            "{:02}-{:02}-{:04}T{:02}:{:02}:{:02}",
-            self.day, self.month, self.year, self.hour, self.minute, self.second,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second,
         )?;
         match self.timezone {
             Some(ref tz) => tz.fmt(f),
@@ -118,7 +210,7 @@ impl Display for IsoDateTime {
 impl<'i> FromPair<'i> for IsoDateTime {
     const RULE: Rule = Rule::Iso8601DateTime;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
-        let mut inner = pair.into_inner();
+        let mut inner = pair.clone().into_inner();
         let mut date = inner.next().unwrap().into_inner();
         let mut time = inner.next().unwrap().into_inner();
 
@@ -130,6 +222,15 @@ impl<'i> FromPair<'i> for IsoDateTime {
         let minute = u8::from_str_radix(time.next().unwrap().as_str(), 10).unwrap();
         let second = u8::from_str_radix(time.next().unwrap().as_str(), 10).unwrap();
 
+        // Hours up to 24 are accepted by the grammar, but 24 is only
+        // valid for the end of the day, i.e. `24:00:00`. Invalid months
+        // have no days, so they are rejected with the day.
+        let valid_date = (1..=days_in_month(year, month)).contains(&day);
+        let valid_time = hour < 24 || (minute == 0 && second == 0);
+        if !valid_date || !valid_time {
+            return Err(invalid_date(&pair));
+        }
+
         let timezone = match inner.next() {
             Some(pair) => Some(IsoTimezone::from_pair_unchecked(pair)?),
             None => None,
@@ -204,6 +305,11 @@ mod tests {
         fn from_str() {
             let naive = NaiveDateTime::from_str("12:06:2018 17:13").unwrap();
             assert_eq!(naive, NaiveDateTime::new(12, 6, 2018, 17, 13));
+            assert!(NaiveDateTime::from_str("29:02:2020 00:00").is_ok());
+            match NaiveDateTime::from_str("29:02:2019 00:00") {
+                Err(Error::InvalidDate { value, .. }) => assert_eq!(value, "29:02:2019 00:00"),
+                other => panic!("unexpected result: {:?}", other),
+            }
         }
 
     }
@@ -240,6 +346,20 @@ mod tests {
             }
         }
 
+        #[test]
+        fn invalid_date() {
+            for text in &["2017-13-24T14:41:36Z", "2017-4-31T14:41:36Z", "2017-4-30T24:01:00Z"] {
+                match IsoDateTime::from_str(text) {
+                    Err(Error::InvalidDate { value, location }) => {
+                        assert_eq!(&value, text);
+                        assert_eq!(location.column, 1);
+                    }
+                    other => panic!("unexpected result: {:?}", other),
+                }
+            }
+            assert!(IsoDateTime::from_str("2017-4-30T24:00:00Z").is_ok());
+        }
+
     }
 
 }
@@ -0,0 +1,202 @@
+//! Escaping and unescaping of OBO strings and identifiers.
+//!
+//! The OBO 1.4 syntax uses the same escape sequences everywhere, but the
+//! characters that must be escaped depend on where a value appears: spaces
+//! are only meaningful in identifiers, while quotes only end quoted strings.
+
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+
+use pest::Span;
+
+use crate::ast::SourceLocation;
+use crate::error::Error;
+use crate::error::Result;
+use crate::parser::Rule;
+
+/// Write `s` escaped with the given predicate for characters to escape.
+fn escape_with<W, F>(f: &mut W, s: &str, special: F) -> FmtResult
+where
+    W: Write,
+    F: Fn(char) -> bool,
+{
+    s.chars().try_for_each(|c| match c {
+        '\r' => f.write_str("\\r"),
+        '\n' => f.write_str("\\n"),
+        '\u{000c}' => f.write_str("\\f"),
+        '\\' => f.write_str("\\\\"),
+        '\t' if special(c) => f.write_str("\\t"),
+        _ if special(c) => f.write_char('\\').and(f.write_char(c)),
+        _ => f.write_char(c),
+    })
+}
+
+/// Escape the content of a quoted string.
+pub(crate) fn escape_quoted<W: Write>(f: &mut W, s: &str) -> FmtResult {
+    escape_with(f, s, |c| c == '"')
+}
+
+/// Escape an unquoted string, which ends at the first hidden comment.
+pub(crate) fn escape_unquoted<W: Write>(f: &mut W, s: &str) -> FmtResult {
+    escape_with(f, s, |c| c == '"' || c == '!')
+}
+
+/// Escape a non-canonical identifier component.
+pub(crate) fn escape_ident<W: Write>(f: &mut W, s: &str) -> FmtResult {
+    escape_with(f, s, |c| matches!(c, ' ' | '\t' | ':' | '"' | '!'))
+}
+
+/// Unescape the text of `span` into `f`.
+///
+/// Besides the single character escapes (`\n`, `\t`, `\W`, ...), escaped
+/// unicode code points are written as `\u` followed by 4 hexadecimal digits.
+/// Any other escaped character stands for itself. A trailing backslash or an
+/// invalid code point is reported as an `InvalidEscape` error.
+pub(crate) fn unescape<W: Write>(f: &mut W, span: Span) -> Result<()> {
+    let s = span.as_str();
+    let error = |start: usize, end: usize| -> Error {
+        let span = span.get(start..end).unwrap_or(span);
+        Error::InvalidEscape {
+            value: span.as_str().to_string(),
+            location: SourceLocation::from_span(span),
+        }
+    };
+
+    let mut chars = s.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        if c != '\\' {
+            f.write_char(c).expect("fmt::Write cannot fail on a String");
+            continue;
+        }
+        let c = match chars.next() {
+            Some((_, 'r')) => '\r',
+            Some((_, 'n')) => '\n',
+            Some((_, 'f')) => '\u{000c}',
+            Some((_, 't')) => '\t',
+            Some((_, 'W')) => ' ',
+            Some((_, 'u')) => {
+                // Hexadecimal digits are ASCII, so `digits` is also a byte
+                // count and slicing on it stays on character boundaries.
+                let digits = chars
+                    .clone()
+                    .take(4)
+                    .take_while(|(_, c)| c.is_ascii_hexdigit())
+                    .count();
+                let code = Some(&s[idx + 2..idx + 2 + digits])
+                    .filter(|_| digits == 4)
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(std::char::from_u32);
+                match code {
+                    Some(c) => {
+                        chars.nth(3);
+                        c
+                    }
+                    // Only report the hexadecimal digits of the escape.
+                    None => return Err(error(idx, idx + 2 + digits)),
+                }
+            }
+            Some((_, other)) => other,
+            None => return Err(error(idx, s.len())),
+        };
+        f.write_char(c).expect("fmt::Write cannot fail on a String");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use quickcheck::quickcheck;
+
+    use super::*;
+    use crate::ast::IdentLocal;
+    use crate::ast::IdentPrefix;
+    use crate::ast::QuotedString;
+    use crate::ast::UnprefixedIdent;
+    use crate::ast::UnquotedString;
+
+    fn unescaped(s: &str) -> Result<String> {
+        let mut out = String::new();
+        unescape(&mut out, Span::new(s, 0, s.len()).unwrap()).map(|_| out)
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(unescaped("a\\nb\\tc\\Wd").unwrap(), "a\nb\tc d");
+        assert_eq!(unescaped("\\\"\\\\\\:\\,").unwrap(), "\"\\:,");
+        assert_eq!(unescaped("caf\\u00e9").unwrap(), "café");
+        assert_eq!(unescaped("\\u2018x\\u2019").unwrap(), "\u{2018}x\u{2019}");
+    }
+
+    #[test]
+    fn errors() {
+        for (text, column) in &[("abc\\", 4), ("ab\\u12", 3), ("\\ud800", 1), ("a\\uzzzz", 2)] {
+            match unescaped(text).unwrap_err() {
+                Error::InvalidEscape { location, .. } => {
+                    assert_eq!(location.column, *column, "{}", text)
+                }
+                e => panic!("unexpected error: {:?}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn incomplete_escape_before_multibyte() {
+        match unescaped("\\u123é").unwrap_err() {
+            Error::InvalidEscape { value, .. } => assert_eq!(value, "\\u123"),
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(QuotedString::from_str("\"\\u123é\"").is_err());
+        assert!(UnquotedString::from_str("x\\u123é").is_err());
+        assert!(crate::ast::ClassIdent::from_str("GO:\\u123é").is_err());
+    }
+
+    #[test]
+    fn escape_contexts() {
+        let mut s = String::new();
+        escape_quoted(&mut s, "a \"b\"! c").unwrap();
+        assert_eq!(s, "a \\\"b\\\"! c");
+
+        let mut s = String::new();
+        escape_unquoted(&mut s, "a \"b\"! c").unwrap();
+        assert_eq!(s, "a \\\"b\\\"\\! c");
+
+        let mut s = String::new();
+        escape_ident(&mut s, "a b:c\td").unwrap();
+        assert_eq!(s, "a\\ b\\:c\\td");
+    }
+
+    quickcheck! {
+        fn quoted_string_roundtrip(s: String) -> bool {
+            let string = QuotedString::new(s);
+            QuotedString::from_str(&string.to_string()).ok() == Some(string)
+        }
+
+        fn unquoted_string_roundtrip(s: String) -> bool {
+            let string = UnquotedString::new(s);
+            string.is_empty() || UnquotedString::from_str(&string.to_string()).ok() == Some(string)
+        }
+
+        fn unprefixed_ident_roundtrip(s: String) -> bool {
+            let id = UnprefixedIdent::new(s);
+            id.as_str().is_empty() || UnprefixedIdent::from_str(&id.to_string()).ok() == Some(id)
+        }
+
+        fn ident_prefix_roundtrip(s: String) -> bool {
+            let prefix = IdentPrefix::new(s);
+            IdentPrefix::from_str(&prefix.to_string())
+                .map(|p| p.as_str() == prefix.as_str())
+                .unwrap_or(false)
+        }
+
+        fn ident_local_roundtrip(s: String) -> bool {
+            let local = IdentLocal::new(s);
+            IdentLocal::from_str(&local.to_string())
+                .map(|l| l.as_str() == local.as_str())
+                .unwrap_or(false)
+        }
+    }
+}
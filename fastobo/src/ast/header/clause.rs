@@ -14,6 +14,7 @@ use crate::share::Redeem;
 use crate::error::Error;
 use crate::error::Result;
 use crate::parser::FromPair;
+use crate::parser::FromSlice;
 use crate::parser::Rule;
 
 
@@ -24,6 +25,8 @@ use crate::parser::Rule;
 /// clause, to help with interoperability and to make sure the semantics of
 /// the right OBO specification are in use.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum HeaderClause {
     FormatVersion(UnquotedString),
     DataVersion(UnquotedString),
@@ -32,16 +35,22 @@ pub enum HeaderClause {
     AutoGeneratedBy(UnquotedString),
     Import(Import),
     Subsetdef(SubsetIdent, QuotedString),
+    #[cfg_attr(feature = "serde", serde(rename = "synonymtypedef"))]
     SynonymTypedef(SynonymTypeIdent, QuotedString, Option<SynonymScope>),
     DefaultNamespace(NamespaceIdent),
-    Idspace(IdentPrefix, Url, Option<QuotedString>),
+    Idspace(
+        IdentPrefix,
+        #[cfg_attr(feature = "serde", serde(with = "crate::ast::serde::url_string"))] Url,
+        Option<QuotedString>,
+    ),
     TreatXrefsAsEquivalent(IdentPrefix),
     TreatXrefsAsGenusDifferentia(IdentPrefix, RelationIdent, ClassIdent),
     TreatXrefsAsReverseGenusDifferentia(IdentPrefix, RelationIdent, ClassIdent),
     TreatXrefsAsRelationship(IdentPrefix, RelationIdent),
+    #[cfg_attr(feature = "serde", serde(rename = "treat-xrefs-as-is_a"))]
     TreatXrefsAsIsA(IdentPrefix),
     TreatXrefsAsHasSubclass(IdentPrefix),
-    // FIXME(@althonos): Add support for hidden comment and qualifiers.
+    #[cfg_attr(feature = "serde", serde(rename = "property_value"))]
     PropertyValue(PropertyValue),
     Remark(UnquotedString),
     Ontology(UnquotedString),
@@ -49,18 +58,6 @@ pub enum HeaderClause {
     Unreserved(UnquotedString, UnquotedString),
 }
 
-// impl<'a> Borrow<'a, HeaderClauseRef<'a>> for HeaderClause {
-//     fn borrow(&'a self) -> HeaderClauseRef<'a> {
-//         match self {
-//             HeaderClause::FormatVersion(ref v) =>
-//                 HeaderClauseRef::FormatVersion(Cow::Borrowed(v.borrow())),
-//             HeaderClause::TreatXrefsAsIsA(ref pref) =>
-//                 HeaderClauseRef::TreatXrefsAsIsA(Cow::Borrowed(pref.borrow())),
-//             _ => unimplemented!(),
-//         }
-//     }
-// }
-
 impl Display for HeaderClause {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         use self::HeaderClause::*;
@@ -122,7 +119,7 @@ impl Display for HeaderClause {
                 .and(rel.fmt(f)),
             TreatXrefsAsIsA(prefix) => f.write_str("treat-xrefs-as-is_a: ").and(prefix.fmt(f)),
             TreatXrefsAsHasSubclass(prefix) => f
-                .write_str("treat-xrefs-as-has-subclass")
+                .write_str("treat-xrefs-as-has-subclass: ")
                 .and(prefix.fmt(f)),
             PropertyValue(pv) => f.write_str("property_value: ").and(pv.fmt(f)),
             Remark(remark) => f.write_str("remark: ").and(remark.fmt(f)),
@@ -248,49 +245,292 @@ impl<'i> FromPair<'i> for HeaderClause {
 }
 impl_fromstr!(HeaderClause);
 
-// WIP(@athonos)
-//
-// #[derive(Clone, Debug)]
-// pub enum HeaderClauseRef<'a> {
-//     FormatVersion(Cow<'a, &'a UnquotedStr>),
-//     // DataVersion(Cow<'a, &'a UnquotedStr>),
-//     // Date(Cow<'a, NaiveDateTime>),
-//     // SavedBy(Cow<'a, &'a UnquotedStr>),
-//     // AutoGeneratedBy(Cow<'a, &'a UnquotedStr>),
-//     // Import(Cow<'a, &'a Import>),
-//     // SubsetDef(SubsetId, Cow<'a, &'a UnquotedStr>),
-//     // SynonymTypedef(SynonymTypeId, Cow<'a, &'a UnquotedStr>),
-//
-//
-//     TreatXrefsAsIsA(Cow<'a, IdPrefix<'a>>),
-//
-// }
-//
-// impl<'a> Display for HeaderClauseRef<'a> {
-//     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-//         use self::HeaderClauseRef::*;
-//         match self {
-//             FormatVersion(version) =>
-//                 f.write_str("format-version: ")
-//                     .and(version.fmt(f)),
-//             TreatXrefsAsIsA(prefix) =>
-//                 f.write_str("treat-xrefs-as-is_a: ")
-//                     .and(prefix.fmt(f)),
-//         }
-//     }
-// }
-//
-// impl<'a> ToOwned<'a> for HeaderClauseRef<'a> {
-//     type Owned = HeaderClause;
-//     fn to_owned(&'a self) -> Self::Owned {
-//         match self {
-//             HeaderClauseRef::FormatVersion(ref v) =>
-//                 HeaderClause::FormatVersion(ToOwned::to_owned(v)),
-//             HeaderClauseRef::TreatXrefsAsIsA(ref v) =>
-//                 HeaderClause::TreatXrefsAsIsA(ToOwned::to_owned(v))
-//         }
-//     }
-// }
+impl<'i> FromPair<'i> for Line<HeaderClause> {
+    const RULE: Rule = Rule::HeaderClauseLine;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
+        let mut inner = pair.into_inner();
+        let clause = HeaderClause::from_pair_unchecked(inner.next().unwrap())?;
+        let eol = match inner.next() {
+            Some(pair) => Eol::from_pair_unchecked(pair)?,
+            None => Eol::new(),
+        };
+        Ok(eol.and_inner(clause).and_location(location))
+    }
+}
+impl_fromstr!(Line<HeaderClause>);
+
+/// A borrowed `HeaderClause`.
+#[derive(Clone, Debug)]
+pub enum HeaderClauseRef<'a> {
+    FormatVersion(Cow<'a, &'a UnquotedStr>),
+    DataVersion(Cow<'a, &'a UnquotedStr>),
+    Date(NaiveDateTime),
+    SavedBy(Cow<'a, &'a UnquotedStr>),
+    AutoGeneratedBy(Cow<'a, &'a UnquotedStr>),
+    Import(ImportRef<'a>),
+    Subsetdef(SubsetId<'a>, Cow<'a, &'a QuotedStr>),
+    SynonymTypedef(SynonymTypeId<'a>, Cow<'a, &'a QuotedStr>, Option<SynonymScope>),
+    DefaultNamespace(NamespaceId<'a>),
+    Idspace(Cow<'a, IdPrefix<'a>>, Cow<'a, &'a Url>, Option<Cow<'a, &'a QuotedStr>>),
+    TreatXrefsAsEquivalent(Cow<'a, IdPrefix<'a>>),
+    TreatXrefsAsGenusDifferentia(Cow<'a, IdPrefix<'a>>, RelationId<'a>, ClassId<'a>),
+    TreatXrefsAsReverseGenusDifferentia(Cow<'a, IdPrefix<'a>>, RelationId<'a>, ClassId<'a>),
+    TreatXrefsAsRelationship(Cow<'a, IdPrefix<'a>>, RelationId<'a>),
+    TreatXrefsAsIsA(Cow<'a, IdPrefix<'a>>),
+    TreatXrefsAsHasSubclass(Cow<'a, IdPrefix<'a>>),
+    PropertyValue(PropVal<'a>),
+    Remark(Cow<'a, &'a UnquotedStr>),
+    Ontology(Cow<'a, &'a UnquotedStr>),
+    OwlAxioms(Cow<'a, &'a UnquotedStr>),
+    Unreserved(Cow<'a, &'a UnquotedStr>, Cow<'a, &'a UnquotedStr>),
+}
+
+impl<'a> Display for HeaderClauseRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::HeaderClauseRef::*;
+        match self {
+            FormatVersion(ref version) =>
+                f.write_str("format-version: ").and(version.fmt(f)),
+            DataVersion(version) => f.write_str("data-version: ").and(version.fmt(f)),
+            Date(date) => f.write_str("date: ").and(date.fmt(f)),
+            SavedBy(person) => f.write_str("saved-by: ").and(person.fmt(f)),
+            AutoGeneratedBy(thing) => f.write_str("auto-generated-by: ").and(thing.fmt(f)),
+            Import(import) => f.write_str("import: ").and(import.fmt(f)),
+            Subsetdef(subset, desc) => f
+                .write_str("subsetdef: ")
+                .and(subset.fmt(f))
+                .and(f.write_char(' '))
+                .and(desc.fmt(f)),
+            SynonymTypedef(syntype, desc, optscope) => {
+                f.write_str("synonymtypedef: ")
+                    .and(syntype.fmt(f))
+                    .and(f.write_char(' '))
+                    .and(desc.fmt(f))?;
+                match optscope {
+                    Some(scope) => f.write_char(' ').and(scope.fmt(f)),
+                    None => Ok(()),
+                }
+            }
+            DefaultNamespace(ns) => f.write_str("default-namespace: ").and(ns.fmt(f)),
+            Idspace(prefix, url, optdesc) => {
+                f.write_str("idspace: ")
+                    .and(prefix.fmt(f))
+                    .and(f.write_char(' '))
+                    .and(url.fmt(f))?;
+                match optdesc {
+                    Some(desc) => f.write_char(' ').and(desc.fmt(f)),
+                    None => Ok(()),
+                }
+            }
+            TreatXrefsAsEquivalent(prefix) => f
+                .write_str("treat-xrefs-as-equivalent: ")
+                .and(prefix.fmt(f)),
+            TreatXrefsAsGenusDifferentia(prefix, rel, cls) => f
+                .write_str("treat-xrefs-as-genus-differentia: ")
+                .and(prefix.fmt(f))
+                .and(f.write_char(' '))
+                .and(rel.fmt(f))
+                .and(f.write_char(' '))
+                .and(cls.fmt(f)),
+            TreatXrefsAsReverseGenusDifferentia(prefix, rel, cls) => f
+                .write_str("treat-xrefs-as-reverse-genus-differentia: ")
+                .and(prefix.fmt(f))
+                .and(f.write_char(' '))
+                .and(rel.fmt(f))
+                .and(f.write_char(' '))
+                .and(cls.fmt(f)),
+            TreatXrefsAsRelationship(prefix, rel) => f
+                .write_str("treat-xrefs-as-relationship: ")
+                .and(prefix.fmt(f))
+                .and(f.write_char(' '))
+                .and(rel.fmt(f)),
+            TreatXrefsAsIsA(prefix) => f.write_str("treat-xrefs-as-is_a: ").and(prefix.fmt(f)),
+            TreatXrefsAsHasSubclass(prefix) => f
+                .write_str("treat-xrefs-as-has-subclass: ")
+                .and(prefix.fmt(f)),
+            PropertyValue(pv) => f.write_str("property_value: ").and(pv.fmt(f)),
+            Remark(remark) => f.write_str("remark: ").and(remark.fmt(f)),
+            Ontology(ont) => f.write_str("ontology: ").and(ont.fmt(f)),
+            OwlAxioms(axioms) => f.write_str("owl-axioms: ").and(axioms.fmt(f)),
+            Unreserved(key, value) => key.fmt(f).and(f.write_str(": ")).and(value.fmt(f)),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for HeaderClauseRef<'i> {
+    const RULE: Rule = Rule::HeaderClause;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        let tag = inner.next().unwrap();
+        match tag.as_rule() {
+            Rule::FormatVersionTag => {
+                let version = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::FormatVersion(version))
+            }
+            Rule::DataVersionTag => {
+                let version = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::DataVersion(version))
+            }
+            Rule::DateTag => {
+                let date = NaiveDateTime::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::Date(date))
+            }
+            Rule::SavedByTag => {
+                let person = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::SavedBy(person))
+            }
+            Rule::AutoGeneratedByTag => {
+                let soft = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::AutoGeneratedBy(soft))
+            }
+            Rule::ImportTag => {
+                let import = ImportRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::Import(import))
+            }
+            Rule::SubsetdefTag => {
+                let subset = SubsetId::from_pair_unchecked(inner.next().unwrap())?;
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::Subsetdef(subset, desc))
+            }
+            Rule::SynonymTypedefTag => {
+                let id = SynonymTypeId::from_pair_unchecked(inner.next().unwrap())?;
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let scope = match inner.next() {
+                    Some(pair) => Some(SynonymScope::from_pair_unchecked(pair)?),
+                    None => None,
+                };
+                Ok(HeaderClauseRef::SynonymTypedef(id, desc, scope))
+            }
+            Rule::DefaultNamespaceTag => {
+                let id = NamespaceId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::DefaultNamespace(id))
+            }
+            Rule::IdspaceTag => {
+                let prefix = Cow::<IdPrefix>::from_pair_unchecked(inner.next().unwrap())?;
+                let url = Url::from_pair_unchecked(inner.next().unwrap()).map(Cow::Owned)?;
+                let desc = match inner.next() {
+                    Some(pair) => Some(Cow::<&QuotedStr>::from_pair_unchecked(pair)?),
+                    None => None,
+                };
+                Ok(HeaderClauseRef::Idspace(prefix, url, desc))
+            }
+            Rule::TreatXrefsAsEquivalentTag => {
+                let prefix = Cow::<IdPrefix>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::TreatXrefsAsEquivalent(prefix))
+            }
+            Rule::TreatXrefsAsGenusDifferentiaTag => {
+                let prefix = Cow::<IdPrefix>::from_pair_unchecked(inner.next().unwrap())?;
+                let rel = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                let cls = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::TreatXrefsAsGenusDifferentia(prefix, rel, cls))
+            }
+            Rule::TreatXrefsAsReverseGenusDifferentiaTag => {
+                let prefix = Cow::<IdPrefix>::from_pair_unchecked(inner.next().unwrap())?;
+                let rel = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                let cls = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::TreatXrefsAsReverseGenusDifferentia(
+                    prefix, rel, cls,
+                ))
+            }
+            Rule::TreatXrefsAsRelationshipTag => {
+                let prefix = Cow::<IdPrefix>::from_pair_unchecked(inner.next().unwrap())?;
+                let rel = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::TreatXrefsAsRelationship(prefix, rel))
+            }
+            Rule::TreatXrefsAsIsATag => {
+                let prefix = Cow::<IdPrefix>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::TreatXrefsAsIsA(prefix))
+            }
+            Rule::TreatXrefsAsHasSubclassTag => {
+                let prefix = Cow::<IdPrefix>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::TreatXrefsAsHasSubclass(prefix))
+            }
+            Rule::PropertyValueTag => {
+                let pv = PropVal::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::PropertyValue(pv))
+            }
+            Rule::RemarkTag => {
+                let remark = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::Remark(remark))
+            }
+            Rule::OntologyTag => {
+                let ont = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::Ontology(ont))
+            }
+            Rule::OwlAxiomsTag => {
+                let axioms = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::OwlAxioms(axioms))
+            }
+            Rule::Unreserved => {
+                let tag = Cow::<&UnquotedStr>::from_slice(tag.as_str())?;
+                let value = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(HeaderClauseRef::Unreserved(tag, value))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+impl_fromslice!('i, HeaderClauseRef<'i>);
+
+impl<'i> FromPair<'i> for Line<HeaderClauseRef<'i>> {
+    const RULE: Rule = Rule::HeaderClauseLine;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
+        let mut inner = pair.into_inner();
+        let clause = HeaderClauseRef::from_pair_unchecked(inner.next().unwrap())?;
+        let eol = match inner.next() {
+            Some(pair) => Eol::from_pair_unchecked(pair)?,
+            None => Eol::new(),
+        };
+        Ok(eol.and_inner(clause).and_location(location))
+    }
+}
+impl_fromslice!('i, Line<HeaderClauseRef<'i>>);
+
+impl<'a> Redeem<'a> for HeaderClauseRef<'a> {
+    type Owned = HeaderClause;
+    fn redeem(&'a self) -> HeaderClause {
+        use self::HeaderClauseRef::*;
+        match self {
+            FormatVersion(x) => HeaderClause::FormatVersion(x.redeem()),
+            DataVersion(x) => HeaderClause::DataVersion(x.redeem()),
+            Date(x) => HeaderClause::Date(x.clone()),
+            SavedBy(x) => HeaderClause::SavedBy(x.redeem()),
+            AutoGeneratedBy(x) => HeaderClause::AutoGeneratedBy(x.redeem()),
+            Import(x) => HeaderClause::Import(x.redeem()),
+            Subsetdef(x0, x1) => HeaderClause::Subsetdef(x0.redeem(), x1.redeem()),
+            SynonymTypedef(x0, x1, x2) => {
+                HeaderClause::SynonymTypedef(x0.redeem(), x1.redeem(), x2.clone())
+            }
+            DefaultNamespace(x) => HeaderClause::DefaultNamespace(x.redeem()),
+            Idspace(x0, x1, x2) => {
+                HeaderClause::Idspace(x0.redeem(), x1.redeem(), x2.as_ref().map(|x| x.redeem()))
+            }
+            TreatXrefsAsEquivalent(x) => HeaderClause::TreatXrefsAsEquivalent(x.redeem()),
+            TreatXrefsAsGenusDifferentia(x0, x1, x2) => {
+                HeaderClause::TreatXrefsAsGenusDifferentia(x0.redeem(), x1.redeem(), x2.redeem())
+            }
+            TreatXrefsAsReverseGenusDifferentia(x0, x1, x2) => {
+                HeaderClause::TreatXrefsAsReverseGenusDifferentia(
+                    x0.redeem(),
+                    x1.redeem(),
+                    x2.redeem(),
+                )
+            }
+            TreatXrefsAsRelationship(x0, x1) => {
+                HeaderClause::TreatXrefsAsRelationship(x0.redeem(), x1.redeem())
+            }
+            TreatXrefsAsIsA(x) => HeaderClause::TreatXrefsAsIsA(x.redeem()),
+            TreatXrefsAsHasSubclass(x) => HeaderClause::TreatXrefsAsHasSubclass(x.redeem()),
+            PropertyValue(x) => HeaderClause::PropertyValue(x.redeem()),
+            Remark(x) => HeaderClause::Remark(x.redeem()),
+            Ontology(x) => HeaderClause::Ontology(x.redeem()),
+            OwlAxioms(x) => HeaderClause::OwlAxioms(x.redeem()),
+            Unreserved(x0, x1) => HeaderClause::Unreserved(x0.redeem(), x1.redeem()),
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -325,4 +565,41 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn line() {
+        let line = Line::<HeaderClause>::from_str(
+            "subsetdef: GO_SLIM \"GO Slim\" {source=\"GOC\"} ! generic slim\n"
+        ).unwrap();
+        assert_eq!(
+            line.as_ref(),
+            &HeaderClause::Subsetdef(
+                SubsetIdent::from(UnprefixedIdent::new("GO_SLIM")),
+                QuotedString::new("GO Slim"),
+            )
+        );
+        assert_eq!(line.comment(), Some(&Comment::new("generic slim")));
+        assert_eq!(line.qualifiers().map(|q| q.len()), Some(1));
+        assert_eq!(
+            line.to_string(),
+            "subsetdef: GO_SLIM \"GO Slim\" {source=\"GOC\"} ! generic slim\n"
+        );
+
+        let line = Line::<HeaderClause>::from_str("format-version: 1.4").unwrap();
+        assert_eq!(line.to_string(), "format-version: 1.4\n");
+    }
+
+    #[test]
+    fn from_slice() {
+        for line in &[
+            "format-version: 1.2",
+            "subsetdef: GO_SLIM \"GO Slim\"",
+            "idspace: GO http://purl.obolibrary.org/obo/GO_ \"Gene Ontology\"",
+            "namespace-id-rule: * XAO:$sequence(7,5000,9999999)$",
+        ] {
+            let clause = HeaderClauseRef::from_slice(line).unwrap();
+            assert_eq!(clause.to_string(), *line);
+            assert_eq!(clause.redeem(), HeaderClause::from_str(line).unwrap());
+        }
+    }
+
 }
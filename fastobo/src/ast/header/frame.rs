@@ -4,6 +4,7 @@ use std::fmt::Result as FmtResult;
 use std::fmt::Write;
 use std::iter::FromIterator;
 use std::iter::IntoIterator;
+use std::ops::Deref;
 use std::str::FromStr;
 
 use pest::iterators::Pair;
@@ -12,6 +13,7 @@ use crate::ast::*;
 use crate::share::Share;
 use crate::share::Cow;
 use crate::share::Redeem;
+use crate::error::Error;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
@@ -28,51 +30,60 @@ use crate::parser::Rule;
     FromInner,
     PartialEq(Inner),
 ))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct HeaderFrame {
-    clauses: Vec<HeaderClause>,
+    clauses: Vec<Line<HeaderClause>>,
 }
 
 impl HeaderFrame {
-    pub fn new(clauses: Vec<HeaderClause>) -> Self {
+    pub fn new(clauses: Vec<Line<HeaderClause>>) -> Self {
         Self { clauses }
     }
 }
 
-impl AsRef<[HeaderClause]> for HeaderFrame {
-    fn as_ref(&self) -> &[HeaderClause] {
+impl AsRef<[Line<HeaderClause>]> for HeaderFrame {
+    fn as_ref(&self) -> &[Line<HeaderClause>] {
         &self.clauses
     }
 }
 
 impl Display for HeaderFrame {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        for clause in self.clauses.iter() {
-            clause.fmt(f).and(f.write_char('\n'))?;
-        }
-        Ok(())
+        self.clauses.iter().try_for_each(|line| line.fmt(f))
+    }
+}
+
+impl FromIterator<Line<HeaderClause>> for HeaderFrame {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Line<HeaderClause>>
+    {
+        Self::new(iter.into_iter().collect())
     }
 }
 
+/// Create a header frame from bare clauses, without qualifiers or comments.
 impl FromIterator<HeaderClause> for HeaderFrame {
     fn from_iter<T>(iter: T) -> Self
     where
         T: IntoIterator<Item = HeaderClause>
     {
-        Self::new(iter.into_iter().collect())
+        Self::new(iter.into_iter().map(Line::from).collect())
     }
 }
 
 impl IntoIterator for HeaderFrame {
-    type Item = HeaderClause;
-    type IntoIter = <Vec<HeaderClause> as IntoIterator>::IntoIter;
+    type Item = Line<HeaderClause>;
+    type IntoIter = <Vec<Line<HeaderClause>> as IntoIterator>::IntoIter;
     fn into_iter(self) -> Self::IntoIter {
         self.clauses.into_iter()
     }
 }
 
 impl<'a> IntoIterator for &'a HeaderFrame {
-    type Item = &'a HeaderClause;
-    type IntoIter = <&'a Vec<HeaderClause> as IntoIterator>::IntoIter;
+    type Item = &'a Line<HeaderClause>;
+    type IntoIter = <&'a Vec<Line<HeaderClause>> as IntoIterator>::IntoIter;
     fn into_iter(self) -> Self::IntoIter {
         self.clauses.as_slice().iter()
     }
@@ -83,17 +94,119 @@ impl<'i> FromPair<'i> for HeaderFrame {
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
         let mut clauses = Vec::new();
         for inner in pair.into_inner() {
-            clauses.push(HeaderClause::from_pair_unchecked(inner)?)
+            let clause = Line::<HeaderClause>::from_pair_unchecked(inner)?;
+            check_unique_clause(&clauses, &clause)?;
+            clauses.push(clause);
         }
         Ok(HeaderFrame { clauses })
     }
 }
 impl_fromstr!(HeaderFrame);
 
-// WIP(@althonos)
-// pub struct HeaderFrameRef<'a> {
-//     pub clauses: Cow<'a, &'a [HeaderClauseRef<'a>]>
-// }
+/// Check whether a header clause may only appear once in a header frame.
+///
+/// The OBO 1.4 specification only allows a single `format-version`,
+/// `data-version`, `date`, `saved-by`, `auto-generated-by`,
+/// `default-namespace` and `ontology` clause in a header frame.
+pub(crate) fn is_unique_clause(clause: &HeaderClause) -> bool {
+    use self::HeaderClause::*;
+    matches!(
+        clause,
+        FormatVersion(_) | DataVersion(_) | Date(_) | SavedBy(_) | AutoGeneratedBy(_)
+            | DefaultNamespace(_) | Ontology(_)
+    )
+}
+
+/// Check that a parsed clause is not a duplicate of a unique header clause.
+pub(crate) fn check_unique_clause(
+    clauses: &[Line<HeaderClause>],
+    clause: &Line<HeaderClause>,
+) -> Result<()> {
+    let inner: &HeaderClause = clause.as_ref();
+    let kind = std::mem::discriminant(inner);
+    if is_unique_clause(inner) && clauses.iter().any(|c| std::mem::discriminant(c.as_ref()) == kind) {
+        Err(Error::DuplicateClause {
+            value: inner.to_string(),
+            location: clause.location().cloned(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that a parsed borrowed clause is not a duplicate of a unique header clause.
+#[cfg(feature = "memmap")]
+pub(crate) fn check_unique_clause_ref<'a>(
+    clauses: &[Line<HeaderClauseRef<'a>>],
+    clause: &Line<HeaderClauseRef<'a>>,
+) -> Result<()> {
+    use self::HeaderClauseRef::*;
+    let inner: &HeaderClauseRef<'a> = clause.as_ref();
+    let kind = std::mem::discriminant(inner);
+    let unique = matches!(
+        inner,
+        FormatVersion(_) | DataVersion(_) | Date(_) | SavedBy(_) | AutoGeneratedBy(_)
+            | DefaultNamespace(_) | Ontology(_)
+    );
+    if unique && clauses.iter().any(|c| std::mem::discriminant(c.as_ref()) == kind) {
+        Err(Error::DuplicateClause {
+            value: inner.to_string(),
+            location: clause.location().cloned(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// A borrowed `HeaderFrame`.
+#[derive(Clone, Debug, Default)]
+pub struct HeaderFrameRef<'a> {
+    clauses: Vec<Line<HeaderClauseRef<'a>>>,
+}
+
+impl<'a> HeaderFrameRef<'a> {
+    pub fn new(clauses: Vec<Line<HeaderClauseRef<'a>>>) -> Self {
+        Self { clauses }
+    }
+}
+
+impl<'a> AsRef<[Line<HeaderClauseRef<'a>>]> for HeaderFrameRef<'a> {
+    fn as_ref(&self) -> &[Line<HeaderClauseRef<'a>>] {
+        &self.clauses
+    }
+}
+
+impl<'a> Deref for HeaderFrameRef<'a> {
+    type Target = [Line<HeaderClauseRef<'a>>];
+    fn deref(&self) -> &Self::Target {
+        &self.clauses
+    }
+}
+
+impl<'a> Display for HeaderFrameRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.clauses.iter().try_for_each(|line| line.fmt(f))
+    }
+}
+
+impl<'i> FromPair<'i> for HeaderFrameRef<'i> {
+    const RULE: Rule = Rule::HeaderFrame;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut clauses = Vec::new();
+        for inner in pair.into_inner() {
+            clauses.push(Line::<HeaderClauseRef>::from_pair_unchecked(inner)?)
+        }
+        Ok(HeaderFrameRef { clauses })
+    }
+}
+impl_fromslice!('i, HeaderFrameRef<'i>);
+
+impl<'a> Redeem<'a> for HeaderFrameRef<'a> {
+    type Owned = HeaderFrame;
+    fn redeem(&'a self) -> HeaderFrame {
+        self.clauses.iter().map(|line| line.redeem()).collect()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -116,21 +229,42 @@ mod tests {
         ).unwrap();
 
         assert_eq!(
-            actual.clauses[0],
-            HeaderClause::FormatVersion(UnquotedString::new("1.2")),
+            actual.clauses[0].as_ref(),
+            &HeaderClause::FormatVersion(UnquotedString::new("1.2")),
         );
 
         assert_eq!(
-            actual.clauses[1],
-            HeaderClause::DataVersion(UnquotedString::new("releases/2019-03-17")),
+            actual.clauses[1].as_ref(),
+            &HeaderClause::DataVersion(UnquotedString::new("releases/2019-03-17")),
         );
 
         assert_eq!(
-            actual.clauses[2],
-            HeaderClause::Subsetdef(
+            actual.clauses[2].as_ref(),
+            &HeaderClause::Subsetdef(
                 SubsetIdent::from(UnprefixedIdent::new("gocheck_do_not_annotate")),
                 QuotedString::new("Term not to be used for direct annotation"),
             )
         );
     }
+
+    #[test]
+    fn comments_and_qualifiers() {
+        let text = "default-namespace: test ! default namespace\n\
+                    subsetdef: GO_SLIM \"GO Slim\" {source=\"GOC\"}\n\
+                    ontology: go\n";
+        let frame = HeaderFrame::from_str(text).unwrap();
+        assert_eq!(frame.len(), 3);
+        assert_eq!(frame[0].comment(), Some(&Comment::new("default namespace")));
+        assert!(frame[1].qualifiers().is_some());
+        assert_eq!(frame[2].location().map(|l| l.line), Some(3));
+        assert_eq!(frame.to_string(), text);
+    }
+
+    #[test]
+    fn duplicate_unlocated_clause() {
+        let clause = Line::from(HeaderClause::Ontology(UnquotedString::new("go")));
+        let err = check_unique_clause(&[clause.clone()], &clause).unwrap_err();
+        assert!(err.location().is_none());
+        assert_eq!(err.to_string(), "duplicate clause `ontology: go`");
+    }
 }
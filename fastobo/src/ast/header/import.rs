@@ -8,6 +8,8 @@ use pest::iterators::Pair;
 use url::Url;
 
 use crate::ast::*;
+use crate::share::Cow;
+use crate::share::Redeem;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
@@ -46,10 +48,50 @@ impl<'i> FromPair<'i> for Import {
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
         let inner = pair.into_inner().next().unwrap();
         match inner.as_rule() {
-            Rule::Iri => Ok(Url::parse(inner.as_str()).unwrap().into()), // FIXME
+            Rule::Iri => Url::from_pair_unchecked(inner).map(From::from),
             Rule::Id => Ident::from_pair_unchecked(inner).map(From::from),
             _ => unreachable!(),
         }
     }
 }
 impl_fromstr!(Import);
+
+/// A borrowed `Import`.
+#[derive(Clone, Debug)]
+pub enum ImportRef<'a> {
+    Url(Cow<'a, &'a Url>),
+    Abbreviated(Id<'a>),
+}
+
+impl<'a> Display for ImportRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::ImportRef::*;
+        match self {
+            Url(url) => url.fmt(f),
+            Abbreviated(id) => id.fmt(f),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for ImportRef<'i> {
+    const RULE: Rule = Rule::Import;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::Iri => Url::from_pair_unchecked(inner).map(|url| ImportRef::Url(Cow::Owned(url))),
+            Rule::Id => Id::from_pair_unchecked(inner).map(ImportRef::Abbreviated),
+            _ => unreachable!(),
+        }
+    }
+}
+impl_fromslice!('i, ImportRef<'i>);
+
+impl<'a> Redeem<'a> for ImportRef<'a> {
+    type Owned = Import;
+    fn redeem(&'a self) -> Import {
+        match self {
+            ImportRef::Url(url) => Import::Url(url.redeem()),
+            ImportRef::Abbreviated(id) => Import::Abbreviated(id.redeem()),
+        }
+    }
+}
@@ -3,5 +3,12 @@ mod frame;
 mod import;
 
 pub use self::clause::HeaderClause;
+pub use self::clause::HeaderClauseRef;
 pub use self::frame::HeaderFrame;
+pub use self::frame::HeaderFrameRef;
+pub(crate) use self::frame::check_unique_clause;
+#[cfg(feature = "memmap")]
+pub(crate) use self::frame::check_unique_clause_ref;
+pub(crate) use self::frame::is_unique_clause;
 pub use self::import::Import;
+pub use self::import::ImportRef;
@@ -7,6 +7,8 @@ use std::fmt::Write;
 use opaque_typedef::OpaqueTypedefUnsized;
 use pest::iterators::Pair;
 
+use crate::ast::escape::escape_ident;
+use crate::ast::escape::unescape;
 use crate::share::Share;
 use crate::share::Cow;
 use crate::share::Redeem;
@@ -16,44 +18,10 @@ use crate::parser::FromPair;
 use crate::parser::Rule;
 use crate::parser::QuickFind;
 
-fn escape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    s.chars().try_for_each(|char| match char {
-        '\r' => f.write_str("\\r"),
-        '\n' => f.write_str("\\n"),
-        '\u{000c}' => f.write_str("\\f"),
-        ' ' => f.write_str("\\ "),
-        '\t' => f.write_str("\\t"),
-        ':' => f.write_str("\\:"),
-        '"' => f.write_str("\\\""),
-        '\\' => f.write_str("\\\\"),
-        _ => f.write_char(char)
-    })
-}
-
-fn unescape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    let mut chars = s.chars();
-    while let Some(char) = chars.next() {
-        if char == '\\' {
-            match chars.next() {
-                Some('r') => f.write_char('\r')?,
-                Some('n') => f.write_char('\n')?,
-                Some('f') => f.write_char('\u{000c}')?,
-                Some('t') => f.write_char('\t')?,
-                Some(other) => f.write_char(other)?,
-                None => panic!("invalid escape"), // FIXME(@althonos)
-            }
-        } else {
-            f.write_char(char)?;
-        }
-    }
-    Ok(())
-}
-
 fn is_canonical<S: AsRef<str>>(s: S) -> bool {
     s.as_ref().chars().all(|c| c.is_ascii_digit())
 }
 
-
 /// A local identifier, preceded by a prefix in prefixed IDs.
 ///
 /// * A canonical local ID only contains digits (`[0-9]`).
@@ -134,13 +102,15 @@ impl<'i> FromPair<'i> for IdentLocal {
             return Ok(Self::new_unchecked(inner.as_str().to_string(), true));
         }
 
-        // Unescape the local ID if it is non canonical.
+        // Unescape the local ID if it is non canonical. The unescaped ID
+        // may still be canonical if the rule was rejected because of what
+        // follows it (e.g. a comma in xref lists).
         let s = inner.as_str();
         let escaped = s.quickcount(b'\\');
         let mut local = String::with_capacity(s.len() + escaped);
-        unescape(&mut local, s).expect("fmt::Write cannot fail on a String");
+        unescape(&mut local, inner.as_span())?;
 
-        Ok(Self::new_unchecked(local, false))
+        Ok(Self::new(local))
     }
 }
 impl_fromstr!(IdentLocal);
@@ -197,7 +167,7 @@ impl<'a> Display for IdLocal<'a> {
         if self.canonical {
             f.write_str(&self.value)
         } else {
-            escape(f, &self.value)
+            escape_ident(f, &self.value)
         }
     }
 }
@@ -205,13 +175,13 @@ impl<'a> Display for IdLocal<'a> {
 impl<'i> FromPair<'i> for Cow<'i, IdLocal<'i>> {
     const RULE: Rule = Rule::IdLocal;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
-        let inner = pair.into_inner().next().unwrap();
+        let inner = pair.clone().into_inner().next().unwrap();
         if inner.as_rule() == Rule::CanonicalIdLocal {
             Ok(Cow::Borrowed(IdLocal::new_unchecked(inner.as_str(), true)))
         } else if inner.as_str().find('\\').is_some() {
-            IdentLocal::from_pair_unchecked(inner).map(Cow::Owned)
+            IdentLocal::from_pair_unchecked(pair).map(Cow::Owned)
         } else {
-            Ok(Cow::Borrowed(IdLocal::new_unchecked(inner.as_str(), false)))
+            Ok(Cow::Borrowed(IdLocal::new(inner.as_str())))
         }
     }
 }
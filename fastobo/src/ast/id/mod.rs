@@ -109,6 +109,7 @@ impl<'a> Share<'a, Id<'a>> for Ident {
 }
 
 /// A borrowed `Identifier`.
+#[derive(Clone, Debug)]
 pub enum Id<'a> {
     Prefixed(Cow<'a, PrefixedId<'a>>),
     Unprefixed(Cow<'a, &'a UnprefixedId>),
@@ -7,6 +7,8 @@ use std::fmt::Write;
 use opaque_typedef::OpaqueTypedefUnsized;
 use pest::iterators::Pair;
 
+use crate::ast::escape::escape_ident;
+use crate::ast::escape::unescape;
 use crate::share::Share;
 use crate::share::Cow;
 use crate::share::Redeem;
@@ -16,51 +18,16 @@ use crate::parser::FromPair;
 use crate::parser::Rule;
 use crate::parser::QuickFind;
 
-
-fn escape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    s.chars().try_for_each(|char| match char {
-        '\r' => f.write_str("\\r"),
-        '\n' => f.write_str("\\n"),
-        '\u{000c}' => f.write_str("\\f"),
-        ' ' => f.write_str("\\ "),
-        '\t' => f.write_str("\\t"),
-        ':' => f.write_str("\\:"),
-        '"' => f.write_str("\\\""),
-        '\\' => f.write_str("\\\\"),
-        _ => f.write_char(char)
-    })
-}
-
-fn unescape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    let mut chars = s.chars();
-    while let Some(char) = chars.next() {
-        if char == '\\' {
-            match chars.next() {
-                Some('r') => f.write_char('\r')?,
-                Some('n') => f.write_char('\n')?,
-                Some('f') => f.write_char('\u{000c}')?,
-                Some('t') => f.write_char('\t')?,
-                Some(other) => f.write_char(other)?,
-                None => panic!("invalid escape"), // FIXME(@althonos)
-            }
-        } else {
-            f.write_char(char)?;
-        }
-    }
-    Ok(())
-}
-
 fn is_canonical<S: AsRef<str>>(s: S) -> bool {
     let string = s.as_ref();
     let mut chars = string.chars();
     if let Some(c) = chars.next() {
-        c.is_ascii_alphabetic() && chars.all(|ref c| char::is_ascii_alphanumeric(c))
+        c.is_ascii_alphabetic() && chars.all(|c| c.is_ascii_alphabetic() || c == '_')
     } else {
         false
     }
 }
 
-
 /// An identifier prefix, either canonical or non-canonical.
 ///
 /// * A canonical ID prefix only contains alphabetic characters (`[a-zA-Z]`)
@@ -152,13 +119,15 @@ impl<'i> FromPair<'i> for IdentPrefix {
             return Ok(Self::new_unchecked(inner.as_str().to_string(), true));
         }
 
-        // Unescape the prefix if is non canonical.
+        // Unescape the prefix if is non canonical. The unescaped prefix
+        // may still be canonical if the rule was rejected because of what
+        // follows it (e.g. whitespace in `idspace` clauses).
         let s = inner.as_str();
         let escaped = s.quickcount(b'\\');
         let mut local = String::with_capacity(s.len() + escaped);
-        unescape(&mut local, s).expect("fmt::Write cannot fail on a String");
+        unescape(&mut local, inner.as_span())?;
 
-        Ok(Self::new_unchecked(local, false))
+        Ok(Self::new(local))
     }
 }
 impl_fromstr!(IdentPrefix);
@@ -181,7 +150,6 @@ impl<'a> Share<'a, IdPrefix<'a>> for IdentPrefix {
     }
 }
 
-
 /// A borrowed `IdentPrefix`
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq)]
 pub struct IdPrefix<'a> {
@@ -219,7 +187,7 @@ impl<'a> Display for IdPrefix<'a> {
         if self.canonical {
             f.write_str(&self.value)
         } else {
-            escape(f, &self.value)
+            escape_ident(f, &self.value)
         }
     }
 }
@@ -233,13 +201,13 @@ impl<'a> Into<&'a str> for IdPrefix<'a> {
 impl<'i> FromPair<'i> for Cow<'i, IdPrefix<'i>> {
     const RULE: Rule = Rule::IdPrefix;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
-        let inner = pair.into_inner().next().unwrap();
+        let inner = pair.clone().into_inner().next().unwrap();
         if inner.as_rule() == Rule::CanonicalIdPrefix {
             Ok(Cow::Borrowed(IdPrefix::new_unchecked(inner.as_str(), true)))
         } else if inner.as_str().find('\\').is_some() {
-            IdentPrefix::from_pair_unchecked(inner).map(Cow::Owned)
+            IdentPrefix::from_pair_unchecked(pair).map(Cow::Owned)
         } else {
-            Ok(Cow::Borrowed(IdPrefix::new_unchecked(inner.as_str(), false)))
+            Ok(Cow::Borrowed(IdPrefix::new(inner.as_str())))
         }
     }
 }
@@ -266,7 +234,6 @@ impl<'a> Redeem<'a> for IdPrefix<'a> {
     }
 }
 
-
 #[cfg(test)]
 mod tests {
 
@@ -93,6 +93,7 @@ macro_rules! ident_subclasses {
 
 macro_rules! id_subclass {
     (#[doc = $docstring:literal] $rule:expr => pub struct $name:ident : &$life:lifetime $owned:ident) => {
+        #[derive(Clone, Debug)]
         #[doc=$docstring]
         pub struct $name<$life> {
             inner: Id<$life>
@@ -9,6 +9,8 @@ use std::ops::Deref;
 use opaque_typedef::OpaqueTypedefUnsized;
 use pest::iterators::Pair;
 
+use crate::ast::escape::escape_ident;
+use crate::ast::escape::unescape;
 use crate::share::Share;
 use crate::share::Cow;
 use crate::share::Redeem;
@@ -18,41 +20,6 @@ use crate::parser::FromPair;
 use crate::parser::Rule;
 use crate::parser::QuickFind;
 
-
-fn escape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    s.chars().try_for_each(|char| match char {
-        '\r' => f.write_str("\\r"),
-        '\n' => f.write_str("\\n"),
-        '\u{000c}' => f.write_str("\\f"),
-        ' ' => f.write_str("\\ "),
-        '\t' => f.write_str("\\t"),
-        ':' => f.write_str("\\:"),
-        '"' => f.write_str("\\\""),
-        '\\' => f.write_str("\\\\"),
-        _ => f.write_char(char)
-    })
-}
-
-fn unescape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    let mut chars = s.chars();
-    while let Some(char) = chars.next() {
-        if char == '\\' {
-            match chars.next() {
-                Some('r') => f.write_char('\r')?,
-                Some('n') => f.write_char('\n')?,
-                Some('f') => f.write_char('\u{000c}')?,
-                Some('t') => f.write_char('\t')?,
-                Some(other) => f.write_char(other)?,
-                None => panic!("invalid escape"), // FIXME(@althonos)
-            }
-        } else {
-            f.write_char(char)?;
-        }
-    }
-    Ok(())
-}
-
-
 /// An identifier without a prefix.
 #[derive(Clone, Debug, Ord, PartialEq, PartialOrd, Hash, Eq)]
 pub struct UnprefixedIdent {
@@ -120,7 +87,7 @@ impl<'i> FromPair<'i> for UnprefixedIdent {
         let s = pair.as_str();
         let escaped = s.quickcount(b'\\');
         let mut local = String::with_capacity(s.len() + escaped);
-        unescape(&mut local, s).expect("fmt::Write cannot fail on a String");
+        unescape(&mut local, pair.as_span())?;
         Ok(Self::new(local))
     }
 }
@@ -157,7 +124,7 @@ impl AsRef<str> for UnprefixedId {
 
 impl Display for UnprefixedId {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        escape(f, &self.0)
+        escape_ident(f, &self.0)
     }
 }
 
@@ -6,12 +6,16 @@ use std::fmt::Write;
 use pest::iterators::Pair;
 
 use crate::ast::*;
+use crate::share::Cow;
+use crate::share::Redeem;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
 
 /// A clause appearing in an instance frame.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum InstanceClause {
     IsAnonymous(bool),
     Name(UnquotedString),
@@ -24,7 +28,7 @@ pub enum InstanceClause {
     Xref(Xref),
     PropertyValue(PropertyValue),
     InstanceOf(ClassIdent),
-    Relationship(RelationIdent, Ident), // QUESTION(@althonos): InstanceId ?
+    Relationship(RelationIdent, InstanceIdent),
     CreatedBy(UnquotedString),
     CreationDate(IsoDateTime),
     IsObsolete(bool),
@@ -117,7 +121,7 @@ impl<'i> FromPair<'i> for InstanceClause {
             }
             Rule::RelationshipTag => {
                 let r = RelationIdent::from_pair_unchecked(inner.next().unwrap())?;
-                let id = Ident::from_pair_unchecked(inner.next().unwrap())?;
+                let id = InstanceIdent::from_pair_unchecked(inner.next().unwrap())?;
                 Ok(InstanceClause::Relationship(r, id))
             }
             Rule::CreatedByTag => {
@@ -149,10 +153,276 @@ impl_fromstr!(InstanceClause);
 impl<'i> FromPair<'i> for Line<InstanceClause> {
     const RULE: Rule = Rule::InstanceClauseLine;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
         let mut inner = pair.into_inner();
         let clause = InstanceClause::from_pair_unchecked(inner.next().unwrap())?;
         let eol = inner.next().unwrap();
-        Ok(Eol::from_pair_unchecked(eol)?.and_inner(clause))
+        Ok(Eol::from_pair_unchecked(eol)?
+            .and_inner(clause)
+            .and_location(location))
     }
 }
 impl_fromstr!(Line<InstanceClause>);
+
+/// A borrowed `InstanceClause`.
+#[derive(Clone, Debug)]
+pub enum InstanceClauseRef<'a> {
+    IsAnonymous(bool),
+    Name(Cow<'a, &'a UnquotedStr>),
+    Namespace(NamespaceId<'a>),
+    AltId(Id<'a>),
+    Def(Cow<'a, &'a QuotedStr>, XrefListRef<'a>),
+    Comment(Cow<'a, &'a UnquotedStr>),
+    Subset(SubsetId<'a>),
+    Synonym(SynonymRef<'a>),
+    Xref(XrefRef<'a>),
+    PropertyValue(PropVal<'a>),
+    InstanceOf(ClassId<'a>),
+    Relationship(RelationId<'a>, InstanceId<'a>),
+    CreatedBy(Cow<'a, &'a UnquotedStr>),
+    CreationDate(IsoDateTime),
+    IsObsolete(bool),
+    ReplacedBy(InstanceId<'a>),
+    Consider(Id<'a>),
+}
+
+impl<'a> Display for InstanceClauseRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::InstanceClauseRef::*;
+        match self {
+            IsAnonymous(b) => f.write_str("is_anonymous: ").and(b.fmt(f)),
+            Name(n) => f.write_str("name: ").and(n.fmt(f)),
+            Namespace(ns) => f.write_str("namespace: ").and(ns.fmt(f)),
+            AltId(id) => f.write_str("alt_id: ").and(id.fmt(f)),
+            Def(desc, xrefs) => f
+                .write_str("def: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            Comment(s) => f.write_str("comment: ").and(s.fmt(f)),
+            Subset(id) => f.write_str("subset: ").and(id.fmt(f)),
+            Synonym(syn) => f.write_str("synonym: ").and(syn.fmt(f)),
+            Xref(xref) => f.write_str("xref: ").and(xref.fmt(f)),
+            PropertyValue(pv) => f.write_str("property_value: ").and(pv.fmt(f)),
+            InstanceOf(id) => f.write_str("instance_of: ").and(id.fmt(f)),
+            Relationship(r, id) => f
+                .write_str("relationship: ")
+                .and(r.fmt(f))
+                .and(f.write_char(' '))
+                .and(id.fmt(f)),
+            CreatedBy(s) => f.write_str("created_by: ").and(s.fmt(f)),
+            CreationDate(dt) => f.write_str("creation_date: ").and(dt.fmt(f)),
+            IsObsolete(b) => f.write_str("is_obsolete: ").and(b.fmt(f)),
+            ReplacedBy(id) => f.write_str("replaced_by: ").and(id.fmt(f)),
+            Consider(id) => f.write_str("consider: ").and(id.fmt(f)),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for Line<InstanceClauseRef<'i>> {
+    const RULE: Rule = Rule::InstanceClauseLine;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
+        let mut inner = pair.into_inner();
+        let clause = InstanceClauseRef::from_pair_unchecked(inner.next().unwrap())?;
+        let eol = inner.next().unwrap();
+        Ok(Eol::from_pair_unchecked(eol)?
+            .and_inner(clause)
+            .and_location(location))
+    }
+}
+impl_fromslice!('i, Line<InstanceClauseRef<'i>>);
+
+impl<'i> FromPair<'i> for InstanceClauseRef<'i> {
+    const RULE: Rule = Rule::InstanceClause;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        match inner.next().unwrap().as_rule() {
+            Rule::IsAnonymousTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::IsAnonymous(b))
+            }
+            Rule::NameTag => {
+                let n = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Name(n))
+            }
+            Rule::NamespaceTag => {
+                let ns = NamespaceId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Namespace(ns))
+            }
+            Rule::AltIdTag => {
+                let id = Id::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::AltId(id))
+            }
+            Rule::DefTag => {
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefListRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Def(desc, xrefs))
+            }
+            Rule::CommentTag => {
+                let s = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Comment(s))
+            }
+            Rule::SubsetTag => {
+                let id = SubsetId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Subset(id))
+            }
+            Rule::SynonymTag => {
+                let syn = SynonymRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Synonym(syn))
+            }
+            Rule::XrefTag => {
+                let xref = XrefRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Xref(xref))
+            }
+            Rule::PropertyValueTag => {
+                let pv = PropVal::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::PropertyValue(pv))
+            }
+            Rule::InstanceOfTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::InstanceOf(id))
+            }
+            Rule::RelationshipTag => {
+                let r = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                let id = InstanceId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Relationship(r, id))
+            }
+            Rule::CreatedByTag => {
+                let s = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::CreatedBy(s))
+            }
+            Rule::CreationDateTag => {
+                let dt = IsoDateTime::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::CreationDate(dt))
+            }
+            Rule::IsObsoleteTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::IsObsolete(b))
+            }
+            Rule::ReplacedByTag => {
+                let id = InstanceId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::ReplacedBy(id))
+            }
+            Rule::ConsiderTag => {
+                let id = Id::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(InstanceClauseRef::Consider(id))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+impl_fromslice!('i, InstanceClauseRef<'i>);
+
+impl<'a> Redeem<'a> for InstanceClauseRef<'a> {
+    type Owned = InstanceClause;
+    fn redeem(&'a self) -> InstanceClause {
+        use self::InstanceClauseRef::*;
+        match self {
+            IsAnonymous(x) => InstanceClause::IsAnonymous(*x),
+            Name(x) => InstanceClause::Name(x.redeem()),
+            Namespace(x) => InstanceClause::Namespace(x.redeem()),
+            AltId(x) => InstanceClause::AltId(x.redeem()),
+            Def(x0, x1) => InstanceClause::Def(x0.redeem(), x1.redeem()),
+            Comment(x) => InstanceClause::Comment(x.redeem()),
+            Subset(x) => InstanceClause::Subset(x.redeem()),
+            Synonym(x) => InstanceClause::Synonym(x.redeem()),
+            Xref(x) => InstanceClause::Xref(x.redeem()),
+            PropertyValue(x) => InstanceClause::PropertyValue(x.redeem()),
+            InstanceOf(x) => InstanceClause::InstanceOf(x.redeem()),
+            Relationship(x0, x1) => InstanceClause::Relationship(x0.redeem(), x1.redeem()),
+            CreatedBy(x) => InstanceClause::CreatedBy(x.redeem()),
+            CreationDate(x) => InstanceClause::CreationDate(x.clone()),
+            IsObsolete(x) => InstanceClause::IsObsolete(*x),
+            ReplacedBy(x) => InstanceClause::ReplacedBy(x.redeem()),
+            Consider(x) => InstanceClause::Consider(x.redeem()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use super::*;
+
+    mod instance_of {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let actual = InstanceClause::from_str("instance_of: PATO:0000001").unwrap();
+            let expected = InstanceClause::InstanceOf(
+                ClassIdent::from(PrefixedIdent::new("PATO", "0000001"))
+            );
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn to_string() {
+            let clause = InstanceClause::InstanceOf(
+                ClassIdent::from(PrefixedIdent::new("PATO", "0000001"))
+            );
+            assert_eq!(clause.to_string(), "instance_of: PATO:0000001");
+        }
+    }
+
+    mod relationship {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let actual = InstanceClause::from_str("relationship: married_to heather").unwrap();
+            let expected = InstanceClause::Relationship(
+                RelationIdent::from(UnprefixedIdent::new("married_to")),
+                InstanceIdent::from(UnprefixedIdent::new("heather")),
+            );
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn to_string() {
+            let clause = InstanceClause::Relationship(
+                RelationIdent::from(UnprefixedIdent::new("married_to")),
+                InstanceIdent::from(UnprefixedIdent::new("heather")),
+            );
+            assert_eq!(clause.to_string(), "relationship: married_to heather");
+        }
+    }
+
+    mod replaced_by {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let actual = InstanceClause::from_str("replaced_by: EX:0000002").unwrap();
+            let expected = InstanceClause::ReplacedBy(
+                InstanceIdent::from(PrefixedIdent::new("EX", "0000002"))
+            );
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn to_string() {
+            let clause = InstanceClause::ReplacedBy(
+                InstanceIdent::from(PrefixedIdent::new("EX", "0000002"))
+            );
+            assert_eq!(clause.to_string(), "replaced_by: EX:0000002");
+        }
+    }
+
+    mod line {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let actual = Line::<InstanceClause>::from_str(
+                "instance_of: FOAF:Person ! person\n"
+            ).unwrap();
+            let expected = Line::from(InstanceClause::InstanceOf(
+                ClassIdent::from(PrefixedIdent::new("FOAF", "Person"))
+            )).and_comment(Comment::new("person"));
+            assert_eq!(actual, expected);
+        }
+    }
+}
@@ -8,12 +8,14 @@ use std::fmt::Write;
 use pest::iterators::Pair;
 
 use crate::ast::*;
+use crate::share::Redeem;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
 
 /// An instance frame, describing a particular individual.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct InstanceFrame {
     id: Line<InstanceIdent>,
     clauses: Vec<Line<InstanceClause>>,
@@ -44,6 +46,11 @@ impl InstanceFrame {
         &self.id
     }
 
+    /// Get a mutable reference to the identifier of the `InstanceFrame`.
+    pub fn id_mut(&mut self) -> &mut Line<InstanceIdent> {
+        &mut self.id
+    }
+
     /// Get the `InstanceClause`s of the `InstanceFrame`.
     pub fn clauses(&self) -> &Vec<Line<InstanceClause>> {
         &self.clauses
@@ -82,12 +89,31 @@ impl Display for InstanceFrame {
     }
 }
 
+/// Create a new instance frame with the frame ID given as a `Line`.
+impl From<Line<InstanceIdent>> for InstanceFrame {
+    fn from(line: Line<InstanceIdent>) -> Self {
+        Self::new(line)
+    }
+}
+
+/// Create a new instance frame with the frame ID given as an `InstanceIdent`.
+impl From<InstanceIdent> for InstanceFrame {
+    fn from(id: InstanceIdent) -> Self {
+        Self::new(id)
+    }
+}
+
 impl<'i> FromPair<'i> for InstanceFrame {
     const RULE: Rule = Rule::InstanceFrame;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
         let mut inner = pair.into_inner();
-        let iid = InstanceIdent::from_pair_unchecked(inner.next().unwrap())?;
-        let id = Eol::from_pair_unchecked(inner.next().unwrap())?.and_inner(iid);
+        let id_pair = inner.next().unwrap();
+        let eol_pair = inner.next().unwrap();
+        let location = SourceLocation::from_pairs(&id_pair, &eol_pair);
+        let iid = InstanceIdent::from_pair_unchecked(id_pair)?;
+        let id = Eol::from_pair_unchecked(eol_pair)?
+            .and_inner(iid)
+            .and_location(location);
 
         let mut clauses = Vec::new();
         for pair in inner {
@@ -114,3 +140,178 @@ impl<'a> IntoIterator for &'a InstanceFrame {
         self.clauses.as_slice().iter()
     }
 }
+
+/// A borrowed `InstanceFrame`.
+#[derive(Clone, Debug)]
+pub struct InstanceFrameRef<'a> {
+    id: Line<InstanceId<'a>>,
+    clauses: Vec<Line<InstanceClauseRef<'a>>>,
+}
+
+impl<'a> InstanceFrameRef<'a> {
+    /// Create a new borrowed instance frame with the provided ID and clauses.
+    pub fn with_clauses<I>(id: I, clauses: Vec<Line<InstanceClauseRef<'a>>>) -> Self
+    where
+        I: Into<Line<InstanceId<'a>>>,
+    {
+        Self {
+            id: id.into(),
+            clauses,
+        }
+    }
+
+    /// Get the identifier of the `InstanceFrameRef`.
+    pub fn id(&self) -> &Line<InstanceId<'a>> {
+        &self.id
+    }
+
+    /// Get a mutable reference to the identifier of the `InstanceFrameRef`.
+    pub fn id_mut(&mut self) -> &mut Line<InstanceId<'a>> {
+        &mut self.id
+    }
+
+    /// Get the `InstanceClauseRef`s of the `InstanceFrameRef`.
+    pub fn clauses(&self) -> &Vec<Line<InstanceClauseRef<'a>>> {
+        &self.clauses
+    }
+}
+
+impl<'a> Deref for InstanceFrameRef<'a> {
+    type Target = Vec<Line<InstanceClauseRef<'a>>>;
+    fn deref(&self) -> &Self::Target {
+        &self.clauses
+    }
+}
+
+impl<'a> DerefMut for InstanceFrameRef<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.clauses
+    }
+}
+
+impl<'a> Display for InstanceFrameRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str("[Instance]\nid: ").and(self.id.fmt(f))?;
+        self.clauses.iter().try_for_each(|clause| clause.fmt(f))
+    }
+}
+
+impl<'i> FromPair<'i> for InstanceFrameRef<'i> {
+    const RULE: Rule = Rule::InstanceFrame;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        let id_pair = inner.next().unwrap();
+        let eol_pair = inner.next().unwrap();
+        let location = SourceLocation::from_pairs(&id_pair, &eol_pair);
+        let iid = InstanceId::from_pair_unchecked(id_pair)?;
+        let id = Eol::from_pair_unchecked(eol_pair)?
+            .and_inner(iid)
+            .and_location(location);
+
+        let mut clauses = Vec::new();
+        for pair in inner {
+            clauses.push(Line::<InstanceClauseRef>::from_pair_unchecked(pair)?);
+        }
+
+        Ok(InstanceFrameRef { id, clauses })
+    }
+}
+impl_fromslice!('i, InstanceFrameRef<'i>);
+
+impl<'a> Redeem<'a> for InstanceFrameRef<'a> {
+    type Owned = InstanceFrame;
+    fn redeem(&'a self) -> InstanceFrame {
+        InstanceFrame::with_clauses(
+            self.id.redeem(),
+            self.clauses.iter().map(|line| line.redeem()).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let actual = InstanceFrame::from_str(
+            "[Instance]
+            id: john
+            name: John Doe
+            instance_of: FOAF:Person
+            relationship: married_to jane
+            replaced_by: john_doe\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            actual.id().as_ref(),
+            &InstanceIdent::from(UnprefixedIdent::new("john"))
+        );
+        assert_eq!(
+            actual.clauses()[1].as_ref(),
+            &InstanceClause::InstanceOf(ClassIdent::from(PrefixedIdent::new("FOAF", "Person")))
+        );
+        assert_eq!(
+            actual.clauses()[2].as_ref(),
+            &InstanceClause::Relationship(
+                RelationIdent::from(UnprefixedIdent::new("married_to")),
+                InstanceIdent::from(UnprefixedIdent::new("jane")),
+            )
+        );
+    }
+
+    #[test]
+    fn to_string() {
+        let frame = InstanceFrame::with_clauses(
+            InstanceIdent::from(UnprefixedIdent::new("john")),
+            vec![
+                Line::from(InstanceClause::Name(UnquotedString::new("John Doe"))),
+                Line::from(InstanceClause::InstanceOf(
+                    ClassIdent::from(PrefixedIdent::new("FOAF", "Person"))
+                )),
+            ],
+        );
+        assert_eq!(
+            frame.to_string(),
+            "[Instance]\nid: john\nname: John Doe\ninstance_of: FOAF:Person\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let frame = InstanceFrame::with_clauses(
+            InstanceIdent::from(PrefixedIdent::new("EX", "0000001")),
+            vec![
+                Line::from(InstanceClause::InstanceOf(
+                    ClassIdent::from(PrefixedIdent::new("EX", "Person"))
+                )),
+                Line::from(InstanceClause::Relationship(
+                    RelationIdent::from(UnprefixedIdent::new("knows")),
+                    InstanceIdent::from(PrefixedIdent::new("EX", "0000002")),
+                )).and_comment(Comment::new("Jane")),
+                Line::from(InstanceClause::ReplacedBy(
+                    InstanceIdent::from(PrefixedIdent::new("EX", "0000003"))
+                )),
+            ],
+        );
+        let actual = InstanceFrame::from_str(&frame.to_string()).unwrap();
+        assert_eq!(actual, frame);
+    }
+
+    #[test]
+    fn entity_frame() {
+        let actual = EntityFrame::from_str(
+            "[Instance]\nid: john\ninstance_of: FOAF:Person\n"
+        ).unwrap();
+        let expected = EntityFrame::Instance(InstanceFrame::with_clauses(
+            InstanceIdent::from(UnprefixedIdent::new("john")),
+            vec![Line::from(InstanceClause::InstanceOf(
+                ClassIdent::from(PrefixedIdent::new("FOAF", "Person"))
+            ))],
+        ));
+        assert_eq!(actual, expected);
+    }
+}
@@ -2,4 +2,6 @@ mod clause;
 mod frame;
 
 pub use self::clause::InstanceClause;
+pub use self::clause::InstanceClauseRef;
 pub use self::frame::InstanceFrame;
+pub use self::frame::InstanceFrameRef;
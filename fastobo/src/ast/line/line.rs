@@ -4,6 +4,8 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::fmt::Write;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
@@ -13,13 +15,23 @@ use crate::ast::*;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
+use crate::share::Redeem;
 
 /// A line in an OBO file, possibly followed by qualifiers and a comment.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+///
+/// Lines obtained from the parser also record their location in the source
+/// document, which is not taken into account when comparing or hashing them.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Line<T> {
+    #[cfg_attr(feature = "serde", serde(rename = "value"))]
     inner: T,
+    #[cfg_attr(feature = "serde", serde(default))]
     qualifiers: Option<QualifierList>, // FIXME(@althonos): use an `IndexMap` ?
+    #[cfg_attr(feature = "serde", serde(default))]
     comment: Option<Comment>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    location: Option<SourceLocation>,
 }
 
 impl<T> Line<T> {
@@ -38,9 +50,8 @@ impl<T> Line<T> {
         C: Into<Option<Comment>>
     {
         Self {
-            inner: self.inner,
-            qualifiers: self.qualifiers,
             comment: comment.into(),
+            ..self
         }
     }
 
@@ -50,18 +61,96 @@ impl<T> Line<T> {
         Q: Into<Option<QualifierList>>
     {
         Self {
-            inner: self.inner,
             qualifiers: qualifiers.into(),
-            comment: self.comment,
+            ..self
+        }
+    }
+
+    /// Update the location of the line in the source document.
+    pub fn and_location<L>(self, location: L) -> Self
+    where
+        L: Into<Option<SourceLocation>>
+    {
+        Self {
+            location: location.into(),
+            ..self
         }
     }
 
+    /// Get the qualifiers of the line, if any.
+    pub fn qualifiers(&self) -> Option<&QualifierList> {
+        self.qualifiers.as_ref()
+    }
+
+    /// Get a mutable reference to the qualifiers of the line, if any.
+    pub fn qualifiers_mut(&mut self) -> Option<&mut QualifierList> {
+        self.qualifiers.as_mut()
+    }
+
+    /// Get the comment of the line, if any.
+    pub fn comment(&self) -> Option<&Comment> {
+        self.comment.as_ref()
+    }
+
+    /// Get a mutable reference to the comment of the line, if any.
+    pub fn comment_mut(&mut self) -> Option<&mut Comment> {
+        self.comment.as_mut()
+    }
+
+    /// Get the location of the line in the source document, if known.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let frame = TermFrame::from_str("[Term]\nid: TST:001\nname: test\n").unwrap();
+    /// let location = frame.clauses()[0].location().unwrap();
+    /// assert_eq!((location.line, location.column), (3, 1));
+    /// assert_eq!((location.start, location.end), (19, 30));
+    /// ```
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
+
+    /// Get a mutable reference to the location of the line, if known.
+    pub fn location_mut(&mut self) -> Option<&mut SourceLocation> {
+        self.location.as_mut()
+    }
+
     /// Get the actual OBO clause wrapped in the line.
     pub fn into_inner(self) -> T {
         self.inner
     }
 }
 
+impl<T> PartialEq for Line<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+            && self.qualifiers == other.qualifiers
+            && self.comment == other.comment
+    }
+}
+
+impl<T> Eq for Line<T> where T: Eq {}
+
+impl<T> Hash for Line<T>
+where
+    T: Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.inner.hash(state);
+        self.qualifiers.hash(state);
+        self.comment.hash(state);
+    }
+}
+
 impl<T> AsRef<T> for Line<T> {
     fn as_ref(&self) -> &T {
         &self.inner
@@ -92,6 +181,7 @@ impl<T> From<T> for Line<T> {
             inner,
             qualifiers: None,
             comment: None,
+            location: None,
         }
     }
 }
@@ -109,6 +199,21 @@ impl<T> DerefMut for Line<T> {
     }
 }
 
+impl<'a, T> Redeem<'a> for Line<T>
+where
+    T: Redeem<'a>,
+{
+    type Owned = Line<<T as Redeem<'a>>::Owned>;
+    fn redeem(&'a self) -> Self::Owned {
+        Line {
+            inner: self.inner.redeem(),
+            qualifiers: self.qualifiers.clone(),
+            comment: self.comment.clone(),
+            location: self.location.clone(),
+        }
+    }
+}
+
 impl<T> Display for Line<T>
 where
     T: Display,
@@ -181,6 +286,7 @@ impl Default for Eol {
             inner: (),
             qualifiers: None,
             comment: None,
+            location: None,
         }
     }
 }
@@ -208,6 +314,7 @@ impl Eol {
             inner,
             qualifiers: self.qualifiers,
             comment: self.comment,
+            location: self.location,
         }
     }
 }
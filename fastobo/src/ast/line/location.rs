@@ -0,0 +1,89 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::path::Path;
+use std::sync::Arc;
+
+use pest::iterators::Pair;
+use pest::Span;
+
+use crate::parser::Rule;
+
+/// The location of a parsed element in its source document.
+///
+/// Lines and columns are 1-based, as reported by the parser errors, while
+/// byte offsets are 0-based and relative to the start of the document.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SourceLocation {
+    /// The offset of the first byte of the element.
+    pub start: usize,
+    /// The offset of the byte following the element.
+    pub end: usize,
+    /// The line where the element starts.
+    pub line: usize,
+    /// The column where the element starts.
+    pub column: usize,
+    /// The path to the source file, if the element was read from a file.
+    pub path: Option<Arc<Path>>,
+}
+
+impl SourceLocation {
+    /// Create a new location from a byte range and a line and column.
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+            path: None,
+        }
+    }
+
+    /// Update the location with the path to the source file.
+    pub fn and_path<P>(self, path: P) -> Self
+    where
+        P: Into<Option<Arc<Path>>>,
+    {
+        Self {
+            path: path.into(),
+            ..self
+        }
+    }
+
+    /// Create a new location spanning from the start of `start` to the end of `end`.
+    ///
+    /// Lines and columns are resolved with the line index of the parse, so
+    /// this does not rescan the input from its beginning.
+    pub(crate) fn from_pairs(start: &Pair<Rule>, end: &Pair<Rule>) -> Self {
+        let (line, column) = start.line_col();
+        Self::new(start.as_span().start(), end.as_span().end(), line, column)
+    }
+
+    /// Create a new location from a span of the parsed input.
+    pub(crate) fn from_span(span: Span) -> Self {
+        let (line, column) = span.start_pos().line_col();
+        Self::new(span.start(), span.end(), line, column)
+    }
+
+    /// Shift the location by the given number of lines and bytes.
+    pub(crate) fn shift(&mut self, line_offset: usize, offset: usize) {
+        self.start += offset;
+        self.end += offset;
+        self.line += line_offset;
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if let Some(ref path) = self.path {
+            write!(f, "{}:", path.display())?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl<'i> From<&Pair<'i, Rule>> for SourceLocation {
+    fn from(pair: &Pair<'i, Rule>) -> Self {
+        Self::from_pairs(pair, pair)
+    }
+}
@@ -1,5 +1,7 @@
 mod comment;
 mod line;
+mod location;
 
 pub use self::comment::*;
 pub use self::line::*;
+pub use self::location::*;
@@ -1,25 +1,39 @@
 //! Owned syntax tree for the [OBO format version 1.4].
 //!
+//! Most of the nodes also have a *borrowed* counterpart (*e.g.* [`OboDocRef`]
+//! for [`OboDoc`]) that references the parsed string slice whenever possible.
+//! They can be parsed with [`FromSlice::from_slice`], and converted to their
+//! owned version using [`Redeem::redeem`].
+//!
 //! [`OboDoc`] is the struct acting as the root of the syntax tree. It can be
 //! created from a borrowed string slice with either [`FromStr::from_str`] or
-//! [`FromSlice::from_slice`], from a file with [`OboDoc::from_file`], or from
-//! a buffered reader with [`OboDoc::from_stream`].
+//! [`FromSlice::from_slice`], from a file with [`OboDoc::from_file`] (or
+//! [`OboDoc::from_mmap`] with the `mmap` feature), or from a buffered reader
+//! with [`OboDoc::from_stream`].
 //!
 //! [`FromStr::from_str`]: https://doc.rust-lang.org/std/str/trait.FromStr.html#tymethod.from_str
 //! [`FromSlice::from_slice`]: ../parser/trait.FromSlice.html#method.from_slice
 //! [`OboDoc`]: ./struct.OboDoc.html
+//! [`OboDocRef`]: ./struct.OboDocRef.html
+//! [`Redeem::redeem`]: ../share/trait.Redeem.html#tymethod.redeem
 //! [`OboDoc::from_file`]: ./struct.OboDoc.html#method.from_file
+//! [`OboDoc::from_mmap`]: ./struct.OboDoc.html#method.from_mmap
 //! [`OboDoc::from_stream`]: ./struct.OboDoc.html#method.from_stream
 //! [OBO format version 1.4]: http://owlcollab.github.io/oboformat/doc/GO.format.obo-1_4.html.
 
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 mod date;
+mod escape;
 mod header;
 mod id;
 mod instance;
 mod line;
 mod pv;
 mod qualifier;
+#[cfg(feature = "serde")]
+mod serde;
 mod strings;
 mod synonym;
 mod term;
@@ -46,20 +60,36 @@ use std::fmt::Write;
 use std::fs::File;
 use std::io::BufRead;
 use std::io::BufReader;
+#[cfg(feature = "encoding_rs")]
+use std::io::Read;
 use std::path::Path;
 use std::str::FromStr;
 
+#[cfg(feature = "encoding_rs")]
+use encoding_rs::Encoding;
+#[cfg(feature = "async")]
+use futures::io::AsyncBufRead;
 use pest::iterators::Pair;
 use pest::Parser;
 
 use crate::error::Error;
 use crate::error::Result;
+#[cfg(feature = "async")]
+use crate::parser::AsyncFrameReader;
+use crate::parser::Codec;
+use crate::parser::FrameChunk;
+use crate::parser::FrameReader;
 use crate::parser::FromPair;
+#[cfg(feature = "memmap")]
+use crate::parser::MappedFile;
 use crate::parser::OboParser;
+use crate::parser::Rewrite;
 use crate::parser::Rule;
+use crate::share::Redeem;
 
 /// A complete OBO document in format version 1.4.
 #[derive(Clone, Default, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct OboDoc {
     pub header: HeaderFrame,
     pub entities: Vec<EntityFrame>,
@@ -86,88 +116,194 @@ impl OboDoc {
     }
 
     /// Consume a buffered stream containing an OBO document into an AST.
+    ///
+    /// Streams compressed with `gzip`, `bzip2` or `xz` are detected from
+    /// their first bytes, and decompressed if the matching feature (`gzip`,
+    /// `bz2` or `xz`) is enabled.
+    ///
+    /// Use [`FrameReader`](../parser/struct.FrameReader.html) directly to
+    /// process the entity frames of large documents one at a time.
     pub fn from_stream<B>(stream: &mut B) -> Result<Self>
     where
         B: BufRead,
     {
-        let mut line = String::new();
-        let mut l: &str;
+        match Codec::from_stream(stream)? {
+            Some(codec) => {
+                let decoder = BufReader::new(codec.decoder(stream)?);
+                Self::from_reader(FrameReader::new(decoder)?)
+            }
+            None => Self::from_reader(FrameReader::new(stream)?),
+        }
+    }
 
-        let mut offset = 0;
-        let mut line_offset = 0;
+    /// Consume a stream containing an OBO document in the given encoding.
+    ///
+    /// See [`FrameReader::with_encoding`] for details about how the encoding
+    /// of the stream is detected.
+    ///
+    /// [`FrameReader::with_encoding`]: ../parser/struct.FrameReader.html#method.with_encoding
+    #[cfg(feature = "encoding_rs")]
+    pub fn from_stream_with_encoding<R>(
+        stream: &mut R,
+        encoding: Option<&'static Encoding>,
+    ) -> Result<Self>
+    where
+        R: Read,
+    {
+        Self::from_reader(FrameReader::with_encoding(stream, encoding)?)
+    }
 
-        // collect the header frame
-        let mut frame_clauses = Vec::new();
-        loop {
-            // Read the next line
-            line.clear();
-            stream.read_line(&mut line)?;
-            l = line.trim();
-
-            // Parse header as long as we didn't reach EOL or first frame.
-            if !l.starts_with('[') && !l.is_empty() {
-                unsafe {
-                    let mut pairs = OboParser::parse(Rule::HeaderClause, &line)
-                        .map_err(|e| Error::from(e).with_offsets(line_offset, offset))?;
-                    let clause = HeaderClause::from_pair_unchecked(pairs.next().unwrap())?;
-                    frame_clauses.push(clause);
-                }
-            }
+    /// Consume all the frames of a `FrameReader` into an AST.
+    fn from_reader<B>(mut reader: FrameReader<B>) -> Result<Self>
+    where
+        B: BufRead,
+    {
+        let entities = reader.by_ref().collect::<Result<Vec<_>>>()?;
+        Ok(Self::with_entities(reader.into_header(), entities))
+    }
 
-            // Update offsets
-            line_offset += 1;
-            offset += line.len();
+    /// Consume an asynchronous buffered stream containing an OBO document.
+    ///
+    /// Use [`AsyncFrameReader`](../parser/struct.AsyncFrameReader.html)
+    /// directly to process the entity frames one at a time.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # extern crate futures;
+    /// # use fastobo::ast::*;
+    /// let mut stream = &b"format-version: 1.4\n\n[Term]\nid: TST:001\n"[..];
+    /// let doc = futures::executor::block_on(OboDoc::from_async_stream(&mut stream)).unwrap();
+    /// assert_eq!(doc.entities.len(), 1);
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn from_async_stream<B>(stream: &mut B) -> Result<Self>
+    where
+        B: AsyncBufRead + Unpin,
+    {
+        let mut reader = AsyncFrameReader::new(stream).await?;
+        let mut entities = Vec::new();
+        while let Some(frame) = reader.next_frame().await {
+            entities.push(frame?);
+        }
+        Ok(Self::with_entities(reader.into_header(), entities))
+    }
 
-            // Bail out if we reached EOL or first frame.
-            if l.starts_with('[') || line.is_empty() {
-                break
+    /// Consume a buffered stream containing an OBO document, skipping errors.
+    ///
+    /// Invalid header clauses, entity clauses and entity frames are skipped
+    /// instead of aborting the whole parsing, and the errors are returned
+    /// alongside the partial document, with their position in the stream.
+    /// IO errors are still returned immediately.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// let mut stream = &b"format-version: 1.4\n\n[Term]\nid: TST:001\nis_a: \n"[..];
+    /// let (doc, errors) = OboDoc::from_stream_lenient(&mut stream).unwrap();
+    /// assert_eq!(doc.entities.len(), 1);
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn from_stream_lenient<B>(stream: &mut B) -> Result<(Self, Vec<Error>)>
+    where
+        B: BufRead,
+    {
+        let mut errors = Vec::new();
+        let mut reader = FrameReader::new_lenient(stream, &mut errors)?;
+        let mut entities = Vec::new();
+        while let Some(chunk) = reader.next_chunk() {
+            if let Some(frame) = chunk?.parse_lenient(&mut errors) {
+                entities.push(frame);
             }
         }
+        Ok((Self::with_entities(reader.into_header(), entities), errors))
+    }
 
-        // create the OBO document
-        let mut obodoc = Self::new(HeaderFrame::new(frame_clauses));
-
-        // read all entity frames
-        let mut frame_lines = String::new();
-        let mut local_line_offset = 0;
-        let mut local_offset = 0;
-        while !line.is_empty() {
-
-            // Read the next line.
-            frame_lines.push_str(&line);
-            line.clear();
-            stream.read_line(&mut line)?;
-
-            // Read the line if we reached the next frame.
-            if line.trim_start().starts_with('[') || line.is_empty() {
-
-                unsafe {
-                    let mut pairs = OboParser::parse(Rule::EntitySingle, &frame_lines)
-                        .map_err(|e| Error::from(e).with_offsets(line_offset, offset))?;
-                    let entity = EntityFrame::from_pair_unchecked(pairs.next().unwrap())?;
-                    obodoc.entities.push(entity);
-                    frame_lines.clear()
-                }
+    /// Consume a buffered stream containing an OBO 1.2 document, upgrading it to OBO 1.4.
+    ///
+    /// Legacy constructs are rewritten into their OBO 1.4 equivalent (see
+    /// [`FrameReader::and_legacy`]), and the rewrites are returned alongside
+    /// the document so that they can be reported.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// let mut stream = &b"[Term]\nid: TST:001\nuse_term: TST:002\n"[..];
+    /// let (doc, rewrites) = OboDoc::from_stream_legacy(&mut stream).unwrap();
+    /// assert_eq!(doc.entities[0].to_string(), "[Term]\nid: TST:001\nconsider: TST:002\n");
+    /// assert_eq!(rewrites[0].original(), "use_term: TST:002");
+    /// ```
+    ///
+    /// [`FrameReader::and_legacy`]: ../parser/struct.FrameReader.html#method.and_legacy
+    pub fn from_stream_legacy<B>(stream: &mut B) -> Result<(Self, Vec<Rewrite>)>
+    where
+        B: BufRead,
+    {
+        let mut reader = FrameReader::new(stream)?.and_legacy(true);
+        let entities = reader.by_ref().collect::<Result<Vec<_>>>()?;
+        let rewrites = reader.take_rewrites();
+        Ok((Self::with_entities(reader.into_header(), entities), rewrites))
+    }
 
-                // Update offsets
-                line_offset += local_line_offset;
-                offset += local_offset;
+    /// Consume a buffered stream containing an OBO document using several threads.
+    ///
+    /// Entity frames are read sequentially from the stream, and then parsed
+    /// in batches on the `rayon` global thread pool. Frames are returned in
+    /// the order they appear in the stream, and the reported error (if any)
+    /// is the first one that would have been encountered by `from_stream`.
+    #[cfg(feature = "threading")]
+    pub fn from_stream_parallel<B>(stream: &mut B) -> Result<Self>
+    where
+        B: BufRead,
+    {
+        use rayon::prelude::*;
 
-                // Reset local offsets
-                local_line_offset = 0;
-                local_offset = 0;
+        // Number of frames to read before dispatching them to the workers.
+        const BATCH_SIZE: usize = 1024;
 
+        let mut reader = FrameReader::new(stream)?;
+        let mut entities = Vec::new();
+        let mut chunks = Vec::with_capacity(BATCH_SIZE);
+        loop {
+            // Read a batch of frames, keeping any IO error for later.
+            let mut read_error = None;
+            while chunks.len() < BATCH_SIZE {
+                match reader.next_chunk() {
+                    Some(Ok(chunk)) => chunks.push(chunk),
+                    Some(Err(e)) => {
+                        read_error = Some(e);
+                        break;
+                    }
+                    None => break,
+                }
+            }
+
+            // Parse the frames in parallel, preserving their order.
+            let last_batch = chunks.len() < BATCH_SIZE;
+            let frames: Vec<Result<EntityFrame>> =
+                chunks.par_iter().map(FrameChunk::parse).collect();
+            for frame in frames {
+                entities.push(frame?);
             }
+            chunks.clear();
 
-            // Update local offsets
-            local_line_offset += 1;
-            local_offset += line.len();
+            if let Some(e) = read_error {
+                return Err(e);
+            } else if last_batch {
+                break;
+            }
         }
 
-        Ok(obodoc)
+        Ok(Self::with_entities(reader.into_header(), entities))
     }
 
     /// Read an OBO file located somwhere in the filesystem.
+    ///
+    /// The path of the file is recorded in the location of each entity line.
+    /// Compressed files are detected from their extension (`.gz`, `.bz2`
+    /// or `.xz`) or from their first bytes, like in `from_stream`.
     pub fn from_file<P>(path: P) -> Result<Self>
     where
         P: AsRef<Path>,
@@ -175,7 +311,37 @@ impl OboDoc {
         let pathref: &Path = path.as_ref();
         File::open(pathref)
             .map_err(Error::from)
-            .and_then(|f| Self::from_stream(&mut BufReader::new(f)))
+            .and_then(|f| {
+                let mut stream = BufReader::new(f);
+                let codec = match Codec::from_path(pathref) {
+                    Some(codec) => Some(codec),
+                    None => Codec::from_stream(&mut stream)?,
+                };
+                match codec {
+                    Some(codec) => {
+                        let decoder = BufReader::new(codec.decoder(stream)?);
+                        Self::from_reader(FrameReader::new(decoder)?.and_path(pathref))
+                    }
+                    None => Self::from_reader(FrameReader::new(stream)?.and_path(pathref)),
+                }
+            })
+            .map_err(|e| e.with_path(&pathref.to_string_lossy()))
+    }
+
+    /// Read an OBO file located somewhere in the filesystem using a memory map.
+    ///
+    /// The file is parsed directly from the mapped memory instead of being
+    /// copied line by line, which avoids a large amount of copies for big
+    /// ontologies. See [`MappedFile`](../parser/struct.MappedFile.html) to
+    /// parse the mapped file into a borrowed `OboDocRef` instead.
+    #[cfg(feature = "memmap")]
+    pub fn from_mmap<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let pathref: &Path = path.as_ref();
+        MappedFile::open(pathref)
+            .and_then(|file| file.parse())
             .map_err(|e| e.with_path(&pathref.to_string_lossy()))
     }
 
@@ -227,8 +393,88 @@ impl<'i> FromPair<'i> for OboDoc {
 }
 impl_fromstr!(OboDoc);
 
+/// A borrowed `OboDoc`.
+///
+/// All the strings and identifiers of the document are borrowed from the
+/// input slice whenever possible, which avoids allocating a `String` for
+/// each clause when scanning large documents. Use [`redeem`] to obtain
+/// an owned [`OboDoc`] afterwards.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::ast::*;
+/// # use fastobo::parser::FromSlice;
+/// # use fastobo::share::Redeem;
+/// let text = "format-version: 1.4\n\n[Term]\nid: TST:001\nname: test term\n";
+/// let doc = OboDocRef::from_slice(text).unwrap();
+/// assert_eq!(doc.entities.len(), 1);
+///
+/// let owned: OboDoc = doc.redeem();
+/// assert_eq!(owned.to_string(), text);
+/// ```
+///
+/// [`redeem`]: ../share/trait.Redeem.html#tymethod.redeem
+/// [`OboDoc`]: ./struct.OboDoc.html
+#[derive(Clone, Debug, Default)]
+pub struct OboDocRef<'a> {
+    pub header: HeaderFrameRef<'a>,
+    pub entities: Vec<EntityFrameRef<'a>>,
+}
+
+impl<'a> OboDocRef<'a> {
+    /// Create a new borrowed OBO document with the provided entity frames.
+    pub fn with_entities(header: HeaderFrameRef<'a>, entities: Vec<EntityFrameRef<'a>>) -> Self {
+        Self { header, entities }
+    }
+}
+
+impl<'a> Display for OboDocRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.header.fmt(f).and(f.write_char('\n'))?;
+        let mut entities = self.entities.iter().peekable();
+        while let Some(entity) = entities.next() {
+            entity.fmt(f)?;
+            if entities.peek().is_some() {
+                f.write_char('\n')?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'i> FromPair<'i> for OboDocRef<'i> {
+    const RULE: Rule = Rule::OboDoc;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+
+        let mut entities = Vec::new();
+        let header = HeaderFrameRef::from_pair_unchecked(inner.next().unwrap())?;
+
+        let mut pair = inner.next().unwrap();
+        while pair.as_rule() != Rule::EOI {
+            entities.push(EntityFrameRef::from_pair_unchecked(pair)?);
+            pair = inner.next().unwrap();
+        }
+        Ok(OboDocRef { header, entities })
+    }
+}
+impl_fromslice!('i, OboDocRef<'i>);
+
+impl<'a> Redeem<'a> for OboDocRef<'a> {
+    type Owned = OboDoc;
+    fn redeem(&'a self) -> OboDoc {
+        OboDoc::with_entities(
+            self.header.redeem(),
+            self.entities.iter().map(|entity| entity.redeem()),
+        )
+    }
+}
+
 /// An entity frame, either for a term, an instance, or a typedef.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum EntityFrame {
     Term(TermFrame),
     Typedef(TypedefFrame),
@@ -271,9 +517,148 @@ impl<'i> FromPair<'i> for EntityFrame {
         match inner.as_rule() {
             Rule::TermFrame => TermFrame::from_pair_unchecked(inner).map(From::from),
             Rule::TypedefFrame => TypedefFrame::from_pair_unchecked(inner).map(From::from),
-            Rule::InstanceFrame => unimplemented!(),
+            Rule::InstanceFrame => InstanceFrame::from_pair_unchecked(inner).map(From::from),
             _ => unreachable!(),
         }
     }
 }
 impl_fromstr!(EntityFrame);
+
+/// A borrowed `EntityFrame`.
+#[derive(Clone, Debug)]
+pub enum EntityFrameRef<'a> {
+    Term(TermFrameRef<'a>),
+    Typedef(TypedefFrameRef<'a>),
+    Instance(InstanceFrameRef<'a>),
+}
+
+impl<'a> Display for EntityFrameRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::EntityFrameRef::*;
+        match self {
+            Term(t) => t.fmt(f),
+            Typedef(t) => t.fmt(f),
+            Instance(i) => i.fmt(f),
+        }
+    }
+}
+
+impl<'a> From<TermFrameRef<'a>> for EntityFrameRef<'a> {
+    fn from(frame: TermFrameRef<'a>) -> Self {
+        EntityFrameRef::Term(frame)
+    }
+}
+
+impl<'a> From<TypedefFrameRef<'a>> for EntityFrameRef<'a> {
+    fn from(frame: TypedefFrameRef<'a>) -> Self {
+        EntityFrameRef::Typedef(frame)
+    }
+}
+
+impl<'a> From<InstanceFrameRef<'a>> for EntityFrameRef<'a> {
+    fn from(frame: InstanceFrameRef<'a>) -> Self {
+        EntityFrameRef::Instance(frame)
+    }
+}
+
+impl<'i> FromPair<'i> for EntityFrameRef<'i> {
+    const RULE: Rule = Rule::EntityFrame;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let inner = pair.into_inner().next().unwrap();
+        match inner.as_rule() {
+            Rule::TermFrame => TermFrameRef::from_pair_unchecked(inner).map(From::from),
+            Rule::TypedefFrame => TypedefFrameRef::from_pair_unchecked(inner).map(From::from),
+            Rule::InstanceFrame => InstanceFrameRef::from_pair_unchecked(inner).map(From::from),
+            _ => unreachable!(),
+        }
+    }
+}
+impl_fromslice!('i, EntityFrameRef<'i>);
+
+impl<'a> Redeem<'a> for EntityFrameRef<'a> {
+    type Owned = EntityFrame;
+    fn redeem(&'a self) -> EntityFrame {
+        match self {
+            EntityFrameRef::Term(t) => EntityFrame::Term(t.redeem()),
+            EntityFrameRef::Typedef(t) => EntityFrame::Typedef(t.redeem()),
+            EntityFrameRef::Instance(i) => EntityFrame::Instance(i.redeem()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(feature = "threading")]
+    #[test]
+    fn from_stream_parallel() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/ms.obo");
+        let expected = OboDoc::from_file(&path).unwrap();
+        let file = File::open(&path).unwrap();
+        let actual = OboDoc::from_stream_parallel(&mut BufReader::new(file)).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(feature = "threading")]
+    #[test]
+    fn from_stream_parallel_error() {
+        let mut doc = String::from("format-version: 1.4\n");
+        for i in 0..2000 {
+            doc.push_str(&format!("\n[Term]\nid: TST:{:04}\n", i));
+        }
+        doc.push_str("\n[Term]\nid: TST:9999\nis_a: \n");
+        let expected = OboDoc::from_stream(&mut doc.as_bytes()).unwrap_err();
+        let actual = OboDoc::from_stream_parallel(&mut doc.as_bytes()).unwrap_err();
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn from_stream_lenient() {
+        let doc = "format-version: 1.4
+not-a-clause
+ontology: test
+
+[Term]
+id: TST:001
+is_a: 
+name: first term
+
+[Term
+id: TST:002
+
+[Typedef]
+id: part_of
+";
+        let (doc, errors) = OboDoc::from_stream_lenient(&mut doc.as_bytes()).unwrap();
+        assert_eq!(doc.header().len(), 2);
+        assert_eq!(doc.entities.len(), 2);
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn obo_doc_ref() {
+        use crate::parser::FromSlice;
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/ms.obo");
+        let text = std::fs::read_to_string(&path).unwrap();
+        let expected = OboDoc::from_str(&text).unwrap();
+        let actual = OboDocRef::from_slice(&text).unwrap();
+        assert_eq!(actual.redeem(), expected);
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn from_file_locations() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/ms.obo");
+        let doc = OboDoc::from_file(&path).unwrap();
+        match &doc.entities[0] {
+            EntityFrame::Term(frame) => {
+                let location = frame.id().location().unwrap();
+                assert_eq!(location.path.as_ref().map(|p| p.as_ref()), Some(path.as_path()));
+            }
+            other => panic!("unexpected frame: {:?}", other),
+        }
+    }
+}
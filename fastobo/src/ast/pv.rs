@@ -13,11 +13,14 @@ use crate::share::Share;
 use crate::share::Redeem;
 use crate::error::Result;
 use crate::parser::FromPair;
+use crate::parser::FromSlice;
 use crate::parser::Rule;
 
 
 /// A clause value binding a property to a value in the relevant entity.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum PropertyValue {
     /// A property-value binding where the value is specified with an ID.
     Identified(RelationIdent, Ident),
@@ -75,11 +78,39 @@ impl<'i> FromPair<'i> for PropertyValue {
 impl_fromstr!(PropertyValue);
 
 /// A borrowed `PropertyValue`.
+#[derive(Clone, Debug)]
 pub enum PropVal<'a> {
     Identified(Cow<'a, RelationId<'a>>, Cow<'a, Id<'a>>),
     Typed(Cow<'a, RelationId<'a>>, Cow<'a, &'a QuotedStr>, Cow<'a, Id<'a>>)
 }
 
+impl<'i> FromPair<'i> for PropVal<'i> {
+    const RULE: Rule = Rule::PropertyValue;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        let relid = RelationId::from_pair_unchecked(inner.next().unwrap()).map(Cow::Borrowed)?;
+        let second = inner.next().unwrap();
+        match second.as_rule() {
+            Rule::Id => {
+                let id = Id::from_pair_unchecked(second).map(Cow::Borrowed)?;
+                Ok(PropVal::Identified(relid, id))
+            }
+            Rule::PvValue => {
+                let desc = Cow::Borrowed(QuotedStr::new(second.as_str()));
+                let datatype = Id::from_slice(inner.next().unwrap().as_str()).map(Cow::Borrowed)?;
+                Ok(PropVal::Typed(relid, desc, datatype))
+            }
+            Rule::QuotedString => {
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(second)?;
+                let datatype = Id::from_slice(inner.next().unwrap().as_str()).map(Cow::Borrowed)?;
+                Ok(PropVal::Typed(relid, desc, datatype))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+impl_fromslice!('i, PropVal<'i>);
+
 impl<'a> Redeem<'a> for PropVal<'a> {
     type Owned = PropertyValue;
     fn redeem(&'a self) -> PropertyValue {
@@ -13,6 +13,7 @@ use crate::parser::Rule;
 
 /// A qualifier, possibly used as a trailing modifier.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Qualifier {
     key: RelationIdent,
     value: QuotedString,
@@ -22,6 +23,16 @@ impl Qualifier {
     pub fn new(key: RelationIdent, value: QuotedString) -> Self {
         Self { key, value }
     }
+
+    /// Get the relation identifier of the qualifier.
+    pub fn key(&self) -> &RelationIdent {
+        &self.key
+    }
+
+    /// Get the value of the qualifier.
+    pub fn value(&self) -> &QuotedString {
+        &self.value
+    }
 }
 
 impl Display for Qualifier {
@@ -56,6 +67,8 @@ impl_fromstr!(Qualifier);
     FromInner,
     PartialEq(Inner),
 ))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct QualifierList {
     qualifiers: Vec<Qualifier>,
 }
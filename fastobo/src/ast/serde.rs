@@ -0,0 +1,196 @@
+//! `Serialize` and `Deserialize` implementations, enabled by the `serde` feature.
+//!
+//! Structured nodes (documents, frames, lines, clauses, xrefs, synonyms and
+//! property values) derive their implementations, with clauses tagged by
+//! their OBO tag, for instance `{"is_a": "GO:0008150"}`. Identifiers, dates
+//! and imports are serialized as their OBO string, and string nodes as their
+//! unescaped value, so that the representation stays compact and readable
+//! in any human-readable format.
+
+use std::fmt::Display;
+use std::result::Result;
+use std::str::FromStr;
+
+use ::serde::de::Error as DeError;
+use ::serde::Deserialize;
+use ::serde::Deserializer;
+use ::serde::Serialize;
+use ::serde::Serializer;
+use url::Url;
+
+use crate::ast::*;
+
+/// Serialize a value as its OBO string.
+fn serialize_display<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+/// Deserialize a value by parsing an OBO string.
+fn deserialize_fromstr<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    T::from_str(&s).map_err(D::Error::custom)
+}
+
+/// Implement `Serialize` and `Deserialize` through `Display` and `FromStr`.
+macro_rules! serde_fromstr {
+    ($($type:ty),*) => {
+        $(
+            impl Serialize for $type {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize_display(self, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $type {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize_fromstr(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+/// Implement `Serialize` and `Deserialize` through the unescaped string value.
+macro_rules! serde_string {
+    ($($type:ty),*) => {
+        $(
+            impl Serialize for $type {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.as_str())
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $type {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    String::deserialize(deserializer).map(<$type>::new)
+                }
+            }
+        )*
+    };
+}
+
+serde_fromstr!(
+    Ident,
+    PrefixedIdent,
+    UnprefixedIdent,
+    ClassIdent,
+    RelationIdent,
+    InstanceIdent,
+    SubsetIdent,
+    SynonymTypeIdent,
+    NamespaceIdent,
+    NaiveDateTime,
+    IsoDateTime,
+    Import
+);
+
+serde_string!(IdentPrefix, IdentLocal, QuotedString, UnquotedString);
+
+impl Serialize for Comment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Comment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Comment::new)
+    }
+}
+
+/// Serialize an URL field as a string, since `url` does not support `serde` 1.0.
+pub(crate) mod url_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(url: &Url, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_display(url, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Url, D::Error> {
+        deserialize_fromstr(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use serde_yaml;
+
+    use super::*;
+
+    #[test]
+    fn ident() {
+        let id = ClassIdent::from_str("GO:0008150").unwrap();
+        assert_eq!(serde_yaml::to_string(&id).unwrap().trim(), "---\n\"GO:0008150\"");
+        let id2: ClassIdent = serde_yaml::from_str("GO:0008150").unwrap();
+        assert_eq!(id, id2);
+        assert!(serde_yaml::from_str::<ClassIdent>("GO 0008150").is_err());
+    }
+
+    #[test]
+    fn strings() {
+        let s = UnquotedString::new("Hello, world!");
+        assert_eq!(serde_yaml::to_string(&s).unwrap().trim(), "---\n\"Hello, world!\"");
+        let s2: UnquotedString = serde_yaml::from_str("Hello, world!").unwrap();
+        assert_eq!(s, s2);
+    }
+
+    #[test]
+    fn line() {
+        let line = Line::from(TermClause::IsA(ClassIdent::from_str("GO:0008150").unwrap()))
+            .and_comment(Comment::new("biological_process"));
+        let yaml = serde_yaml::to_string(&line).unwrap();
+        assert_eq!(
+            yaml.trim(),
+            "---\nvalue:\n  is_a: \"GO:0008150\"\nqualifiers: ~\ncomment: biological_process"
+        );
+        let line2: Line<TermClause> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(line, line2);
+        assert_eq!(line.comment(), line2.comment());
+    }
+
+    #[test]
+    fn obo_doc() {
+        let doc = OboDoc::from_str(
+            "format-version: 1.4
+date: 13:06:2019 17:30
+idspace: GO http://purl.obolibrary.org/obo/GO_ \"Gene Ontology\"
+treat-xrefs-as-is_a: CL
+
+[Term]
+id: GO:0008150
+name: biological_process
+def: \"A biological process.\" [GOC:go_curators]
+synonym: \"physiological process\" EXACT [] {comment=\"legacy\"}
+creation_date: 2019-06-13T17:30:00Z
+property_value: IAO:0000589 \"process\" xsd:string
+
+[Typedef]
+id: part_of
+is_transitive: true ! transitive
+
+[Instance]
+id: john
+instance_of: NCBITaxon:9606
+",
+        )
+        .unwrap();
+        let yaml = serde_yaml::to_string(&doc).unwrap();
+        assert!(yaml.contains("treat-xrefs-as-is_a: CL"));
+        assert!(yaml.contains("creation_date: \"2019-06-13T17:30:00Z\""));
+        let doc2: OboDoc = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(doc, doc2);
+        assert_eq!(doc.to_string(), doc2.to_string());
+    }
+}
@@ -1,38 +1,5 @@
-use std::fmt::Result as FmtResult;
-use std::fmt::Write;
-
 mod quoted;
 mod unquoted;
 
 pub use self::quoted::*;
 pub use self::unquoted::*;
-
-fn escape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    s.chars().try_for_each(|char| match char {
-        '\r' => f.write_str("\\r"),
-        '\n' => f.write_str("\\n"),
-        '\u{000c}' => f.write_str("\\f"),
-        '"' => f.write_str("\\\""),
-        '\\' => f.write_str("\\\\"),
-        _ => f.write_char(char)
-    })
-}
-
-fn unescape<W: Write>(f: &mut W, s: &str) -> FmtResult {
-    let mut chars = s.chars();
-    while let Some(char) = chars.next() {
-        if char == '\\' {
-            match chars.next() {
-                Some('r') => f.write_char('\r')?,
-                Some('n') => f.write_char('\n')?,
-                Some('f') => f.write_char('\u{000c}')?,
-                Some('t') => f.write_char('\t')?,
-                Some(other) => f.write_char(other)?,
-                None => panic!("invalid escape"), // FIXME(@althonos)
-            }
-        } else {
-            f.write_char(char)?;
-        }
-    }
-    Ok(())
-}
@@ -9,6 +9,8 @@ use std::ops::Deref;
 use pest::iterators::Pair;
 use opaque_typedef::OpaqueTypedefUnsized;
 
+use crate::ast::escape::escape_quoted;
+use crate::ast::escape::unescape;
 use crate::error::Error;
 use crate::parser::FromPair;
 use crate::parser::QuickFind;
@@ -16,8 +18,6 @@ use crate::parser::Rule;
 use crate::share::Share;
 use crate::share::Cow;
 use crate::share::Redeem;
-use super::escape;
-use super::unescape;
 
 /// A string enclosed by quotes, used for definitions.
 ///
@@ -104,8 +104,7 @@ impl<'i> FromPair<'i> for QuotedString {
         let s = pair.as_str();
         let escaped = s.quickcount(b'\\');
         let mut local = String::with_capacity(s.len() + escaped);
-        unescape(&mut local, s.get_unchecked(1..s.len() - 1))
-            .expect("String as fmt::Write cannot fail");
+        unescape(&mut local, pair.as_span().get(1..s.len() - 1).unwrap())?;
         Ok(QuotedString::new(local))
     }
 }
@@ -146,7 +145,7 @@ impl QuotedStr {
 impl<'a> Display for QuotedStr {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         f.write_char('"')
-            .and(escape(f, &self.0))
+            .and(escape_quoted(f, &self.0))
             .and(f.write_char('"'))
     }
 }
@@ -154,10 +153,11 @@ impl<'a> Display for QuotedStr {
 impl<'i> FromPair<'i> for Cow<'i, &'i QuotedStr> {
     const RULE: Rule = Rule::QuotedString;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self, Error> {
-        if pair.as_str().quickfind(b'\\').is_some() {
+        let s = pair.as_str();
+        if s.quickfind(b'\\').is_some() {
             QuotedString::from_pair_unchecked(pair).map(Cow::Owned)
         } else {
-            Ok(Cow::Borrowed(QuotedStr::new(pair.as_str())))
+            Ok(Cow::Borrowed(QuotedStr::new(s.get_unchecked(1..s.len() - 1))))
         }
     }
 }
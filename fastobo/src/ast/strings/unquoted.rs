@@ -9,6 +9,8 @@ use std::ops::Deref;
 use opaque_typedef::OpaqueTypedefUnsized;
 use pest::iterators::Pair;
 
+use crate::ast::escape::escape_unquoted;
+use crate::ast::escape::unescape;
 use crate::error::Error;
 use crate::parser::FromPair;
 use crate::parser::Rule;
@@ -16,8 +18,6 @@ use crate::parser::QuickFind;
 use crate::share::Share;
 use crate::share::Cow;
 use crate::share::Redeem;
-use super::escape;
-use super::unescape;
 
 /// A string without delimiters, used as values in different clauses.
 ///
@@ -34,7 +34,7 @@ use super::unescape;
 /// # extern crate fastobo;
 /// # use fastobo::ast::UnquotedString;
 /// let s = UnquotedString::new("Hello, world!");
-/// assert_eq!(s.to_string(), "Hello, world!");
+/// assert_eq!(s.to_string(), "Hello, world\\!");
 /// ```
 #[derive(Clone, Debug, Eq, Hash, Ord, OpaqueTypedef, PartialEq, PartialOrd)]
 #[opaque_typedef(derive(AsRef(Inner, Self)))]
@@ -101,7 +101,7 @@ impl<'i> FromPair<'i> for UnquotedString {
         let s = pair.as_str();
         let escaped = s.quickcount(b'\\'); // number of escaped characters
         let mut local = String::with_capacity(s.len() + escaped);
-        unescape(&mut local, s).expect("String as fmt::Write cannot fail");
+        unescape(&mut local, pair.as_span())?;
         Ok(UnquotedString::new(local))
     }
 }
@@ -122,7 +122,7 @@ impl UnquotedStr {
 
 impl<'a> Display for UnquotedStr {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        escape(f, &self.0)
+        escape_unquoted(f, &self.0)
     }
 }
 
@@ -7,6 +7,8 @@ use pest::iterators::Pair;
 use url::Url;
 
 use crate::ast::*;
+use crate::share::Cow;
+use crate::share::Redeem;
 use crate::error::Error;
 use crate::error::Result;
 use crate::parser::FromPair;
@@ -14,6 +16,8 @@ use crate::parser::Rule;
 
 /// A synonym scope specifier.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
 pub enum SynonymScope {
     Exact,
     Broad,
@@ -49,6 +53,7 @@ impl_fromstr!(SynonymScope);
 
 /// A synonym, denoting an alternative name for the embedding entity.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Synonym {
     pub desc: QuotedString,
     pub scope: SynonymScope,
@@ -159,6 +164,70 @@ impl<'i> FromPair<'i> for Synonym {
 impl_fromstr!(Synonym);
 
 
+/// A borrowed `Synonym`.
+#[derive(Clone, Debug)]
+pub struct SynonymRef<'a> {
+    pub desc: Cow<'a, &'a QuotedStr>,
+    pub scope: SynonymScope,
+    pub ty: Option<SynonymTypeId<'a>>,
+    pub xrefs: XrefListRef<'a>,
+}
+
+impl<'a> Display for SynonymRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.desc
+            .fmt(f)
+            .and(f.write_char(' '))
+            .and(self.scope.fmt(f))
+            .and(f.write_char(' '))?;
+
+        if let Some(ref syntype) = self.ty {
+            syntype.fmt(f).and(f.write_char(' '))?;
+        }
+
+        self.xrefs.fmt(f)
+    }
+}
+
+impl<'i> FromPair<'i> for SynonymRef<'i> {
+    const RULE: Rule = Rule::Synonym;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+
+        let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+        let scope = SynonymScope::from_pair_unchecked(inner.next().unwrap())?;
+
+        let nxt = inner.next().unwrap();
+        let (ty, xrefs) = match nxt.as_rule() {
+            Rule::SynonymTypeId => {
+                let ty = Some(SynonymTypeId::from_pair_unchecked(nxt)?);
+                (ty, XrefListRef::from_pair_unchecked(inner.next().unwrap())?)
+            }
+            Rule::XrefList => (None, XrefListRef::from_pair_unchecked(nxt)?),
+            _ => unreachable!(),
+        };
+
+        Ok(SynonymRef {
+            desc,
+            scope,
+            ty,
+            xrefs,
+        })
+    }
+}
+impl_fromslice!('i, SynonymRef<'i>);
+
+impl<'a> Redeem<'a> for SynonymRef<'a> {
+    type Owned = Synonym;
+    fn redeem(&'a self) -> Synonym {
+        Synonym::with_type_and_xrefs(
+            self.desc.redeem(),
+            self.scope.clone(),
+            self.ty.as_ref().map(|ty| ty.redeem()),
+            self.xrefs.redeem(),
+        )
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -224,5 +293,21 @@ mod tests {
 
     }
 
+    mod synonym_ref {
+
+        use super::*;
+        use crate::parser::FromSlice;
 
+        #[test]
+        fn from_slice() {
+            let s = "\"ssDNA-specific endodeoxyribonuclease activity\" RELATED [GOC:mah]";
+            let actual = SynonymRef::from_slice(s).unwrap();
+            match &actual.desc {
+                Cow::Borrowed(desc) => assert_eq!(desc, &"ssDNA-specific endodeoxyribonuclease activity"),
+                Cow::Owned(_) => panic!("synonym description should be borrowed"),
+            }
+            assert_eq!(actual.to_string(), s);
+            assert_eq!(actual.redeem(), Synonym::from_str(s).unwrap());
+        }
+    }
 }
@@ -7,12 +7,16 @@ use std::str::FromStr;
 use pest::iterators::Pair;
 
 use crate::ast::*;
+use crate::share::Cow;
+use crate::share::Redeem;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
 
 /// A clause appearing in a term frame.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum TermClause {
     IsAnonymous(bool),
     Name(UnquotedString),
@@ -36,11 +40,10 @@ pub enum TermClause {
     Consider(ClassIdent),
     CreatedBy(UnquotedString),
     CreationDate(IsoDateTime),
-    // FIXME(@althonos): in the guide but not in the syntax.
-    // ExpandAssertionTo(QuotedString, XrefList),
-    // ExpandExpressionTO(QuotedString, XrefList),
-    // IsMetadataTag(bool),
-    // IsClassLevel(bool),
+    ExpandAssertionTo(QuotedString, XrefList),
+    ExpandExpressionTo(QuotedString, XrefList),
+    IsMetadataTag(bool),
+    IsClassLevel(bool),
 }
 
 impl Display for TermClause {
@@ -82,6 +85,18 @@ impl Display for TermClause {
             Consider(id) => f.write_str("consider: ").and(id.fmt(f)),
             CreatedBy(s) => f.write_str("created_by: ").and(s.fmt(f)),
             CreationDate(date) => f.write_str("creation_date: ").and(date.fmt(f)),
+            ExpandAssertionTo(desc, xrefs) => f
+                .write_str("expand_assertion_to: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            ExpandExpressionTo(desc, xrefs) => f
+                .write_str("expand_expression_to: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            IsMetadataTag(b) => f.write_str("is_metadata_tag: ").and(b.fmt(f)),
+            IsClassLevel(b) => f.write_str("is_class_level: ").and(b.fmt(f)),
         }
     }
 }
@@ -89,10 +104,13 @@ impl Display for TermClause {
 impl<'i> FromPair<'i> for Line<TermClause> {
     const RULE: Rule = Rule::TermClauseLine;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
         let mut inner = pair.into_inner();
         let clause = TermClause::from_pair_unchecked(inner.next().unwrap())?;
         let eol = inner.next().unwrap();
-        Ok(Eol::from_pair_unchecked(eol)?.and_inner(clause))
+        Ok(Eol::from_pair_unchecked(eol)?
+            .and_inner(clause)
+            .and_location(location))
     }
 }
 impl_fromstr!(Line<TermClause>);
@@ -199,12 +217,293 @@ impl<'i> FromPair<'i> for TermClause {
                 let dt = IsoDateTime::from_pair_unchecked(inner.next().unwrap())?;
                 Ok(TermClause::CreationDate(dt))
             }
+            Rule::ExpandAssertionToTag => {
+                let desc = QuotedString::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefList::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClause::ExpandAssertionTo(desc, xrefs))
+            }
+            Rule::ExpandExpressionToTag => {
+                let desc = QuotedString::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefList::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClause::ExpandExpressionTo(desc, xrefs))
+            }
+            Rule::IsMetadataTagTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClause::IsMetadataTag(b))
+            }
+            Rule::IsClassLevelTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClause::IsClassLevel(b))
+            }
             _ => unreachable!(),
         }
     }
 }
 impl_fromstr!(TermClause);
 
+/// A borrowed `TermClause`.
+#[derive(Clone, Debug)]
+pub enum TermClauseRef<'a> {
+    IsAnonymous(bool),
+    Name(Cow<'a, &'a UnquotedStr>),
+    Namespace(NamespaceId<'a>),
+    AltId(Id<'a>),
+    Def(Cow<'a, &'a QuotedStr>, XrefListRef<'a>),
+    Comment(Cow<'a, &'a UnquotedStr>),
+    Subset(SubsetId<'a>),
+    Synonym(SynonymRef<'a>),
+    Xref(XrefRef<'a>),
+    Builtin(bool),
+    PropertyValue(PropVal<'a>),
+    IsA(ClassId<'a>),
+    IntersectionOf(Option<RelationId<'a>>, ClassId<'a>),
+    UnionOf(ClassId<'a>),
+    EquivalentTo(ClassId<'a>),
+    DisjointFrom(ClassId<'a>),
+    Relationship(RelationId<'a>, ClassId<'a>),
+    IsObsolete(bool),
+    ReplacedBy(ClassId<'a>),
+    Consider(ClassId<'a>),
+    CreatedBy(Cow<'a, &'a UnquotedStr>),
+    CreationDate(IsoDateTime),
+    ExpandAssertionTo(Cow<'a, &'a QuotedStr>, XrefListRef<'a>),
+    ExpandExpressionTo(Cow<'a, &'a QuotedStr>, XrefListRef<'a>),
+    IsMetadataTag(bool),
+    IsClassLevel(bool),
+}
+
+impl<'a> Display for TermClauseRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::TermClauseRef::*;
+        match self {
+            IsAnonymous(b) => f.write_str("is_anonymous: ").and(b.fmt(f)),
+            Name(name) => f.write_str("name: ").and(name.fmt(f)),
+            Namespace(id) => f.write_str("namespace: ").and(id.fmt(f)),
+            AltId(id) => f.write_str("alt_id: ").and(id.fmt(f)),
+            Def(desc, xreflist) => f
+                .write_str("def: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xreflist.fmt(f)),
+            Comment(comment) => f.write_str("comment: ").and(comment.fmt(f)),
+            Subset(subset) => f.write_str("subset: ").and(subset.fmt(f)),
+            Synonym(syn) => f.write_str("synonym: ").and(syn.fmt(f)),
+            Xref(xref) => f.write_str("xref: ").and(xref.fmt(f)),
+            Builtin(b) => f.write_str("builtin: ").and(b.fmt(f)),
+            PropertyValue(pv) => f.write_str("property_value: ").and(pv.fmt(f)),
+            IsA(id) => f.write_str("is_a: ").and(id.fmt(f)),
+            IntersectionOf(Some(rel), id) => f
+                .write_str("intersection_of: ")
+                .and(rel.fmt(f))
+                .and(f.write_char(' '))
+                .and(id.fmt(f)),
+            IntersectionOf(None, id) => f.write_str("intersection_of: ").and(id.fmt(f)),
+            UnionOf(id) => f.write_str("union_of: ").and(id.fmt(f)),
+            EquivalentTo(id) => f.write_str("equivalent_to: ").and(id.fmt(f)),
+            DisjointFrom(id) => f.write_str("disjoint_from: ").and(id.fmt(f)),
+            Relationship(rel, id) => f
+                .write_str("relationship: ")
+                .and(rel.fmt(f))
+                .and(f.write_char(' '))
+                .and(id.fmt(f)),
+            IsObsolete(b) => f.write_str("is_obsolete: ").and(b.fmt(f)),
+            ReplacedBy(id) => f.write_str("replaced_by: ").and(id.fmt(f)),
+            Consider(id) => f.write_str("consider: ").and(id.fmt(f)),
+            CreatedBy(s) => f.write_str("created_by: ").and(s.fmt(f)),
+            CreationDate(date) => f.write_str("creation_date: ").and(date.fmt(f)),
+            ExpandAssertionTo(desc, xrefs) => f
+                .write_str("expand_assertion_to: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            ExpandExpressionTo(desc, xrefs) => f
+                .write_str("expand_expression_to: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            IsMetadataTag(b) => f.write_str("is_metadata_tag: ").and(b.fmt(f)),
+            IsClassLevel(b) => f.write_str("is_class_level: ").and(b.fmt(f)),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for Line<TermClauseRef<'i>> {
+    const RULE: Rule = Rule::TermClauseLine;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
+        let mut inner = pair.into_inner();
+        let clause = TermClauseRef::from_pair_unchecked(inner.next().unwrap())?;
+        let eol = inner.next().unwrap();
+        Ok(Eol::from_pair_unchecked(eol)?
+            .and_inner(clause)
+            .and_location(location))
+    }
+}
+impl_fromslice!('i, Line<TermClauseRef<'i>>);
+
+impl<'i> FromPair<'i> for TermClauseRef<'i> {
+    const RULE: Rule = Rule::TermClause;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        match inner.next().unwrap().as_rule() {
+            Rule::IsAnonymousTag => {
+                let b = bool::from_str(inner.next().unwrap().as_str()).unwrap();
+                Ok(TermClauseRef::IsAnonymous(b))
+            }
+            Rule::NameTag => {
+                let name = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Name(name))
+            }
+            Rule::NamespaceTag => {
+                let ns = NamespaceId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Namespace(ns))
+            }
+            Rule::AltIdTag => {
+                let id = Id::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::AltId(id))
+            }
+            Rule::DefTag => {
+                let def = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefListRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Def(def, xrefs))
+            }
+            Rule::CommentTag => {
+                let comment = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Comment(comment))
+            }
+            Rule::SubsetTag => {
+                let id = SubsetId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Subset(id))
+            }
+            Rule::SynonymTag => {
+                let syn = SynonymRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Synonym(syn))
+            }
+            Rule::XrefTag => {
+                let xref = XrefRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Xref(xref))
+            }
+            Rule::BuiltinTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Builtin(b))
+            }
+            Rule::PropertyValueTag => {
+                let pv = PropVal::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::PropertyValue(pv))
+            }
+            Rule::IsATag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::IsA(id))
+            }
+            Rule::IntersectionOfTag => {
+                let id = inner.next().unwrap();
+                if id.as_rule() == Rule::ClassId {
+                    let classid = ClassId::from_pair_unchecked(id)?;
+                    Ok(TermClauseRef::IntersectionOf(None, classid))
+                } else {
+                    let relid = RelationId::from_pair_unchecked(id)?;
+                    let classid = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                    Ok(TermClauseRef::IntersectionOf(Some(relid), classid))
+                }
+            }
+            Rule::UnionOfTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::UnionOf(id))
+            }
+            Rule::EquivalentToTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::EquivalentTo(id))
+            }
+            Rule::DisjointFromTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::DisjointFrom(id))
+            }
+            Rule::RelationshipTag => {
+                let rel = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Relationship(rel, id))
+            }
+            Rule::IsObsoleteTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::IsObsolete(b))
+            }
+            Rule::ReplacedByTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::ReplacedBy(id))
+            }
+            Rule::ConsiderTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::Consider(id))
+            }
+            Rule::CreatedByTag => {
+                let s = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::CreatedBy(s))
+            }
+            Rule::CreationDateTag => {
+                let dt = IsoDateTime::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::CreationDate(dt))
+            }
+            Rule::ExpandAssertionToTag => {
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefListRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::ExpandAssertionTo(desc, xrefs))
+            }
+            Rule::ExpandExpressionToTag => {
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefListRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::ExpandExpressionTo(desc, xrefs))
+            }
+            Rule::IsMetadataTagTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::IsMetadataTag(b))
+            }
+            Rule::IsClassLevelTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TermClauseRef::IsClassLevel(b))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+impl_fromslice!('i, TermClauseRef<'i>);
+
+impl<'a> Redeem<'a> for TermClauseRef<'a> {
+    type Owned = TermClause;
+    fn redeem(&'a self) -> TermClause {
+        use self::TermClauseRef::*;
+        match self {
+            IsAnonymous(x) => TermClause::IsAnonymous(*x),
+            Name(x) => TermClause::Name(x.redeem()),
+            Namespace(x) => TermClause::Namespace(x.redeem()),
+            AltId(x) => TermClause::AltId(x.redeem()),
+            Def(x0, x1) => TermClause::Def(x0.redeem(), x1.redeem()),
+            Comment(x) => TermClause::Comment(x.redeem()),
+            Subset(x) => TermClause::Subset(x.redeem()),
+            Synonym(x) => TermClause::Synonym(x.redeem()),
+            Xref(x) => TermClause::Xref(x.redeem()),
+            Builtin(x) => TermClause::Builtin(*x),
+            PropertyValue(x) => TermClause::PropertyValue(x.redeem()),
+            IsA(x) => TermClause::IsA(x.redeem()),
+            IntersectionOf(x0, x1) => {
+                TermClause::IntersectionOf(x0.as_ref().map(|x| x.redeem()), x1.redeem())
+            }
+            UnionOf(x) => TermClause::UnionOf(x.redeem()),
+            EquivalentTo(x) => TermClause::EquivalentTo(x.redeem()),
+            DisjointFrom(x) => TermClause::DisjointFrom(x.redeem()),
+            Relationship(x0, x1) => TermClause::Relationship(x0.redeem(), x1.redeem()),
+            IsObsolete(x) => TermClause::IsObsolete(*x),
+            ReplacedBy(x) => TermClause::ReplacedBy(x.redeem()),
+            Consider(x) => TermClause::Consider(x.redeem()),
+            CreatedBy(x) => TermClause::CreatedBy(x.redeem()),
+            CreationDate(x) => TermClause::CreationDate(x.clone()),
+            ExpandAssertionTo(x0, x1) => TermClause::ExpandAssertionTo(x0.redeem(), x1.redeem()),
+            ExpandExpressionTo(x0, x1) => TermClause::ExpandExpressionTo(x0.redeem(), x1.redeem()),
+            IsMetadataTag(x) => TermClause::IsMetadataTag(*x),
+            IsClassLevel(x) => TermClause::IsClassLevel(*x),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -365,4 +664,51 @@ mod tests {
     mod created_by {}
 
     mod creation_date {}
+
+    mod expand_assertion_to {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let actual = TermClause::from_str(
+                "expand_assertion_to: \"Class: ?X EquivalentTo: ?Y\" [RO:cjm]"
+            ).unwrap();
+            let expected = TermClause::ExpandAssertionTo(
+                QuotedString::new("Class: ?X EquivalentTo: ?Y"),
+                XrefList::from(vec![Xref::new(PrefixedIdent::new("RO", "cjm"))]),
+            );
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn to_string() {
+            let clause = TermClause::ExpandAssertionTo(
+                QuotedString::new("Class: ?X EquivalentTo: ?Y"),
+                XrefList::from(vec![Xref::new(PrefixedIdent::new("RO", "cjm"))]),
+            );
+            assert_eq!(clause.to_string(), "expand_assertion_to: \"Class: ?X EquivalentTo: ?Y\" [RO:cjm]");
+        }
+    }
+
+    mod expand_expression_to {}
+
+    mod is_metadata_tag {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let actual = TermClause::from_str("is_metadata_tag: true").unwrap();
+            assert_eq!(actual, TermClause::IsMetadataTag(true));
+        }
+    }
+
+    mod is_class_level {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            let actual = TermClause::from_str("is_class_level: false").unwrap();
+            assert_eq!(actual, TermClause::IsClassLevel(false));
+        }
+    }
 }
@@ -9,12 +9,14 @@ use std::ops::DerefMut;
 use pest::iterators::Pair;
 
 use crate::ast::*;
+use crate::share::Redeem;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
 
 /// A term frame, describing a class.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TermFrame {
     id: Line<ClassIdent>,
     clauses: Vec<Line<TermClause>>,
@@ -55,6 +57,11 @@ impl TermFrame {
         &self.id
     }
 
+    /// Get a mutable reference to the identifier of the `TermFrame`.
+    pub fn id_mut(&mut self) -> &mut Line<ClassIdent> {
+        &mut self.id
+    }
+
     /// Get the `TermClause`s of the `TermFrame`.
     pub fn clauses(&self) -> &Vec<Line<TermClause>> {
         &self.clauses
@@ -111,8 +118,13 @@ impl<'i> FromPair<'i> for TermFrame {
     const RULE: Rule = Rule::TermFrame;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
         let mut inner = pair.into_inner();
-        let clsid = ClassIdent::from_pair_unchecked(inner.next().unwrap())?;
-        let id = Eol::from_pair_unchecked(inner.next().unwrap())?.and_inner(clsid);
+        let id_pair = inner.next().unwrap();
+        let eol_pair = inner.next().unwrap();
+        let location = SourceLocation::from_pairs(&id_pair, &eol_pair);
+        let clsid = ClassIdent::from_pair_unchecked(id_pair)?;
+        let id = Eol::from_pair_unchecked(eol_pair)?
+            .and_inner(clsid)
+            .and_location(location);
 
         let mut clauses = Vec::new();
         for pair in inner {
@@ -140,6 +152,93 @@ impl<'a> IntoIterator for &'a TermFrame {
     }
 }
 
+/// A borrowed `TermFrame`.
+#[derive(Clone, Debug)]
+pub struct TermFrameRef<'a> {
+    id: Line<ClassId<'a>>,
+    clauses: Vec<Line<TermClauseRef<'a>>>,
+}
+
+impl<'a> TermFrameRef<'a> {
+    /// Create a new borrowed term frame with the provided ID and clauses.
+    pub fn with_clauses<I>(id: I, clauses: Vec<Line<TermClauseRef<'a>>>) -> Self
+    where
+        I: Into<Line<ClassId<'a>>>,
+    {
+        Self {
+            id: id.into(),
+            clauses,
+        }
+    }
+
+    /// Get the identifier of the `TermFrameRef`.
+    pub fn id(&self) -> &Line<ClassId<'a>> {
+        &self.id
+    }
+
+    /// Get a mutable reference to the identifier of the `TermFrameRef`.
+    pub fn id_mut(&mut self) -> &mut Line<ClassId<'a>> {
+        &mut self.id
+    }
+
+    /// Get the `TermClauseRef`s of the `TermFrameRef`.
+    pub fn clauses(&self) -> &Vec<Line<TermClauseRef<'a>>> {
+        &self.clauses
+    }
+}
+
+impl<'a> Deref for TermFrameRef<'a> {
+    type Target = Vec<Line<TermClauseRef<'a>>>;
+    fn deref(&self) -> &Self::Target {
+        &self.clauses
+    }
+}
+
+impl<'a> DerefMut for TermFrameRef<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.clauses
+    }
+}
+
+impl<'a> Display for TermFrameRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str("[Term]\nid: ").and(self.id.fmt(f))?;
+        self.clauses.iter().try_for_each(|clause| clause.fmt(f))
+    }
+}
+
+impl<'i> FromPair<'i> for TermFrameRef<'i> {
+    const RULE: Rule = Rule::TermFrame;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        let id_pair = inner.next().unwrap();
+        let eol_pair = inner.next().unwrap();
+        let location = SourceLocation::from_pairs(&id_pair, &eol_pair);
+        let clsid = ClassId::from_pair_unchecked(id_pair)?;
+        let id = Eol::from_pair_unchecked(eol_pair)?
+            .and_inner(clsid)
+            .and_location(location);
+
+        let mut clauses = Vec::new();
+        for pair in inner {
+            clauses.push(Line::<TermClauseRef>::from_pair_unchecked(pair)?);
+        }
+
+        Ok(TermFrameRef { id, clauses })
+    }
+}
+impl_fromslice!('i, TermFrameRef<'i>);
+
+impl<'a> Redeem<'a> for TermFrameRef<'a> {
+    type Owned = TermFrame;
+    fn redeem(&'a self) -> TermFrame {
+        TermFrame::with_clauses(
+            self.id.redeem(),
+            self.clauses.iter().map(|line| line.redeem()).collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
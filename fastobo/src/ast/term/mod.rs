@@ -2,4 +2,6 @@ mod clause;
 mod frame;
 
 pub use self::clause::TermClause;
+pub use self::clause::TermClauseRef;
 pub use self::frame::TermFrame;
+pub use self::frame::TermFrameRef;
@@ -5,12 +5,16 @@ use std::fmt::Write;
 use std::str::FromStr;
 
 use crate::ast::*;
+use crate::share::Cow;
+use crate::share::Redeem;
 use crate::error::Result;
 use crate::parser::FromPair;
 use crate::parser::Rule;
 
 /// A clause appearing in a typedef frame.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum TypedefClause {
     IsAnonymous(bool),
     Name(UnquotedString),
@@ -129,10 +133,13 @@ impl Display for TypedefClause {
 impl<'i> FromPair<'i> for Line<TypedefClause> {
     const RULE: Rule = Rule::TypedefClauseLine;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
         let mut inner = pair.into_inner();
         let clause = TypedefClause::from_pair_unchecked(inner.next().unwrap())?;
         let eol = inner.next().unwrap();
-        Ok(Eol::from_pair_unchecked(eol)?.and_inner(clause))
+        Ok(Eol::from_pair_unchecked(eol)?
+            .and_inner(clause)
+            .and_location(location))
     }
 }
 impl_fromstr!(Line<TypedefClause>);
@@ -313,3 +320,362 @@ impl<'i> FromPair<'i> for TypedefClause {
     }
 }
 impl_fromstr!(TypedefClause);
+
+/// A borrowed `TypedefClause`.
+#[derive(Clone, Debug)]
+pub enum TypedefClauseRef<'a> {
+    IsAnonymous(bool),
+    Name(Cow<'a, &'a UnquotedStr>),
+    Namespace(NamespaceId<'a>),
+    AltId(Id<'a>),
+    Def(Cow<'a, &'a QuotedStr>, XrefListRef<'a>),
+    Comment(Cow<'a, &'a UnquotedStr>),
+    Subset(SubsetId<'a>),
+    Synonym(SynonymRef<'a>),
+    Xref(XrefRef<'a>),
+    PropertyValue(PropVal<'a>),
+    Domain(ClassId<'a>),
+    Range(ClassId<'a>),
+    Builtin(bool),
+    HoldsOverChain(RelationId<'a>, RelationId<'a>),
+    IsAntiSymmetric(bool),
+    IsCyclic(bool),
+    IsReflexive(bool),
+    IsSymmetric(bool),
+    IsTransitive(bool),
+    IsFunctional(bool),
+    IsInverseFunctional(bool),
+    IsA(RelationId<'a>),
+    IntersectionOf(RelationId<'a>),
+    UnionOf(RelationId<'a>),
+    EquivalentTo(RelationId<'a>),
+    DisjointFrom(RelationId<'a>),
+    InverseOf(RelationId<'a>),
+    TransitiveOver(RelationId<'a>),
+    EquivalentToChain(RelationId<'a>, RelationId<'a>),
+    DisjointOver(RelationId<'a>),
+    Relationship(RelationId<'a>, RelationId<'a>),
+    IsObsolete(bool),
+    ReplacedBy(RelationId<'a>),
+    Consider(Id<'a>),
+    CreatedBy(Cow<'a, &'a UnquotedStr>),
+    CreationDate(IsoDateTime),
+    ExpandAssertionTo(Cow<'a, &'a QuotedStr>, XrefListRef<'a>),
+    ExpandExpressionTo(Cow<'a, &'a QuotedStr>, XrefListRef<'a>),
+    IsMetadataTag(bool),
+    IsClassLevel(bool),
+}
+
+impl<'a> Display for TypedefClauseRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::TypedefClauseRef::*;
+        match self {
+            IsAnonymous(b) => f.write_str("is_anonymous: ").and(b.fmt(f)),
+            Name(name) => f.write_str("name: ").and(name.fmt(f)),
+            Namespace(ns) => f.write_str("namespace: ").and(ns.fmt(f)),
+            AltId(id) => f.write_str("alt_id: ").and(id.fmt(f)),
+            Def(desc, xrefs) => f
+                .write_str("def: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            Comment(comment) => f.write_str("comment: ").and(comment.fmt(f)),
+            Subset(id) => f.write_str("subset: ").and(id.fmt(f)),
+            Synonym(syn) => f.write_str("synonym: ").and(syn.fmt(f)),
+            Xref(xref) => f.write_str("xref: ").and(xref.fmt(f)),
+            PropertyValue(pv) => f.write_str("property_value: ").and(pv.fmt(f)),
+            Domain(id) => f.write_str("domain: ").and(id.fmt(f)),
+            Range(id) => f.write_str("range: ").and(id.fmt(f)),
+            Builtin(b) => f.write_str("builtin: ").and(b.fmt(f)),
+            HoldsOverChain(r1, r2) => f
+                .write_str("holds_over_chain: ")
+                .and(r1.fmt(f))
+                .and(f.write_char(' '))
+                .and(r2.fmt(f)),
+            IsAntiSymmetric(b) => f.write_str("is_anti_symmetric: ").and(b.fmt(f)),
+            IsCyclic(b) => f.write_str("is_cyclic: ").and(b.fmt(f)),
+            IsReflexive(b) => f.write_str("is_reflexive: ").and(b.fmt(f)),
+            IsSymmetric(b) => f.write_str("is_symmetric: ").and(b.fmt(f)),
+            IsTransitive(b) => f.write_str("is_transitive: ").and(b.fmt(f)),
+            IsFunctional(b) => f.write_str("is_functional: ").and(b.fmt(f)),
+            IsInverseFunctional(b) => f.write_str("is_inverse_functional: ").and(b.fmt(f)),
+            IsA(r) => f.write_str("is_a: ").and(r.fmt(f)),
+            IntersectionOf(r) => f.write_str("intersection_of: ").and(r.fmt(f)),
+            UnionOf(r) => f.write_str("union_of: ").and(r.fmt(f)),
+            EquivalentTo(r) => f.write_str("equivalent_to: ").and(r.fmt(f)),
+            DisjointFrom(r) => f.write_str("disjoint_from: ").and(r.fmt(f)),
+            InverseOf(r) => f.write_str("inverse_of: ").and(r.fmt(f)),
+            TransitiveOver(r) => f.write_str("transitive_over: ").and(r.fmt(f)),
+            EquivalentToChain(r1, r2) => f
+                .write_str("equivalent_to_chain: ")
+                .and(r1.fmt(f))
+                .and(f.write_char(' '))
+                .and(r2.fmt(f)),
+            DisjointOver(r) => f.write_str("disjoint_over: ").and(r.fmt(f)),
+            Relationship(r1, r2) => f
+                .write_str("relationship: ")
+                .and(r1.fmt(f))
+                .and(f.write_char(' '))
+                .and(r2.fmt(f)),
+            IsObsolete(b) => f.write_str("is_obsolete: ").and(b.fmt(f)),
+            ReplacedBy(r) => f.write_str("replaced_by: ").and(r.fmt(f)),
+            Consider(id) => f.write_str("consider: ").and(id.fmt(f)),
+            CreatedBy(s) => f.write_str("created_by: ").and(s.fmt(f)),
+            CreationDate(date) => f.write_str("creation_date: ").and(date.fmt(f)),
+            ExpandAssertionTo(desc, xrefs) => f
+                .write_str("expand_assertion_to: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            ExpandExpressionTo(desc, xrefs) => f
+                .write_str("expand_expression_to: ")
+                .and(desc.fmt(f))
+                .and(f.write_char(' '))
+                .and(xrefs.fmt(f)),
+            IsMetadataTag(b) => f.write_str("is_metadata_tag: ").and(b.fmt(f)),
+            IsClassLevel(b) => f.write_str("is_class_level: ").and(b.fmt(f)),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for Line<TypedefClauseRef<'i>> {
+    const RULE: Rule = Rule::TypedefClauseLine;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let location = SourceLocation::from(&pair);
+        let mut inner = pair.into_inner();
+        let clause = TypedefClauseRef::from_pair_unchecked(inner.next().unwrap())?;
+        let eol = inner.next().unwrap();
+        Ok(Eol::from_pair_unchecked(eol)?
+            .and_inner(clause)
+            .and_location(location))
+    }
+}
+impl_fromslice!('i, Line<TypedefClauseRef<'i>>);
+
+impl<'i> FromPair<'i> for TypedefClauseRef<'i> {
+    const RULE: Rule = Rule::TypedefClause;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        match inner.next().unwrap().as_rule() {
+            Rule::IsAnonymousTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsAnonymous(b))
+            }
+            Rule::NameTag => {
+                let n = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Name(n))
+            }
+            Rule::NamespaceTag => {
+                let ns = NamespaceId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Namespace(ns))
+            }
+            Rule::AltIdTag => {
+                let id = Id::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::AltId(id))
+            }
+            Rule::DefTag => {
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefListRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Def(desc, xrefs))
+            }
+            Rule::CommentTag => {
+                let comment = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Comment(comment))
+            }
+            Rule::SubsetTag => {
+                let id = SubsetId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Subset(id))
+            }
+            Rule::SynonymTag => {
+                let syn = SynonymRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Synonym(syn))
+            }
+            Rule::XrefTag => {
+                let xref = XrefRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Xref(xref))
+            }
+            Rule::PropertyValueTag => {
+                let pv = PropVal::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::PropertyValue(pv))
+            }
+            Rule::DomainTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Domain(id))
+            }
+            Rule::RangeTag => {
+                let id = ClassId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Range(id))
+            }
+            Rule::BuiltinTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Builtin(b))
+            }
+            Rule::HoldsOverChainTag => {
+                let r1 = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                let r2 = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::HoldsOverChain(r1, r2))
+            }
+            Rule::IsAntiSymmetricTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsAntiSymmetric(b))
+            }
+            Rule::IsCyclicTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsCyclic(b))
+            }
+            Rule::IsReflexiveTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsReflexive(b))
+            }
+            Rule::IsSymmetricTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsSymmetric(b))
+            }
+            Rule::IsTransitiveTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsTransitive(b))
+            }
+            Rule::IsFunctionalTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsFunctional(b))
+            }
+            Rule::IsInverseFunctionalTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsInverseFunctional(b))
+            }
+            Rule::IsATag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsA(id))
+            }
+            Rule::IntersectionOfTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IntersectionOf(id))
+            }
+            Rule::UnionOfTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::UnionOf(id))
+            }
+            Rule::EquivalentToTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::EquivalentTo(id))
+            }
+            Rule::DisjointFromTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::DisjointFrom(id))
+            }
+            Rule::InverseOfTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::InverseOf(id))
+            }
+            Rule::TransitiveOverTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::TransitiveOver(id))
+            }
+            Rule::EquivalentToChainTag => {
+                let r1 = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                let r2 = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::EquivalentToChain(r1, r2))
+            }
+            Rule::DisjointOverTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::DisjointOver(id))
+            }
+            Rule::RelationshipTag => {
+                let r1 = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                let r2 = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Relationship(r1, r2))
+            }
+            Rule::IsObsoleteTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsObsolete(b))
+            }
+            Rule::ReplacedByTag => {
+                let id = RelationId::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::ReplacedBy(id))
+            }
+            Rule::ConsiderTag => {
+                let id = Id::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::Consider(id))
+            }
+            Rule::CreatedByTag => {
+                let person = Cow::<&UnquotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::CreatedBy(person))
+            }
+            Rule::CreationDateTag => {
+                let date = IsoDateTime::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::CreationDate(date))
+            }
+            Rule::ExpandAssertionToTag => {
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefListRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::ExpandAssertionTo(desc, xrefs))
+            }
+            Rule::ExpandExpressionToTag => {
+                let desc = Cow::<&QuotedStr>::from_pair_unchecked(inner.next().unwrap())?;
+                let xrefs = XrefListRef::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::ExpandExpressionTo(desc, xrefs))
+            }
+            Rule::IsMetadataTagTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsMetadataTag(b))
+            }
+            Rule::IsClassLevelTag => {
+                let b = bool::from_pair_unchecked(inner.next().unwrap())?;
+                Ok(TypedefClauseRef::IsClassLevel(b))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+impl_fromslice!('i, TypedefClauseRef<'i>);
+
+impl<'a> Redeem<'a> for TypedefClauseRef<'a> {
+    type Owned = TypedefClause;
+    fn redeem(&'a self) -> TypedefClause {
+        use self::TypedefClauseRef::*;
+        match self {
+            IsAnonymous(x) => TypedefClause::IsAnonymous(*x),
+            Name(x) => TypedefClause::Name(x.redeem()),
+            Namespace(x) => TypedefClause::Namespace(x.redeem()),
+            AltId(x) => TypedefClause::AltId(x.redeem()),
+            Def(x0, x1) => TypedefClause::Def(x0.redeem(), x1.redeem()),
+            Comment(x) => TypedefClause::Comment(x.redeem()),
+            Subset(x) => TypedefClause::Subset(x.redeem()),
+            Synonym(x) => TypedefClause::Synonym(x.redeem()),
+            Xref(x) => TypedefClause::Xref(x.redeem()),
+            PropertyValue(x) => TypedefClause::PropertyValue(x.redeem()),
+            Domain(x) => TypedefClause::Domain(x.redeem()),
+            Range(x) => TypedefClause::Range(x.redeem()),
+            Builtin(x) => TypedefClause::Builtin(*x),
+            HoldsOverChain(x0, x1) => TypedefClause::HoldsOverChain(x0.redeem(), x1.redeem()),
+            IsAntiSymmetric(x) => TypedefClause::IsAntiSymmetric(*x),
+            IsCyclic(x) => TypedefClause::IsCyclic(*x),
+            IsReflexive(x) => TypedefClause::IsReflexive(*x),
+            IsSymmetric(x) => TypedefClause::IsSymmetric(*x),
+            IsTransitive(x) => TypedefClause::IsTransitive(*x),
+            IsFunctional(x) => TypedefClause::IsFunctional(*x),
+            IsInverseFunctional(x) => TypedefClause::IsInverseFunctional(*x),
+            IsA(x) => TypedefClause::IsA(x.redeem()),
+            IntersectionOf(x) => TypedefClause::IntersectionOf(x.redeem()),
+            UnionOf(x) => TypedefClause::UnionOf(x.redeem()),
+            EquivalentTo(x) => TypedefClause::EquivalentTo(x.redeem()),
+            DisjointFrom(x) => TypedefClause::DisjointFrom(x.redeem()),
+            InverseOf(x) => TypedefClause::InverseOf(x.redeem()),
+            TransitiveOver(x) => TypedefClause::TransitiveOver(x.redeem()),
+            EquivalentToChain(x0, x1) => TypedefClause::EquivalentToChain(x0.redeem(), x1.redeem()),
+            DisjointOver(x) => TypedefClause::DisjointOver(x.redeem()),
+            Relationship(x0, x1) => TypedefClause::Relationship(x0.redeem(), x1.redeem()),
+            IsObsolete(x) => TypedefClause::IsObsolete(*x),
+            ReplacedBy(x) => TypedefClause::ReplacedBy(x.redeem()),
+            Consider(x) => TypedefClause::Consider(x.redeem()),
+            CreatedBy(x) => TypedefClause::CreatedBy(x.redeem()),
+            CreationDate(x) => TypedefClause::CreationDate(x.clone()),
+            ExpandAssertionTo(x0, x1) => TypedefClause::ExpandAssertionTo(x0.redeem(), x1.redeem()),
+            ExpandExpressionTo(x0, x1) => {
+                TypedefClause::ExpandExpressionTo(x0.redeem(), x1.redeem())
+            }
+            IsMetadataTag(x) => TypedefClause::IsMetadataTag(*x),
+            IsClassLevel(x) => TypedefClause::IsClassLevel(*x),
+        }
+    }
+}
@@ -8,11 +8,13 @@ use std::ops::DerefMut;
 use pest::iterators::Pair;
 
 use crate::ast::*;
+use crate::share::Redeem;
 use crate::parser::FromPair;
 use crate::parser::Rule;
 
 /// A typedef clause, describing a relationship.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct TypedefFrame {
     id: Line<RelationIdent>,
     clauses: Vec<Line<TypedefClause>>,
@@ -41,6 +43,11 @@ impl TypedefFrame {
         &self.id
     }
 
+    /// Get a mutable reference to the identifier of the `TypedefFrame`.
+    pub fn id_mut(&mut self) -> &mut Line<RelationIdent> {
+        &mut self.id
+    }
+
     /// Get the `TypedefClause`s of the `TypedefFrame`.
     pub fn clauses(&self) -> &Vec<Line<TypedefClause>> {
         &self.clauses
@@ -83,8 +90,13 @@ impl<'i> FromPair<'i> for TypedefFrame {
     const RULE: Rule = Rule::TypedefFrame;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
         let mut inner = pair.into_inner();
-        let relid = RelationIdent::from_pair_unchecked(inner.next().unwrap())?;
-        let id = Eol::from_pair_unchecked(inner.next().unwrap())?.and_inner(relid);
+        let id_pair = inner.next().unwrap();
+        let eol_pair = inner.next().unwrap();
+        let location = SourceLocation::from_pairs(&id_pair, &eol_pair);
+        let relid = RelationIdent::from_pair_unchecked(id_pair)?;
+        let id = Eol::from_pair_unchecked(eol_pair)?
+            .and_inner(relid)
+            .and_location(location);
 
         let mut clauses = Vec::new();
         for pair in inner {
@@ -111,3 +123,90 @@ impl<'a> IntoIterator for &'a TypedefFrame {
         self.clauses.as_slice().iter()
     }
 }
+
+/// A borrowed `TypedefFrame`.
+#[derive(Clone, Debug)]
+pub struct TypedefFrameRef<'a> {
+    id: Line<RelationId<'a>>,
+    clauses: Vec<Line<TypedefClauseRef<'a>>>,
+}
+
+impl<'a> TypedefFrameRef<'a> {
+    /// Create a new borrowed typedef frame with the provided ID and clauses.
+    pub fn with_clauses<I>(id: I, clauses: Vec<Line<TypedefClauseRef<'a>>>) -> Self
+    where
+        I: Into<Line<RelationId<'a>>>,
+    {
+        Self {
+            id: id.into(),
+            clauses,
+        }
+    }
+
+    /// Get the identifier of the `TypedefFrameRef`.
+    pub fn id(&self) -> &Line<RelationId<'a>> {
+        &self.id
+    }
+
+    /// Get a mutable reference to the identifier of the `TypedefFrameRef`.
+    pub fn id_mut(&mut self) -> &mut Line<RelationId<'a>> {
+        &mut self.id
+    }
+
+    /// Get the `TypedefClauseRef`s of the `TypedefFrameRef`.
+    pub fn clauses(&self) -> &Vec<Line<TypedefClauseRef<'a>>> {
+        &self.clauses
+    }
+}
+
+impl<'a> Deref for TypedefFrameRef<'a> {
+    type Target = Vec<Line<TypedefClauseRef<'a>>>;
+    fn deref(&self) -> &Self::Target {
+        &self.clauses
+    }
+}
+
+impl<'a> DerefMut for TypedefFrameRef<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.clauses
+    }
+}
+
+impl<'a> Display for TypedefFrameRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str("[Typedef]\nid: ").and(self.id.fmt(f))?;
+        self.clauses.iter().try_for_each(|clause| clause.fmt(f))
+    }
+}
+
+impl<'i> FromPair<'i> for TypedefFrameRef<'i> {
+    const RULE: Rule = Rule::TypedefFrame;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        let id_pair = inner.next().unwrap();
+        let eol_pair = inner.next().unwrap();
+        let location = SourceLocation::from_pairs(&id_pair, &eol_pair);
+        let relid = RelationId::from_pair_unchecked(id_pair)?;
+        let id = Eol::from_pair_unchecked(eol_pair)?
+            .and_inner(relid)
+            .and_location(location);
+
+        let mut clauses = Vec::new();
+        for pair in inner {
+            clauses.push(Line::<TypedefClauseRef>::from_pair_unchecked(pair)?);
+        }
+
+        Ok(TypedefFrameRef { id, clauses })
+    }
+}
+impl_fromslice!('i, TypedefFrameRef<'i>);
+
+impl<'a> Redeem<'a> for TypedefFrameRef<'a> {
+    type Owned = TypedefFrame;
+    fn redeem(&'a self) -> TypedefFrame {
+        TypedefFrame::with_clauses(
+            self.id.redeem(),
+            self.clauses.iter().map(|line| line.redeem()).collect(),
+        )
+    }
+}
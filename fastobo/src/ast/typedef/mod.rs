@@ -2,4 +2,6 @@ mod clause;
 mod frame;
 
 pub use self::clause::TypedefClause;
+pub use self::clause::TypedefClauseRef;
 pub use self::frame::TypedefFrame;
+pub use self::frame::TypedefFrameRef;
@@ -12,9 +12,12 @@ use pest::error::Error as PestError;
 use pest::error::InputLocation;
 
 use crate::ast::*;
+use crate::share::Cow;
+use crate::share::Redeem;
 use crate::error::Error;
 use crate::error::Result;
 use crate::parser::FromPair;
+use crate::parser::FromSlice;
 use crate::parser::Rule;
 
 /// A database cross-reference definition.
@@ -26,6 +29,7 @@ use crate::parser::Rule;
 /// (e.g. when exporting an ontology from a knowledge-base to add an hyperlink
 /// to the original resource).
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Xref {
     pub id: Ident,
     pub desc: Option<QuotedString>,
@@ -78,6 +82,62 @@ impl<'i> FromPair<'i> for Xref {
 }
 impl_fromstr!(Xref);
 
+/// A borrowed `Xref`.
+#[derive(Clone, Debug)]
+pub struct XrefRef<'a> {
+    pub id: Id<'a>,
+    pub desc: Option<Cow<'a, &'a QuotedStr>>,
+}
+
+impl<'a> XrefRef<'a> {
+    /// Create a new `XrefRef` from the given ID, without description.
+    pub fn new(id: Id<'a>) -> Self {
+        Self::with_desc(id, None)
+    }
+
+    /// Create a new `XrefRef` with the given ID and optional description.
+    pub fn with_desc<D>(id: Id<'a>, desc: D) -> Self
+    where
+        D: Into<Option<Cow<'a, &'a QuotedStr>>>,
+    {
+        Self {
+            id,
+            desc: desc.into(),
+        }
+    }
+}
+
+impl<'a> Display for XrefRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.id.fmt(f)?;
+        match &self.desc {
+            Some(desc) => f.write_char(' ').and(desc.fmt(f)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'i> FromPair<'i> for XrefRef<'i> {
+    const RULE: Rule = Rule::Xref;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut inner = pair.into_inner();
+        let id = Id::from_pair_unchecked(inner.next().unwrap())?;
+        let desc = match inner.next() {
+            Some(pair) => Some(Cow::<&QuotedStr>::from_pair_unchecked(pair)?),
+            None => None,
+        };
+        Ok(XrefRef { id, desc })
+    }
+}
+impl_fromslice!('i, XrefRef<'i>);
+
+impl<'a> Redeem<'a> for XrefRef<'a> {
+    type Owned = Xref;
+    fn redeem(&'a self) -> Xref {
+        Xref::with_desc(self.id.redeem(), self.desc.as_ref().map(|d| d.redeem()))
+    }
+}
+
 /// A list of containing zero or more `Xref`s.
 #[derive(Clone, Default, Debug, Hash, Eq, PartialEq, OpaqueTypedef)]
 #[opaque_typedef(allow_mut_ref)]
@@ -90,6 +150,8 @@ impl_fromstr!(Xref);
     FromInner,
     PartialEq(Inner),
 ))]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct XrefList {
     xrefs: Vec<Xref>,
 }
@@ -155,6 +217,66 @@ impl IntoIterator for XrefList {
     }
 }
 
+/// A borrowed `XrefList`.
+#[derive(Clone, Debug, Default)]
+pub struct XrefListRef<'a> {
+    xrefs: Vec<XrefRef<'a>>,
+}
+
+impl<'a> XrefListRef<'a> {
+    pub fn new(xrefs: Vec<XrefRef<'a>>) -> Self {
+        Self { xrefs }
+    }
+}
+
+impl<'a> AsRef<[XrefRef<'a>]> for XrefListRef<'a> {
+    fn as_ref(&self) -> &[XrefRef<'a>] {
+        &self.xrefs
+    }
+}
+
+impl<'a> Deref for XrefListRef<'a> {
+    type Target = [XrefRef<'a>];
+    fn deref(&self) -> &Self::Target {
+        &self.xrefs
+    }
+}
+
+impl<'a> Display for XrefListRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_char('[')?;
+        let mut xrefs = self.xrefs.iter().peekable();
+        while let Some(xref) = xrefs.next() {
+            xref.fmt(f)?;
+            if xrefs.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+        f.write_char(']')
+    }
+}
+
+impl<'i> FromPair<'i> for XrefListRef<'i> {
+    const RULE: Rule = Rule::XrefList;
+    unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self> {
+        let mut xrefs = Vec::new();
+        for inner in pair.into_inner() {
+            let xref = XrefRef::from_slice(inner.as_str())
+                .map_err(|e| e.with_span(inner.as_span()))?;
+            xrefs.push(xref);
+        }
+        Ok(Self { xrefs })
+    }
+}
+impl_fromslice!('i, XrefListRef<'i>);
+
+impl<'a> Redeem<'a> for XrefListRef<'a> {
+    type Owned = XrefList;
+    fn redeem(&'a self) -> XrefList {
+        self.xrefs.iter().map(|xref| xref.redeem()).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -191,4 +313,25 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    mod list_ref {
+
+        use super::*;
+
+        #[test]
+        fn from_slice() {
+            let actual = XrefListRef::from_slice(
+                "[PSI:MS, reactome:R-HSA-8983680 \"OAS1 produces oligoadenylates\"]",
+            )
+            .unwrap();
+            assert_eq!(actual.len(), 2);
+            match &actual[1].desc {
+                Some(Cow::Borrowed(desc)) => assert_eq!(desc, &"OAS1 produces oligoadenylates"),
+                other => panic!("unexpected description: {:?}", other),
+            }
+            assert_eq!(actual.redeem(), XrefList::from_str(
+                "[PSI:MS, reactome:R-HSA-8983680 \"OAS1 produces oligoadenylates\"]",
+            ).unwrap());
+        }
+    }
 }
@@ -0,0 +1,275 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::lines::is_trivia;
+use super::lines::write_lines;
+use super::lines::RawLine;
+use crate::ast::check_unique_clause;
+use crate::ast::EntityFrame;
+use crate::ast::HeaderFrame;
+use crate::error::Result;
+use crate::parser::parse_header_clause;
+use crate::parser::FrameChunk;
+
+/// A header frame together with the source text it was parsed from.
+///
+/// The header frame can be accessed and modified through `Deref` and
+/// `DerefMut`. When written, the clauses that were not modified keep their
+/// original text, and blank or comment-only lines are kept in place.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Header {
+    frame: HeaderFrame,
+    source: Option<HeaderSource>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct HeaderSource {
+    frame: HeaderFrame,
+    lines: Vec<RawLine>,
+}
+
+impl Header {
+    /// Parse the header frame from its source text, one line at a time.
+    pub(crate) fn from_text(text: &str) -> Result<Self> {
+        let mut clauses = Vec::new();
+        let mut lines = Vec::new();
+        let mut offset = 0;
+
+        for (line_offset, line) in text.split_terminator('\n').enumerate() {
+            let len = (line.len() + 1).min(text.len() - offset);
+            let line = &text[offset..offset + len];
+            if !is_trivia(line) {
+                let clause = parse_header_clause(line, line_offset, offset)?;
+                check_unique_clause(&clauses, &clause)?;
+                clauses.push(clause);
+            }
+            lines.push(RawLine::new(line));
+            offset += len;
+        }
+
+        let frame = HeaderFrame::new(clauses);
+        Ok(Self {
+            frame: frame.clone(),
+            source: Some(HeaderSource { frame, lines }),
+        })
+    }
+
+    /// Check whether the header frame was created without any source text.
+    pub(crate) fn is_detached(&self) -> bool {
+        self.source.is_none()
+    }
+
+    /// Consume the header and extract the header frame.
+    pub fn into_inner(self) -> HeaderFrame {
+        self.frame
+    }
+}
+
+impl AsRef<HeaderFrame> for Header {
+    fn as_ref(&self) -> &HeaderFrame {
+        &self.frame
+    }
+}
+
+impl AsMut<HeaderFrame> for Header {
+    fn as_mut(&mut self) -> &mut HeaderFrame {
+        &mut self.frame
+    }
+}
+
+impl Deref for Header {
+    type Target = HeaderFrame;
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl DerefMut for Header {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.frame
+    }
+}
+
+impl Display for Header {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match &self.source {
+            None => self.frame.fmt(f),
+            Some(source) => write_lines(f, &source.lines, &source.frame, &self.frame, |f, c| {
+                c.fmt(f)
+            }),
+        }
+    }
+}
+
+/// Create a new header without source text.
+impl From<HeaderFrame> for Header {
+    fn from(frame: HeaderFrame) -> Self {
+        Self {
+            frame,
+            source: None,
+        }
+    }
+}
+
+impl From<Header> for HeaderFrame {
+    fn from(header: Header) -> Self {
+        header.frame
+    }
+}
+
+/// An entity frame together with the source text it was parsed from.
+///
+/// The entity frame can be accessed and modified through `Deref` and
+/// `DerefMut`. When written, the frame header, the identifier line and the
+/// clauses that were not modified keep their original text, and blank or
+/// comment-only lines are kept in place.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    frame: EntityFrame,
+    source: Option<FrameSource>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct FrameSource {
+    frame: EntityFrame,
+    header: String,
+    id: String,
+    lines: Vec<RawLine>,
+}
+
+impl Frame {
+    /// Parse the entity frame from a chunk of source text.
+    pub(crate) fn from_chunk(chunk: &FrameChunk) -> Result<Self> {
+        let frame = chunk.parse_skipping(is_trivia)?;
+
+        // Split the frame header and identifier from the other lines, which
+        // is safe since the parser accepted each of them on its own line.
+        let mut header = String::new();
+        let mut id = String::new();
+        let mut lines = Vec::new();
+        let mut seen = 0;
+        for (_, _, line) in chunk.lines() {
+            match seen {
+                0 => header.push_str(line),
+                1 if is_trivia(line) => header.push_str(line),
+                1 => id.push_str(line),
+                _ => lines.push(RawLine::new(line)),
+            }
+            if seen < 2 && !is_trivia(line) {
+                seen += 1;
+            }
+        }
+
+        Ok(Self {
+            frame: frame.clone(),
+            source: Some(FrameSource {
+                frame,
+                header,
+                id,
+                lines,
+            }),
+        })
+    }
+
+    /// Check whether the entity frame was created without any source text.
+    pub(crate) fn is_detached(&self) -> bool {
+        self.source.is_none()
+    }
+
+    /// Consume the frame and extract the entity frame.
+    pub fn into_inner(self) -> EntityFrame {
+        self.frame
+    }
+}
+
+impl AsRef<EntityFrame> for Frame {
+    fn as_ref(&self) -> &EntityFrame {
+        &self.frame
+    }
+}
+
+impl AsMut<EntityFrame> for Frame {
+    fn as_mut(&mut self) -> &mut EntityFrame {
+        &mut self.frame
+    }
+}
+
+impl Deref for Frame {
+    type Target = EntityFrame;
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl DerefMut for Frame {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.frame
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let source = match &self.source {
+            Some(source) => source,
+            None => return self.frame.fmt(f),
+        };
+
+        macro_rules! write_frame {
+            ($original:ident, $current:ident) => {{
+                f.write_str(&source.header)?;
+                if $original.id() == $current.id() {
+                    f.write_str(&source.id)?;
+                } else {
+                    f.write_str("id: ").and($current.id().fmt(f))?;
+                }
+                write_lines(
+                    f,
+                    &source.lines,
+                    $original.clauses(),
+                    $current.clauses(),
+                    |f, c| c.fmt(f),
+                )
+            }};
+        }
+
+        use self::EntityFrame::*;
+        match (&source.frame, &self.frame) {
+            (Term(original), Term(current)) => write_frame!(original, current),
+            (Typedef(original), Typedef(current)) => write_frame!(original, current),
+            (Instance(original), Instance(current)) => write_frame!(original, current),
+            // The frame kind changed, only the trailing trivia can be kept.
+            (_, current) => {
+                current.fmt(f)?;
+                let tail = source
+                    .lines
+                    .iter()
+                    .rposition(|l| !is_trivia(l.as_str()))
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                source.lines[tail..]
+                    .iter()
+                    .try_for_each(|l| f.write_str(l.as_str()))
+            }
+        }
+    }
+}
+
+/// Create a new frame without source text.
+impl From<EntityFrame> for Frame {
+    fn from(frame: EntityFrame) -> Self {
+        Self {
+            frame,
+            source: None,
+        }
+    }
+}
+
+impl From<Frame> for EntityFrame {
+    fn from(frame: Frame) -> Self {
+        frame.frame
+    }
+}
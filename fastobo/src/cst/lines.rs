@@ -0,0 +1,168 @@
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+/// A raw line of an OBO frame, as found in the source document.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum RawLine {
+    /// A blank or comment-only line, ignored by the parser.
+    Trivia(String),
+    /// A line containing a clause, in the same order as the parsed clauses.
+    Clause(String),
+}
+
+impl RawLine {
+    /// Create a new raw line from its text, including the line terminator.
+    pub fn new(text: &str) -> Self {
+        if is_trivia(text) {
+            RawLine::Trivia(text.to_string())
+        } else {
+            RawLine::Clause(text.to_string())
+        }
+    }
+
+    /// Get the text of the line, including the line terminator.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RawLine::Trivia(s) => s,
+            RawLine::Clause(s) => s,
+        }
+    }
+}
+
+/// Check whether a line is blank or only contains a comment.
+pub(crate) fn is_trivia(line: &str) -> bool {
+    let l = line.trim();
+    l.is_empty() || l.starts_with('!')
+}
+
+/// Write a list of clauses, reusing the source text of the unchanged ones.
+///
+/// `lines` is the source text that was parsed into `original`. The clauses of
+/// `current` that also appear in `original` (as found by a longest common
+/// subsequence) are written using their source text, and trivia lines are
+/// kept in place. Other clauses are written with `write`, before the trivia
+/// lines ending the source.
+pub(crate) fn write_lines<C, F>(
+    f: &mut Formatter,
+    lines: &[RawLine],
+    original: &[C],
+    current: &[C],
+    mut write: F,
+) -> FmtResult
+where
+    C: PartialEq,
+    F: FnMut(&mut Formatter, &C) -> FmtResult,
+{
+    // Use the source text as-is if no clause was changed.
+    if original == current {
+        return lines.iter().try_for_each(|l| f.write_str(l.as_str()));
+    }
+
+    // Find where the trailing trivia starts.
+    let tail = lines
+        .iter()
+        .rposition(|l| match l {
+            RawLine::Clause(_) => true,
+            RawLine::Trivia(_) => false,
+        })
+        .map(|i| i + 1)
+        .unwrap_or(0);
+
+    let matches = align(original, current);
+    let mut next = 0;
+    let mut idx = 0;
+    let mut newline = true;
+    for (i, line) in lines.iter().enumerate() {
+        if i == tail {
+            for clause in &current[next..] {
+                if !newline {
+                    f.write_str("\n")?;
+                }
+                write(f, clause)?;
+                newline = true;
+            }
+            next = current.len();
+        }
+        match line {
+            RawLine::Trivia(text) => {
+                f.write_str(text)?;
+                newline = text.ends_with('\n');
+            }
+            RawLine::Clause(text) => {
+                if let Some(j) = matches[idx] {
+                    for clause in &current[next..j] {
+                        write(f, clause)?;
+                    }
+                    f.write_str(text)?;
+                    newline = text.ends_with('\n');
+                    next = j + 1;
+                }
+                idx += 1;
+            }
+        }
+    }
+
+    for clause in &current[next..] {
+        if !newline {
+            f.write_str("\n")?;
+        }
+        write(f, clause)?;
+        newline = true;
+    }
+
+    Ok(())
+}
+
+/// Match the clauses of `original` with the ones of `current`.
+///
+/// Returns, for each clause of `original`, the index of the same clause in
+/// `current`, or `None` if it was removed or modified.
+fn align<C: PartialEq>(original: &[C], current: &[C]) -> Vec<Option<usize>> {
+    let (n, m) = (original.len(), current.len());
+
+    // Compute the length of the longest common subsequence of each suffix.
+    let mut table = vec![0; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[at(i, j)] = if original[i] == current[j] {
+                table[at(i + 1, j + 1)] + 1
+            } else {
+                table[at(i + 1, j)].max(table[at(i, j + 1)])
+            };
+        }
+    }
+
+    // Walk the table to recover the matched clauses.
+    let mut matches = vec![None; n];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if original[i] == current[j] {
+            matches[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if table[at(i + 1, j)] >= table[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn align_clauses() {
+        let original = ["a", "b", "c", "d"];
+        let current = ["a", "c", "e", "d"];
+        assert_eq!(
+            align(&original, &current),
+            vec![Some(0), None, Some(1), Some(3)]
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! Lossless syntax tree for format-preserving edits of OBO documents.
+//!
+//! The `Display` implementation of [`OboDoc`] normalizes the serialized
+//! document: whitespace is canonicalized, and blank lines or comment-only
+//! lines are dropped. A [`Document`] instead keeps the source text of every
+//! frame next to its syntax tree, so that writing it back only rewrites the
+//! lines that were actually modified.
+//!
+//! Frames are accessed and edited through the usual [`ast`] types, using
+//! the `Deref` and `DerefMut` implementations of [`Header`] and [`Frame`].
+//! Blank lines and lines only containing a `!` comment are treated as trivia:
+//! they are ignored by the parser, and written back at their original place.
+//!
+//! # Example
+//! ```rust
+//! # extern crate fastobo;
+//! # use fastobo::ast::*;
+//! # use fastobo::cst::Document;
+//! let text = "format-version: 1.4\n\n[Term]\nid: TST:001\nname:   first term\n! a comment\nis_a: TST:000\n";
+//! let mut doc: Document = text.parse().unwrap();
+//!
+//! if let EntityFrame::Term(frame) = &mut *doc.entities[0] {
+//!     frame.pop();
+//! }
+//!
+//! assert_eq!(
+//!     doc.to_string(),
+//!     "format-version: 1.4\n\n[Term]\nid: TST:001\nname:   first term\n! a comment\n"
+//! );
+//! ```
+//!
+//! [`ast`]: ../ast/index.html
+//! [`Document`]: ./struct.Document.html
+//! [`Frame`]: ./struct.Frame.html
+//! [`Header`]: ./struct.Header.html
+//! [`OboDoc`]: ../ast/struct.OboDoc.html
+
+mod frame;
+mod lines;
+
+pub use self::frame::*;
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::str::FromStr;
+
+use crate::ast::OboDoc;
+use crate::error::Error;
+use crate::error::Result;
+use crate::parser::frame_starts;
+use crate::parser::FrameChunk;
+
+/// An OBO document preserving the source text of its frames.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Document {
+    pub header: Header,
+    pub entities: Vec<Frame>,
+}
+
+impl Document {
+    /// Create a new document with the provided header and entity frames.
+    pub fn with_entities<E>(header: Header, entities: E) -> Self
+    where
+        E: IntoIterator<Item = Frame>,
+    {
+        Self {
+            header,
+            entities: entities.into_iter().collect(),
+        }
+    }
+
+    /// Get a reference to the header of the document.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Get a mutable reference to the header of the document.
+    pub fn header_mut(&mut self) -> &mut Header {
+        &mut self.header
+    }
+}
+
+impl Display for Document {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        // Frames without source text are separated from their neighbours by
+        // a blank line, like in `OboDoc`. Other frames are written as-is, so
+        // the number of newlines ending the output so far must be tracked.
+        let mut newlines = if self.header.is_detached() {
+            let text = format!("{}\n", self.header);
+            f.write_str(&text)?;
+            trailing_newlines(&text, 0)
+        } else {
+            let text = self.header.to_string();
+            f.write_str(&text)?;
+            trailing_newlines(&text, 2)
+        };
+
+        let mut detached = self.header.is_detached();
+        for entity in self.entities.iter() {
+            let required = if detached || entity.is_detached() { 2 } else { 1 };
+            for _ in newlines..required {
+                f.write_str("\n")?;
+            }
+            let text = entity.to_string();
+            f.write_str(&text)?;
+            newlines = trailing_newlines(&text, newlines);
+            detached = entity.is_detached();
+        }
+
+        Ok(())
+    }
+}
+
+impl From<OboDoc> for Document {
+    fn from(doc: OboDoc) -> Self {
+        Self {
+            header: Header::from(doc.header),
+            entities: doc.entities.into_iter().map(Frame::from).collect(),
+        }
+    }
+}
+
+impl From<Document> for OboDoc {
+    fn from(doc: Document) -> Self {
+        Self::with_entities(
+            doc.header.into_inner(),
+            doc.entities.into_iter().map(Frame::into_inner),
+        )
+    }
+}
+
+impl FromStr for Document {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        // Split the text into the header and the frame chunks, using the
+        // same frame boundaries as the `FrameReader`.
+        let starts = frame_starts(s);
+        let header = Header::from_text(&s[..starts[0].1])?;
+        let entities = starts
+            .windows(2)
+            .map(|w| {
+                let ((line_offset, start), (_, end)) = (w[0], w[1]);
+                Frame::from_chunk(&FrameChunk::new(
+                    s[start..end].to_string(),
+                    line_offset,
+                    start,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { header, entities })
+    }
+}
+
+/// Count the newlines ending the output after writing `text`, up to two.
+fn trailing_newlines(text: &str, previous: usize) -> usize {
+    let count = text.bytes().rev().take_while(|&b| b == b'\n').count();
+    if count == text.len() {
+        (previous + count).min(2)
+    } else {
+        count.min(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ast::*;
+
+    const DOC: &str = "format-version: 1.4
+! the ontology name
+ontology:  test
+
+[Term]
+id: TST:001
+name:    first term
+! an inline comment
+def: \"A term.\" []
+
+
+[Term]
+id: TST:002
+name: second term
+is_a: TST:001 ! first term
+
+[Typedef]
+id: part_of
+";
+
+    #[test]
+    fn unchanged() {
+        let doc = Document::from_str(DOC).unwrap();
+        assert_eq!(doc.to_string(), DOC);
+        assert_eq!(doc.entities.len(), 3);
+        assert_eq!(doc.header.len(), 2);
+    }
+
+    #[test]
+    fn unchanged_ms() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/ms.obo");
+        let text = std::fs::read_to_string(path).unwrap();
+        let doc = Document::from_str(&text).unwrap();
+        assert_eq!(doc.to_string(), text);
+    }
+
+    #[test]
+    fn modified_clause() {
+        let mut doc = Document::from_str(DOC).unwrap();
+        if let EntityFrame::Term(frame) = &mut *doc.entities[1] {
+            frame[0] = Line::from(TermClause::Name(UnquotedString::new("renamed")));
+            frame.push(Line::from(TermClause::IsObsolete(false)));
+        }
+        assert_eq!(
+            doc.to_string(),
+            DOC.replace("name: second term", "name: renamed")
+                .replace("! first term\n", "! first term\nis_obsolete: false\n")
+        );
+    }
+
+    #[test]
+    fn modified_header() {
+        let mut doc = Document::from_str(DOC).unwrap();
+        doc.header.remove(0);
+        doc.header.push(Line::from(HeaderClause::Remark(UnquotedString::new("edited"))));
+        assert_eq!(
+            doc.to_string(),
+            DOC.replacen("format-version: 1.4\n", "", 1)
+                .replacen("test\n", "test\nremark: edited\n", 1)
+        );
+    }
+
+    #[test]
+    fn new_frame() {
+        let mut doc = Document::from_str(DOC).unwrap();
+        let id = ClassIdent::from(PrefixedIdent::new("TST", "003"));
+        doc.entities.insert(1, Frame::from(EntityFrame::from(TermFrame::new(id))));
+        assert_eq!(
+            doc.to_string(),
+            DOC.replace("\n\n\n[Term]", "\n\n\n[Term]\nid: TST:003\n\n[Term]")
+        );
+    }
+
+    #[test]
+    fn from_obo_doc() {
+        let text = "format-version: 1.4\n\n[Term]\nid: TST:001\n\n[Typedef]\nid: part_of\n";
+        let doc = OboDoc::from_str(text).unwrap();
+        let cst = Document::from(doc.clone());
+        assert_eq!(cst.to_string(), doc.to_string());
+        assert_eq!(OboDoc::from(cst), doc);
+    }
+
+    #[test]
+    fn error_offsets() {
+        let text = "format-version: 1.4\n\n[Term]\nid: TST:001\n! comment\nis_a: \n";
+        match Document::from_str(text).unwrap_err() {
+            Error::ParserError { error } => match error.line_col {
+                pest::error::LineColLocation::Pos((l, _)) => assert_eq!(l, 6),
+                pest::error::LineColLocation::Span((l, _), _) => assert_eq!(l, 6),
+            },
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+}
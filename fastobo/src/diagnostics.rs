@@ -0,0 +1,630 @@
+//! Human-readable diagnostics for the errors of this crate.
+//!
+//! Errors reported by [`pest`](https://pest.rs/) refer to the rules of the
+//! OBO grammar by their internal name (e.g. `NonCanonicalIdPrefix`), which
+//! is not very helpful to someone editing an ontology. A [`Diagnostic`]
+//! describes an [`Error`] with a stable [`Code`], a message written with the
+//! OBO terminology, the location of the error and some additional notes.
+//! A [`Renderer`] can then be used to display it with an annotated snippet
+//! of the source.
+//!
+//! # Example
+//! ```rust
+//! extern crate fastobo;
+//!
+//! use std::str::FromStr;
+//! use fastobo::ast::OboDoc;
+//! use fastobo::diagnostics::Diagnostic;
+//! use fastobo::diagnostics::Renderer;
+//!
+//! let text = "[Term]\nid: GO:0005623\nsynonym: \"cell\" SOMETIMES []\n";
+//! let error = OboDoc::from_str(text).unwrap_err();
+//! let diagnostic = Diagnostic::from(&error);
+//!
+//! assert_eq!(diagnostic.code().as_str(), "E0001");
+//! assert_eq!(diagnostic.message(), "expected a synonym scope");
+//! println!("{}", Renderer::new().render(&diagnostic));
+//! ```
+//!
+//! [`Diagnostic`]: ./struct.Diagnostic.html
+//! [`Error`]: ../error/enum.Error.html
+//! [`Code`]: ./enum.Code.html
+//! [`Renderer`]: ./struct.Renderer.html
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+
+use pest::error::Error as PestError;
+use pest::error::ErrorVariant;
+
+use crate::error::Error;
+use crate::parser::upgrade_line;
+use crate::parser::Rule;
+
+/// The stable code of a diagnostic.
+///
+/// Codes are never reused nor renumbered, so that they can be referred to
+/// in documentation or used to filter the diagnostics of a tool.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Code {
+    /// `E0001`: the input does not match the OBO grammar.
+    Syntax,
+    /// `E0002`: a value matched the OBO grammar but was rejected.
+    ///
+    /// Values rejected for a known reason are reported with one of the more
+    /// specific codes from `E0006` to `E0010`.
+    InvalidValue,
+    /// `E0003`: a syntax node was built from a token of another rule.
+    UnexpectedRule,
+    /// `E0004`: the input could not be read.
+    Io,
+    /// `E0005`: the input is not valid UTF-8.
+    InvalidEncoding,
+    /// `E0006`: an IRI could not be parsed.
+    InvalidIri,
+    /// `E0007`: a date does not exist in the calendar.
+    InvalidDate,
+    /// `E0008`: some input was left after a value.
+    RemainingInput,
+    /// `E0009`: a string contains an invalid escape sequence.
+    InvalidEscape,
+    /// `E0010`: a unique clause was repeated in a frame.
+    DuplicateClause,
+}
+
+impl Code {
+    /// Get the code as a string, e.g. `"E0001"`.
+    pub fn as_str(self) -> &'static str {
+        use self::Code::*;
+        match self {
+            Syntax => "E0001",
+            InvalidValue => "E0002",
+            UnexpectedRule => "E0003",
+            Io => "E0004",
+            InvalidEncoding => "E0005",
+            InvalidIri => "E0006",
+            InvalidDate => "E0007",
+            RemainingInput => "E0008",
+            InvalidEscape => "E0009",
+            DuplicateClause => "E0010",
+        }
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The location of a diagnostic in the source.
+///
+/// Lines and columns start from 1, and columns are counted in characters.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Span {
+    path: Option<String>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+impl Span {
+    /// Create a new span between two `(line, column)` positions.
+    pub fn new(start: (usize, usize), end: (usize, usize)) -> Self {
+        Self {
+            path: None,
+            start,
+            end,
+        }
+    }
+
+    /// Set the path of the file the span refers to.
+    pub fn and_path<P>(mut self, path: P) -> Self
+    where
+        P: Into<Option<String>>,
+    {
+        self.path = path.into();
+        self
+    }
+
+    /// Get the path of the file the span refers to, if any.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Get the `(line, column)` position of the start of the span.
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// Get the `(line, column)` position of the end of the span.
+    ///
+    /// The end of a span pointing at a single position is equal to its start.
+    pub fn end(&self) -> (usize, usize) {
+        self.end
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if let Some(ref path) = self.path {
+            write!(f, "{}:", path)?;
+        }
+        write!(f, "{}:{}", self.start.0, self.start.1)
+    }
+}
+
+/// A human-readable description of an error.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Diagnostic {
+    code: Code,
+    message: String,
+    span: Option<Span>,
+    source_line: Option<String>,
+    notes: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Create a new diagnostic with the given code and message.
+    pub fn new<S>(code: Code, message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            code,
+            message: message.into(),
+            span: None,
+            source_line: None,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Set the primary span of the diagnostic.
+    pub fn and_span<S>(mut self, span: S) -> Self
+    where
+        S: Into<Option<Span>>,
+    {
+        self.span = span.into();
+        self
+    }
+
+    /// Set the source line the primary span starts on.
+    pub fn and_source_line<S>(mut self, line: S) -> Self
+    where
+        S: Into<Option<String>>,
+    {
+        self.source_line = line.into();
+        self
+    }
+
+    /// Add a note to the diagnostic.
+    pub fn and_note<S>(mut self, note: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Get the code of the diagnostic.
+    pub fn code(&self) -> Code {
+        self.code
+    }
+
+    /// Get the message of the diagnostic.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the primary span of the diagnostic, if any.
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+
+    /// Get the source line the primary span starts on, if known.
+    pub fn source_line(&self) -> Option<&str> {
+        self.source_line.as_deref()
+    }
+
+    /// Get the additional notes of the diagnostic.
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Build a diagnostic from a syntax error reported by pest.
+    fn from_pest(source: &Error, error: &PestError<Rule>) -> Self {
+        let (code, message) = match error.variant {
+            ErrorVariant::ParsingError {
+                ref positives,
+                ref negatives,
+            } => (Code::Syntax, parsing_message(positives, negatives)),
+            ErrorVariant::CustomError { ref message } => {
+                // Invalid UTF-8 is reported by the readers before parsing.
+                let code = if message.starts_with("invalid UTF-8") {
+                    Code::InvalidEncoding
+                } else {
+                    Code::InvalidValue
+                };
+                (code, message.clone())
+            }
+        };
+
+        let line = source.source_line().unwrap_or_default();
+        let mut diagnostic = Diagnostic::new(code, message)
+            .and_span(source.span())
+            .and_source_line(String::from(line));
+
+        if let ErrorVariant::ParsingError { ref positives, .. } = error.variant {
+            if upgrade_line(line).is_some() {
+                diagnostic = diagnostic.and_note(
+                    "this clause uses the OBO 1.2 syntax, which can be upgraded \
+                     with `OboDoc::from_stream_legacy`",
+                );
+            } else if positives.contains(&Rule::EntityFrame) {
+                diagnostic = diagnostic
+                    .and_note("frames must start with `[Term]`, `[Typedef]` or `[Instance]`");
+            } else if positives.iter().any(is_clause) {
+                diagnostic = diagnostic.and_note(
+                    "the clause tag may be misspelled, or its value may be missing or invalid",
+                );
+            }
+        }
+
+        diagnostic
+    }
+}
+
+impl<'a> From<&'a Error> for Diagnostic {
+    fn from(error: &'a Error) -> Self {
+        match error {
+            Error::ParserError { error: pest } => Diagnostic::from_pest(error, pest),
+            Error::UnexpectedRule { expected, actual } => Diagnostic::new(
+                Code::UnexpectedRule,
+                format!("expected {}, found {}", describe(*expected), describe(*actual)),
+            )
+            .and_note("`FromPair::from_pair` was called with a token of another rule"),
+            Error::IOError { error } => Diagnostic::new(Code::Io, error.to_string()),
+            Error::InvalidIri { value, error: e, .. } => {
+                Diagnostic::new(Code::InvalidIri, format!("invalid IRI `{}`: {}", value, e))
+                    .and_span(error.span())
+            }
+            Error::InvalidDate { value, .. } => {
+                Diagnostic::new(Code::InvalidDate, format!("invalid date `{}`", value))
+                    .and_span(error.span())
+                    .and_note("the day does not exist in that month, or the time is out of range")
+            }
+            Error::RemainingInput { value, .. } => {
+                Diagnostic::new(Code::RemainingInput, format!("unexpected `{}`", value))
+                    .and_span(error.span())
+                    .and_note("the input continues after the end of the value")
+            }
+            Error::InvalidEscape { value, .. } => {
+                Diagnostic::new(Code::InvalidEscape, format!("invalid escape sequence `{}`", value))
+                    .and_span(error.span())
+                    .and_note("unicode escapes are written `\\u` followed by 4 hexadecimal digits")
+            }
+            Error::DuplicateClause { value, .. } => {
+                Diagnostic::new(Code::DuplicateClause, format!("duplicate clause `{}`", value))
+                    .and_span(error.span())
+                    .and_note("this clause may only appear once in a frame")
+            }
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&Renderer::new().render(self))
+    }
+}
+
+/// A renderer for diagnostics, printing annotated source snippets.
+///
+/// The output mimics the diagnostics of `rustc`:
+/// ```text
+/// error[E0001]: expected a synonym scope
+///  --> go.obo:5:17
+///   |
+/// 5 | synonym: "cell" SOMETIMES []
+///   |                 ^
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    /// Create a new renderer without colors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable ANSI colors in the rendered output.
+    pub fn and_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Render a diagnostic to a string.
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut out = String::new();
+        self.write(diagnostic, &mut out)
+            .expect("writing to a string cannot fail");
+        out
+    }
+
+    /// Render a diagnostic to the given writer.
+    pub fn write<W: Write>(&self, diagnostic: &Diagnostic, f: &mut W) -> FmtResult {
+        write!(
+            f,
+            "{}: {}",
+            self.paint(&format!("error[{}]", diagnostic.code), "1;31"),
+            self.paint(&diagnostic.message, "1")
+        )?;
+
+        let span = match diagnostic.span {
+            Some(ref span) => span,
+            None => {
+                for note in diagnostic.notes.iter() {
+                    write!(f, "\n{} note: {}", self.paint("=", "1;34"), note)?;
+                }
+                return Ok(());
+            }
+        };
+
+        let number = span.start.0.to_string();
+        let gutter = " ".repeat(number.len());
+        let bar = self.paint("|", "1;34");
+
+        write!(f, "\n{}{} {}", gutter, self.paint("-->", "1;34"), span)?;
+        if let Some(ref line) = diagnostic.source_line {
+            // Keep the tabs of the source line so that the marker is aligned.
+            let start = span.start.1.saturating_sub(1);
+            let padding: String = line
+                .chars()
+                .take(start)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let width = if span.end.0 == span.start.0 && span.end.1 > span.start.1 {
+                span.end.1 - span.start.1
+            } else if span.end.0 > span.start.0 {
+                line.chars().count().saturating_sub(start).max(1)
+            } else {
+                1
+            };
+            write!(f, "\n{} {}", gutter, bar)?;
+            write!(f, "\n{} {} {}", self.paint(&number, "1;34"), bar, line)?;
+            write!(
+                f,
+                "\n{} {} {}{}",
+                gutter,
+                bar,
+                padding,
+                self.paint(&"^".repeat(width), "1;31")
+            )?;
+        }
+
+        if !diagnostic.notes.is_empty() {
+            write!(f, "\n{} {}", gutter, bar)?;
+            for note in diagnostic.notes.iter() {
+                write!(f, "\n{} {} note: {}", gutter, self.paint("=", "1;34"), note)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wrap some text in an ANSI escape sequence if colors are enabled.
+    fn paint(&self, text: &str, style: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", style, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Check whether a rule is one of the clause rules of a frame.
+fn is_clause(rule: &Rule) -> bool {
+    matches!(
+        rule,
+        Rule::HeaderClause | Rule::TermClause | Rule::TypedefClause | Rule::InstanceClause
+    )
+}
+
+/// Build the message of a pest parsing error.
+fn parsing_message(positives: &[Rule], negatives: &[Rule]) -> String {
+    match (positives.is_empty(), negatives.is_empty()) {
+        (false, true) => format!("expected {}", enumerate(positives)),
+        (true, false) => format!("unexpected {}", enumerate(negatives)),
+        (false, false) => format!(
+            "unexpected {}, expected {}",
+            enumerate(negatives),
+            enumerate(positives)
+        ),
+        (true, true) => String::from("unknown parsing error"),
+    }
+}
+
+/// Describe a list of rules, merging rules with the same description.
+fn enumerate(rules: &[Rule]) -> String {
+    let mut descriptions: Vec<String> = Vec::with_capacity(rules.len());
+    for rule in rules {
+        let description = describe(*rule);
+        if !descriptions.contains(&description) {
+            descriptions.push(description);
+        }
+    }
+    match descriptions.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+/// Describe a grammar rule using the OBO terminology.
+///
+/// Rules without a dedicated description are described by splitting their
+/// name into lowercase words, e.g. `IsObsoleteTag` becomes `is obsolete tag`.
+pub fn describe(rule: Rule) -> String {
+    use crate::parser::Rule::*;
+    let description = match rule {
+        EOI => "end of input",
+        EOL => "end of line",
+        HiddenComment => "a comment",
+        Boolean => "a boolean (`true` or `false`)",
+        QuotedString => "a quoted string",
+        UnquotedString => "a value",
+        Qualifier => "a qualifier",
+        QualifierId => "a qualifier name",
+        QualifierList => "a qualifier list",
+        ClassId => "a class identifier",
+        RelationId => "a relation identifier",
+        InstanceId => "an instance identifier",
+        SynonymTypeId => "a synonym type identifier",
+        NamespaceId => "a namespace identifier",
+        SubsetId => "a subset identifier",
+        Id | UrlId | UnprefixedId | PrefixedId => "an identifier",
+        IdPrefix | CanonicalIdPrefix | NonCanonicalIdPrefix => "an identifier prefix",
+        IdLocal | CanonicalIdLocal | NonCanonicalIdLocal => "a local identifier",
+        Xref | XrefId | XrefListItem => "a cross-reference",
+        XrefList => "a cross-reference list",
+        OboDoc => "an OBO document",
+        EntityFrame | EntitySingle => "a frame",
+        HeaderFrame => "a header frame",
+        HeaderClause | HeaderClauseLine => "a header clause",
+        Unreserved => "a header tag",
+        TermFrame => "a term frame",
+        TermClause | TermClauseLine => "a term clause",
+        TypedefFrame => "a typedef frame",
+        TypedefClause | TypedefClauseLine => "a typedef clause",
+        InstanceFrame => "an instance frame",
+        InstanceClause | InstanceClauseLine => "an instance clause",
+        NaiveDateTime | NaiveDate | NaiveTime | NaiveDay | NaiveMonth | NaiveYear
+        | NaiveHour | NaiveMinute => "a date (`dd:MM:yyyy HH:mm`)",
+        SynonymScope => "a synonym scope",
+        Synonym => "a synonym",
+        Import => "an import",
+        PropertyValue => "a property value",
+        PvValue => "a property value",
+        XsdDatatype => "a datatype",
+        Iso8601DateTime | Iso8601Date | Iso8601DateSep | Iso8601Year | Iso8601Month
+        | Iso8601Day | Iso8601Time | Iso8601TimeSep | Iso8601Hour | Iso8601Minute
+        | Iso8601Second => "an ISO-8601 datetime",
+        Iso8601TimeZone => "a timezone",
+        Iri => "an IRI",
+        IriPort => "an IRI port",
+        _ => {
+            let name = format!("{:?}", rule);
+            if name.starts_with("Iri") {
+                return String::from("an IRI");
+            }
+            let mut words = String::with_capacity(name.len() + 4);
+            for c in name.chars() {
+                if c.is_uppercase() && !words.is_empty() {
+                    words.push(' ');
+                }
+                words.extend(c.to_lowercase());
+            }
+            return words;
+        }
+    };
+    String::from(description)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::str::FromStr;
+
+    use crate::ast::OboDoc;
+    use crate::ast::QuotedString;
+    use crate::parser::FromPair;
+    use crate::parser::OboParser;
+
+    use pest::Parser;
+
+    #[test]
+    fn describe() {
+        assert_eq!(super::describe(Rule::NonCanonicalIdPrefix), "an identifier prefix");
+        assert_eq!(super::describe(Rule::IsObsoleteTag), "is obsolete tag");
+        assert_eq!(super::describe(Rule::IriSegmentNz), "an IRI");
+    }
+
+    #[test]
+    fn syntax_error() {
+        let text = "[Term]\nid: GO:0005623\nis_obsolete: maybe\n";
+        let error = OboDoc::from_str(text).unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), Code::Syntax);
+        assert_eq!(diagnostic.message(), "expected a boolean (`true` or `false`)");
+        assert_eq!(diagnostic.span(), Some(&Span::new((3, 14), (3, 14))));
+        assert_eq!(diagnostic.source_line(), Some("is_obsolete: maybe"));
+        assert!(diagnostic.notes().is_empty());
+        assert_eq!(
+            diagnostic.to_string(),
+            concat!(
+                "error[E0001]: expected a boolean (`true` or `false`)\n",
+                " --> 3:14\n",
+                "  |\n",
+                "3 | is_obsolete: maybe\n",
+                "  |              ^",
+            )
+        );
+    }
+
+    #[test]
+    fn legacy_note() {
+        let text = "[Term]\nid: GO:0005623\nexact_synonym: \"cell\" []\n";
+        let error = OboDoc::from_str(text).unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(
+            diagnostic.message(),
+            "expected end of input, a frame or a term clause"
+        );
+        assert_eq!(diagnostic.notes().len(), 1);
+        assert!(diagnostic.notes()[0].contains("OBO 1.2"));
+    }
+
+    #[test]
+    fn unexpected_rule() {
+        let pairs = OboParser::parse(Rule::UnquotedString, "hello").unwrap();
+        let error = QuotedString::from_pair(pairs.into_iter().next().unwrap()).unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), Code::UnexpectedRule);
+        assert_eq!(diagnostic.message(), "expected a quoted string, found a value");
+        assert_eq!(diagnostic.span(), None);
+    }
+
+    #[test]
+    fn encoding_error() {
+        let mut stream = std::io::BufReader::new(&b"format-version: 1.4\nremark: \xff\n"[..]);
+        let error = OboDoc::from_stream(&mut stream).unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), Code::InvalidEncoding);
+        assert_eq!(diagnostic.code().as_str(), "E0005");
+        assert_eq!(diagnostic.message(), "invalid UTF-8 sequence starting with \\xff\\x0a");
+        assert_eq!(diagnostic.span().map(Span::start), Some((2, 9)));
+    }
+
+    #[test]
+    fn semantic_error() {
+        let text = "format-version: 1.4\ndate: 30:02:2019 12:00\n";
+        let error = OboDoc::from_str(text).unwrap_err();
+        let diagnostic = Diagnostic::from(&error);
+        assert_eq!(diagnostic.code(), Code::InvalidDate);
+        assert_eq!(diagnostic.message(), "invalid date `30:02:2019 12:00`");
+        assert_eq!(diagnostic.span(), Some(&Span::new((2, 7), (2, 23))));
+    }
+
+    #[test]
+    fn render_color() {
+        let diagnostic = Diagnostic::new(Code::Io, "file not found");
+        assert_eq!(
+            Renderer::new().and_color(true).render(&diagnostic),
+            "\x1b[1;31merror[E0004]\x1b[0m: \x1b[1mfile not found\x1b[0m"
+        );
+    }
+}
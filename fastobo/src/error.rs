@@ -1,17 +1,26 @@
 //! `Error` and `Result` types for this crate.
 
+use std::error::Error as StdError;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
 use std::io::Error as IOError;
+use std::path::Path;
+use std::sync::Arc;
 
 use pest::Span;
 use pest::Position;
 use pest::error::Error as PestError;
 use pest::error::InputLocation;
 use pest::error::LineColLocation;
+use url::ParseError as UrlParseError;
 
+use crate::ast::SourceLocation;
+use crate::diagnostics::Span as SourceSpan;
 use crate::parser::Rule;
 
 /// The error type for this crate.
-#[derive(Debug, Fail)]
+#[derive(Debug)]
 pub enum Error {
     /// An unexpected rule was used in `FromPair::from_pair`.
     ///
@@ -31,7 +40,6 @@ pub enum Error {
     /// #   e => panic!("unexpected error: {:?}", e),
     /// # };
     /// ```
-    #[fail(display = "unexpected rule: {:?} (expected {:?})", actual, expected)]
     UnexpectedRule { expected: Rule, actual: Rule },
 
     /// The underlying parser encountered an error.
@@ -48,7 +56,6 @@ pub enum Error {
     /// #   e => panic!("unexpected error: {:?}", e),
     /// # };
     /// ```
-    #[fail(display = "parser error: {}", error)]
     ParserError { error: PestError<Rule> },
 
     /// An IO error occurred.
@@ -63,62 +70,339 @@ pub enum Error {
     /// #   fastobo::error::Error::IOError { error } => (),
     /// #   e => panic!("unexpected error: {:?}", e),
     /// # };
-    #[fail(display = "IO error: {}", error)]
     IOError { error: IOError },
+
+    /// An IRI matched the grammar but could not be parsed as an URL.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::error::Error;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// Import::from_str("http://example.com:99999/onto.owl").unwrap_err();
+    /// # assert_eq!(err.source().unwrap().to_string(), "invalid port number");
+    /// # match err {
+    /// #   fastobo::error::Error::InvalidIri { value, .. } => {
+    /// #       assert_eq!(value, "http://example.com:99999/onto.owl");
+    /// #   }
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    InvalidIri {
+        value: String,
+        location: SourceLocation,
+        error: UrlParseError,
+    },
+
+    /// A date matched the grammar but does not exist in the calendar.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// NaiveDateTime::from_str("31:02:2019 12:00").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::Error::InvalidDate { value, .. } => {
+    /// #       assert_eq!(value, "31:02:2019 12:00");
+    /// #   }
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    InvalidDate {
+        value: String,
+        location: SourceLocation,
+    },
+
+    /// Some input was left after parsing a value from a string.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// ClassIdent::from_str("GO:0005623 GO:0005575").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::Error::RemainingInput { value, .. } => {
+    /// #       assert_eq!(value, " GO:0005575");
+    /// #   }
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    RemainingInput {
+        value: String,
+        location: SourceLocation,
+    },
+
+    /// A string contains an incomplete or invalid escape sequence.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// QuotedString::from_str("\"caf\\u00\"").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::Error::InvalidEscape { value, .. } => {
+    /// #       assert_eq!(value, "\\u00");
+    /// #   }
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    InvalidEscape {
+        value: String,
+        location: SourceLocation,
+    },
+
+    /// A clause that may only appear once in a frame was repeated.
+    ///
+    /// The location is missing if the repeated clause was built without one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// # let err =
+    /// HeaderFrame::from_str("format-version: 1.4\nformat-version: 1.2\n").unwrap_err();
+    /// # match err {
+    /// #   fastobo::error::Error::DuplicateClause { value, .. } => {
+    /// #       assert_eq!(value, "format-version: 1.2");
+    /// #   }
+    /// #   e => panic!("unexpected error: {:?}", e),
+    /// # };
+    /// ```
+    DuplicateClause {
+        value: String,
+        location: Option<SourceLocation>,
+    },
 }
 
 impl Error {
-    /// Update the line of the error, if needed.
-    pub(crate) fn with_offsets(self, line_offset: usize, offset: usize) -> Self {
-        use self::Error::*;
-        use pest::error::InputLocation;
-        use pest::error::LineColLocation;
+    /// Get the line where the error occurred, starting from 1.
+    ///
+    /// Returns `None` if the error is not located in the source, e.g. for
+    /// IO errors.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use std::str::FromStr;
+    /// # use fastobo::ast::*;
+    /// let text = "[Term]\nid: GO:0005623\nis_obsolete: maybe\n";
+    /// let err = OboDoc::from_str(text).unwrap_err();
+    /// assert_eq!(err.line(), Some(3));
+    /// assert_eq!(err.column(), Some(14));
+    /// assert_eq!(err.source_line(), Some("is_obsolete: maybe"));
+    /// ```
+    pub fn line(&self) -> Option<usize> {
+        self.span().map(|span| span.start().0)
+    }
+
+    /// Get the column where the error occurred, starting from 1.
+    ///
+    /// Columns are counted in characters, not in bytes.
+    pub fn column(&self) -> Option<usize> {
+        self.span().map(|span| span.start().1)
+    }
+
+    /// Get the path of the file where the error occurred, if known.
+    pub fn path(&self) -> Option<&str> {
         match self {
-            IOError { error } => IOError { error },
-            UnexpectedRule { expected, actual } => UnexpectedRule { expected, actual },
-            ParserError { mut error } => {
-                error.location = match error.location {
-                    InputLocation::Pos(s) =>
-                        InputLocation::Pos(s + offset),
-                    InputLocation::Span((s, e)) =>
-                        InputLocation::Span((s + offset, e + offset))
+            Error::ParserError { error } => error.path(),
+            _ => self.location()?.path.as_ref()?.to_str(),
+        }
+    }
+
+    /// Get the location of the error in the source, if any.
+    pub fn span(&self) -> Option<SourceSpan> {
+        match self {
+            Error::ParserError { error } => {
+                let span = match error.line_col {
+                    LineColLocation::Pos(pos) => SourceSpan::new(pos, pos),
+                    LineColLocation::Span(start, end) => SourceSpan::new(start, end),
                 };
-                error.line_col = match error.line_col {
-                    LineColLocation::Pos((l, c)) =>
-                        LineColLocation::Pos((l + line_offset, c)),
-                    LineColLocation::Span((ls, cs), (le, ce)) =>
-                        LineColLocation::Span((ls + line_offset, cs), (le + line_offset, ce))
+                Some(span.and_path(error.path().map(String::from)))
+            }
+            _ => {
+                let location = self.location()?;
+                let start = (location.line, location.column);
+                let value = self.value().unwrap_or_default();
+                let end = if value.contains('\n') {
+                    start
+                } else {
+                    (location.line, location.column + value.chars().count())
                 };
-                ParserError { error }
+                let path = self.path().map(String::from);
+                Some(SourceSpan::new(start, end).and_path(path))
             }
         }
     }
 
+    /// Get the source line where the error occurred, without its line
+    /// terminator.
+    ///
+    /// Only syntax errors reported by the parser record their source line.
+    pub fn source_line(&self) -> Option<&str> {
+        match self {
+            Error::ParserError { error } => {
+                Some(error.line().trim_end_matches(&['\r', '\n'][..]))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the location of a semantic error, if any.
+    ///
+    /// Syntax errors reported by the parser are located with [`span`]
+    /// instead, since they are not associated with a parsed value.
+    ///
+    /// [`span`]: #method.span
+    pub fn location(&self) -> Option<&SourceLocation> {
+        use self::Error::*;
+        match self {
+            InvalidIri { location, .. }
+            | InvalidDate { location, .. }
+            | RemainingInput { location, .. }
+            | InvalidEscape { location, .. } => Some(location),
+            DuplicateClause { location, .. } => location.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to the location of a semantic error, if any.
+    fn location_mut(&mut self) -> Option<&mut SourceLocation> {
+        use self::Error::*;
+        match self {
+            InvalidIri { location, .. }
+            | InvalidDate { location, .. }
+            | RemainingInput { location, .. }
+            | InvalidEscape { location, .. } => Some(location),
+            DuplicateClause { location, .. } => location.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Get the offending value of a semantic error, if any.
+    fn value(&self) -> Option<&str> {
+        use self::Error::*;
+        match self {
+            InvalidIri { value, .. }
+            | InvalidDate { value, .. }
+            | RemainingInput { value, .. }
+            | InvalidEscape { value, .. }
+            | DuplicateClause { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Update the line of the error, if needed.
+    pub(crate) fn with_offsets(mut self, line_offset: usize, offset: usize) -> Self {
+        if let Error::ParserError { ref mut error } = self {
+            error.location = match error.location {
+                InputLocation::Pos(s) =>
+                    InputLocation::Pos(s + offset),
+                InputLocation::Span((s, e)) =>
+                    InputLocation::Span((s + offset, e + offset))
+            };
+            error.line_col = match error.line_col {
+                LineColLocation::Pos((l, c)) =>
+                    LineColLocation::Pos((l + line_offset, c)),
+                LineColLocation::Span((ls, cs), (le, ce)) =>
+                    LineColLocation::Span((ls + line_offset, cs), (le + line_offset, ce))
+            };
+        } else if let Some(location) = self.location_mut() {
+            location.shift(line_offset, offset);
+        }
+        self
+    }
+
     /// Update the path of the error, if needed.
     pub(crate) fn with_path(self, path: &str) -> Self {
-        use self::Error::*;
         match self {
-            IOError { error } => IOError { error },
-            UnexpectedRule { expected, actual } => UnexpectedRule { expected, actual },
-            ParserError { error } => ParserError { error: error.with_path(path) },
+            Error::ParserError { error } => Error::ParserError { error: error.with_path(path) },
+            mut other => {
+                if let Some(location) = other.location_mut() {
+                    location.path = Some(Arc::from(Path::new(path)));
+                }
+                other
+            }
         }
     }
 
     /// Update the span of the error, if needed.
+    ///
+    /// Semantic errors are located relative to `span`, which must be the
+    /// span of the text they were parsed from.
     pub(crate) fn with_span<'i>(self, span: Span<'i>) -> Self {
-        use self::Error::*;
         match self {
-            IOError { error } => IOError { error },
-            UnexpectedRule { expected, actual } => UnexpectedRule { expected, actual },
-            ParserError { error } => {
+            Error::ParserError { error } => {
                 // FIXME(@althonos): the new error should be spanned only if
                 //                   the original error is spanned, but there
                 //                   is no clean way to create an error at
                 //                   the right position with `pest::error`.
-                ParserError {
+                Error::ParserError {
                     error: PestError::new_from_span(error.variant, span)
                 }
             }
+            mut other => {
+                if let Some(location) = other.location_mut() {
+                    let (line, column) = span.start_pos().line_col();
+                    if location.line == 1 {
+                        location.column += column - 1;
+                    }
+                    location.shift(line - 1, span.start());
+                }
+                other
+            }
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::Error::*;
+        match self {
+            UnexpectedRule { expected, actual } => {
+                write!(f, "unexpected rule: {:?} (expected {:?})", actual, expected)
+            }
+            ParserError { error } => write!(f, "parser error: {}", error),
+            IOError { error } => write!(f, "IO error: {}", error),
+            InvalidIri { value, location, error } => {
+                write!(f, "invalid IRI `{}` at {}: {}", value, location, error)
+            }
+            InvalidDate { value, location } => {
+                write!(f, "invalid date `{}` at {}", value, location)
+            }
+            RemainingInput { value, location } => {
+                write!(f, "remaining input `{}` at {}", value, location)
+            }
+            InvalidEscape { value, location } => {
+                write!(f, "invalid escape sequence `{}` at {}", value, location)
+            }
+            DuplicateClause { value, location: Some(location) } => {
+                write!(f, "duplicate clause `{}` at {}", value, location)
+            }
+            DuplicateClause { value, location: None } => {
+                write!(f, "duplicate clause `{}`", value)
+            }
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        use self::Error::*;
+        match self {
+            ParserError { error } => Some(error),
+            IOError { error } => Some(error),
+            InvalidIri { error, .. } => Some(error),
+            _ => None,
         }
     }
 }
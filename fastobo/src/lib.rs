@@ -18,14 +18,33 @@
 //!   OBO language, with constructors and covenience traits where applicable.
 //!   There is a plan to provide borrowed data structures as well, to be able
 //!   to build a view of an OBO document from borrowed data.
+//!   With the `arbitrary` feature, the AST implements [`Arbitrary`](https://docs.rs/arbitrary/1/arbitrary/trait.Arbitrary.html)
+//!   to generate syntactically valid values, e.g. to fuzz tools built on top
+//!   of `fastobo`. With the `serde` feature, it also implements `Serialize`
+//!   and `Deserialize`, so that parsed documents can be cached or exchanged
+//!   using any [serde](https://serde.rs/) format.
 //! * **Parsing** - The parser is implemented using [pest](http://pest.rs/),
 //!   and is reexported from the [`fastobo-syntax`](https://crates.io/crates/fastobo-syntax)
 //!   crate. Most structures implement the [`FromPair`](./parser/trait.FromPair.html)
 //!   trait which allows to build a data structure from a stream of pest tokens.
+//!   The `fast-parser` feature enables a hand-written parser for the most
+//!   common term clauses, falling back to pest for everything else.
+//!   Documents using the OBO 1.2 syntax can be upgraded to OBO 1.4 while
+//!   being parsed with [`OboDoc::from_stream_legacy`](./ast/struct.OboDoc.html#method.from_stream_legacy).
 //! * **Errors** - All functions in that crate that return a `Result` will
 //!   always use the `Error` struct defined in the `error` module. Errors
 //!   reported by pest are very meaningful, and can give the exact location
-//!   of a syntax error encountered by the parser.
+//!   of a syntax error encountered by the parser. The
+//!   [`diagnostics`](./diagnostics/index.html) module describes them with
+//!   stable codes and OBO terminology, and renders annotated source snippets.
+//! * **Editing** - The [`cst`](./cst/index.html) module keeps the source
+//!   text of every frame next to its syntax tree, so that an edited document
+//!   can be written back without reformatting the lines that were not changed.
+//! * **Serialization** - The [`writer`](./writer/index.html) module can
+//!   serialize documents following the canonical ordering of the OBO 1.4
+//!   specification, with configurable qualifiers, comments and line endings.
+//!   With the `obographs` feature, the [`obographs`](./obographs/index.html)
+//!   module converts documents to the OBO Graphs JSON model.
 //!
 //! *Warning: this project adheres to [Semantic Versioning](http://semver.org/spec/v2.0.0.html),
 //!  but the API is likely to change a lot before the release of a stable 1.0.*
@@ -85,22 +104,47 @@
 
 #![allow(dead_code, unused_imports)]
 
-#[macro_use]
-extern crate failure;
 #[macro_use]
 extern crate opaque_typedef_macros;
 
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "bzip2")]
+extern crate bzip2;
 extern crate fastobo_syntax;
+#[cfg(feature = "encoding_rs")]
+extern crate encoding_rs;
+#[cfg(feature = "encoding_rs_io")]
+extern crate encoding_rs_io;
+#[cfg(feature = "flate2")]
+extern crate flate2;
+#[cfg(feature = "futures")]
+extern crate futures;
 #[cfg(feature = "memchr")]
 extern crate memchr;
+#[cfg(feature = "memmap")]
+extern crate memmap;
 extern crate opaque_typedef;
 extern crate pest;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
 extern crate url;
+#[cfg(feature = "xz2")]
+extern crate xz2;
 
 #[macro_use]
 pub mod parser;
 
 pub mod ast;
+pub mod cst;
+pub mod diagnostics;
 pub mod error;
+#[cfg(feature = "obographs")]
+pub mod obographs;
 pub mod share;
 pub mod visit;
+pub mod writer;
@@ -0,0 +1,704 @@
+//! Conversion of OBO documents to the [OBO Graphs] JSON model.
+//!
+//! This module is enabled by the `obographs` feature. A [`GraphDocument`] can
+//! be built from an [`OboDoc`] with `From`, and written as JSON with
+//! [`GraphDocument::to_writer`], or with any other `serde` format.
+//!
+//! The conversion follows the OBO to OWL mapping of the OBO 1.4 specification:
+//!
+//! * Term and typedef frames become `CLASS` and `PROPERTY` nodes, labeled
+//!   with their `name`, and with `meta` holding their definition, comments,
+//!   synonyms, xrefs, subsets and property values.
+//! * `is_a` and `relationship` clauses become edges, as well as the
+//!   `inverse_of` clauses of typedef frames.
+//! * `equivalent_to` clauses become equivalent node sets, and the
+//!   `intersection_of` clauses of a term frame become a logical definition.
+//!
+//! Instance frames and the clauses that have no counterpart in the OBO Graphs
+//! model (such as `union_of` or `disjoint_from`) are ignored.
+//!
+//! # Example
+//! ```rust
+//! extern crate fastobo;
+//!
+//! use std::str::FromStr;
+//! use fastobo::ast::OboDoc;
+//! use fastobo::obographs::GraphDocument;
+//!
+//! let doc = OboDoc::from_str(
+//!     "ontology: test\n\n[Term]\nid: TST:002\nname: child\nis_a: TST:001\n"
+//! ).unwrap();
+//!
+//! let graphs = GraphDocument::from(&doc);
+//! let graph = &graphs.graphs[0];
+//! assert_eq!(graph.id.as_ref().unwrap(), "http://purl.obolibrary.org/obo/test.owl");
+//! assert_eq!(graph.nodes[0].lbl.as_ref().unwrap(), "child");
+//! assert_eq!(graph.edges[0].pred, "is_a");
+//! assert_eq!(graph.edges[0].obj, "http://purl.obolibrary.org/obo/TST_001");
+//! ```
+//!
+//! [OBO Graphs]: https://github.com/geneontology/obographs
+//! [`GraphDocument`]: ./struct.GraphDocument.html
+//! [`GraphDocument::to_writer`]: ./struct.GraphDocument.html#method.to_writer
+//! [`OboDoc`]: ../ast/struct.OboDoc.html
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ast::*;
+use crate::error::Error;
+use crate::error::Result;
+
+/// The base IRI of OBO Foundry identifiers.
+const OBO: &str = "http://purl.obolibrary.org/obo/";
+/// The base IRI of the `oboInOwl` vocabulary.
+const OBO_IN_OWL: &str = "http://www.geneontology.org/formats/oboInOwl#";
+
+// --- Data model ------------------------------------------------------------
+
+/// A document containing one or more graphs.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphDocument {
+    pub graphs: Vec<Graph>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
+
+/// A graph, corresponding to a single ontology.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Graph {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+    #[serde(default)]
+    pub edges: Vec<Edge>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equivalent_nodes_sets: Vec<EquivalentNodesSet>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logical_definition_axioms: Vec<LogicalDefinitionAxiom>,
+}
+
+/// The type of a node.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NodeType {
+    Class,
+    Property,
+    Individual,
+}
+
+/// A node, corresponding to an entity of the ontology.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Node {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lbl: Option<String>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ty: Option<NodeType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
+
+/// An edge, linking a subject node to an object node with a predicate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Edge {
+    pub sub: String,
+    pub pred: String,
+    pub obj: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
+
+/// Metadata about a graph, a node or an edge.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<DefinitionPropertyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subsets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub synonyms: Vec<SynonymPropertyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub xrefs: Vec<XrefPropertyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub basic_property_values: Vec<BasicPropertyValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+}
+
+/// A textual definition, with the cross-references supporting it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefinitionPropertyValue {
+    pub val: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub xrefs: Vec<String>,
+}
+
+/// A synonym, with its scope predicate and optional synonym type.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SynonymPropertyValue {
+    pub pred: String,
+    pub val: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub xrefs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synonym_type: Option<String>,
+}
+
+/// A database cross-reference.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XrefPropertyValue {
+    pub val: String,
+}
+
+/// An annotation binding a predicate to a value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BasicPropertyValue {
+    pub pred: String,
+    pub val: String,
+}
+
+/// A set of nodes declared equivalent to each other.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquivalentNodesSet {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub representative_node_id: Option<String>,
+    pub node_ids: Vec<String>,
+}
+
+/// A class defined as the intersection of genus classes and restrictions.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicalDefinitionAxiom {
+    pub defined_class_id: String,
+    #[serde(default)]
+    pub genus_ids: Vec<String>,
+    #[serde(default)]
+    pub restrictions: Vec<ExistentialRestrictionExpression>,
+}
+
+/// An existential restriction on a property, e.g. `part_of some nucleus`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExistentialRestrictionExpression {
+    pub property_id: String,
+    pub filler_id: String,
+}
+
+impl GraphDocument {
+    /// Write the graph document as compact JSON.
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<()> {
+        serde_json::to_writer(writer, self).map_err(|e| Error::from(std::io::Error::from(e)))
+    }
+
+    /// Write the graph document as pretty-printed JSON.
+    pub fn to_writer_pretty<W: Write>(&self, writer: W) -> Result<()> {
+        serde_json::to_writer_pretty(writer, self)
+            .map_err(|e| Error::from(std::io::Error::from(e)))
+    }
+}
+
+impl<'a> From<&'a OboDoc> for GraphDocument {
+    fn from(doc: &'a OboDoc) -> Self {
+        GraphDocument {
+            graphs: vec![GraphBuilder::new(doc).build(doc)],
+            meta: None,
+        }
+    }
+}
+
+// --- Conversion ------------------------------------------------------------
+
+/// Return `Some(meta)` unless `meta` is empty.
+fn non_empty(meta: Meta) -> Option<Meta> {
+    if meta == Meta::default() {
+        None
+    } else {
+        Some(meta)
+    }
+}
+
+/// Get the unescaped text of an identifier, as used in xrefs and annotations.
+fn curie(id: &Ident) -> String {
+    match id {
+        Ident::Prefixed(id) => format!("{}:{}", id.prefix.as_str(), id.local.as_str()),
+        Ident::Unprefixed(id) => id.as_str().to_string(),
+        Ident::Url(url) => url.to_string(),
+    }
+}
+
+/// Get the `oboInOwl` predicate of a synonym scope.
+fn synonym_pred(scope: &SynonymScope) -> String {
+    let pred = match scope {
+        SynonymScope::Exact => "hasExactSynonym",
+        SynonymScope::Broad => "hasBroadSynonym",
+        SynonymScope::Narrow => "hasNarrowSynonym",
+        SynonymScope::Related => "hasRelatedSynonym",
+    };
+    format!("{}{}", OBO_IN_OWL, pred)
+}
+
+/// The state of the conversion of a single document.
+struct GraphBuilder<'a> {
+    /// The ontology declared in the header, used to build unprefixed IRIs.
+    ontology: Option<&'a str>,
+    /// The IRI prefixes declared with `idspace` header clauses.
+    idspaces: HashMap<&'a str, String>,
+    /// The IRIs of typedefs with an unprefixed ID, taken from their xrefs.
+    relations: HashMap<&'a Ident, String>,
+    graph: Graph,
+}
+
+impl<'a> GraphBuilder<'a> {
+    fn new(doc: &'a OboDoc) -> Self {
+        let mut builder = GraphBuilder {
+            ontology: None,
+            idspaces: HashMap::new(),
+            relations: HashMap::new(),
+            graph: Graph::default(),
+        };
+
+        for line in doc.header().iter() {
+            match line.as_ref() {
+                HeaderClause::Ontology(ont) => builder.ontology = Some(ont.as_str()),
+                HeaderClause::Idspace(prefix, url, _) => {
+                    builder.idspaces.insert(prefix.as_str(), url.to_string());
+                }
+                _ => (),
+            }
+        }
+
+        // An unprefixed relation is translated to the IRI of its first
+        // prefixed xref, e.g. `part_of` to `BFO:0000050`.
+        for frame in doc.entities.iter() {
+            if let EntityFrame::Typedef(frame) = frame {
+                let id: &Ident = frame.id().as_ref().as_ref();
+                if let Ident::Unprefixed(_) = id {
+                    let xref = frame.clauses().iter().find_map(|line| match line.as_ref() {
+                        TypedefClause::Xref(xref) => match xref.id {
+                            Ident::Prefixed(_) => Some(&xref.id),
+                            _ => None,
+                        },
+                        _ => None,
+                    });
+                    if let Some(xref) = xref {
+                        let iri = builder.iri(xref);
+                        builder.relations.insert(id, iri);
+                    }
+                }
+            }
+        }
+
+        builder
+    }
+
+    /// Get the IRI of an identifier.
+    fn iri(&self, id: &Ident) -> String {
+        match id {
+            Ident::Url(url) => url.to_string(),
+            Ident::Prefixed(id) => match self.idspaces.get(id.prefix.as_str()) {
+                Some(base) => format!("{}{}", base, id.local.as_str()),
+                None => format!("{}{}_{}", OBO, id.prefix.as_str(), id.local.as_str()),
+            },
+            Ident::Unprefixed(id) => match self.ontology {
+                Some(ont) => format!("{}{}#{}", OBO, ont, id.as_str()),
+                None => format!("{}{}", OBO, id.as_str()),
+            },
+        }
+    }
+
+    /// Get the IRI of a relation identifier.
+    fn relation_iri(&self, id: &RelationIdent) -> String {
+        match self.relations.get(id.as_ref()) {
+            Some(iri) => iri.clone(),
+            None => self.iri(id.as_ref()),
+        }
+    }
+
+    fn build(mut self, doc: &'a OboDoc) -> Graph {
+        let mut meta = Meta::default();
+        if let Some(ont) = self.ontology {
+            self.graph.id = Some(format!("{}{}.owl", OBO, ont));
+        }
+        for line in doc.header().iter() {
+            match line.as_ref() {
+                HeaderClause::DataVersion(v) => meta.version = Some(v.as_str().to_string()),
+                HeaderClause::Remark(remark) => meta.comments.push(remark.as_str().to_string()),
+                HeaderClause::PropertyValue(pv) => self.property_value(&mut meta, pv),
+                _ => (),
+            }
+        }
+        self.graph.meta = non_empty(meta);
+
+        for frame in doc.entities.iter() {
+            match frame {
+                EntityFrame::Term(frame) => self.term_frame(frame),
+                EntityFrame::Typedef(frame) => self.typedef_frame(frame),
+                EntityFrame::Instance(_) => (),
+            }
+        }
+
+        self.graph
+    }
+
+    fn term_frame(&mut self, frame: &'a TermFrame) {
+        let id = self.iri(frame.id().as_ref().as_ref());
+        let mut lbl = None;
+        let mut meta = Meta::default();
+        let mut genus_ids = Vec::new();
+        let mut restrictions = Vec::new();
+
+        for line in frame.clauses() {
+            match line.as_ref() {
+                TermClause::Name(name) => lbl = Some(name.as_str().to_string()),
+                TermClause::Namespace(ns) => {
+                    self.basic(&mut meta, "hasOBONamespace", curie(ns.as_ref()))
+                }
+                TermClause::AltId(alt) => self.basic(&mut meta, "hasAlternativeId", curie(alt)),
+                TermClause::Def(desc, xrefs) => self.definition(&mut meta, desc, xrefs),
+                TermClause::Comment(c) => meta.comments.push(c.as_str().to_string()),
+                TermClause::Subset(subset) => meta.subsets.push(self.iri(subset.as_ref())),
+                TermClause::Synonym(syn) => self.synonym(&mut meta, syn),
+                TermClause::Xref(xref) => self.xref(&mut meta, xref),
+                TermClause::PropertyValue(pv) => self.property_value(&mut meta, pv),
+                TermClause::IsA(cls) => self.edge(&id, "is_a".to_string(), cls.as_ref()),
+                TermClause::IntersectionOf(None, cls) => genus_ids.push(self.iri(cls.as_ref())),
+                TermClause::IntersectionOf(Some(rel), cls) => {
+                    restrictions.push(ExistentialRestrictionExpression {
+                        property_id: self.relation_iri(rel),
+                        filler_id: self.iri(cls.as_ref()),
+                    })
+                }
+                TermClause::EquivalentTo(cls) => self.equivalent(&id, cls.as_ref()),
+                TermClause::Relationship(rel, cls) => {
+                    let pred = self.relation_iri(rel);
+                    self.edge(&id, pred, cls.as_ref());
+                }
+                TermClause::IsObsolete(obsolete) => meta.deprecated = *obsolete,
+                TermClause::ReplacedBy(cls) => {
+                    let val = self.iri(cls.as_ref());
+                    meta.basic_property_values.push(BasicPropertyValue {
+                        pred: format!("{}IAO_0100001", OBO),
+                        val,
+                    });
+                }
+                TermClause::Consider(cls) => self.basic(&mut meta, "consider", curie(cls.as_ref())),
+                TermClause::CreatedBy(by) => self.basic(&mut meta, "created_by", by.as_str()),
+                TermClause::CreationDate(date) => self.basic(&mut meta, "creation_date", date),
+                _ => (),
+            }
+        }
+
+        if !genus_ids.is_empty() || !restrictions.is_empty() {
+            self.graph.logical_definition_axioms.push(LogicalDefinitionAxiom {
+                defined_class_id: id.clone(),
+                genus_ids,
+                restrictions,
+            });
+        }
+
+        self.graph.nodes.push(Node {
+            id,
+            lbl,
+            ty: Some(NodeType::Class),
+            meta: non_empty(meta),
+        });
+    }
+
+    fn typedef_frame(&mut self, frame: &'a TypedefFrame) {
+        let id = self.relation_iri(frame.id().as_ref());
+        let mut lbl = None;
+        let mut meta = Meta::default();
+
+        for line in frame.clauses() {
+            match line.as_ref() {
+                TypedefClause::Name(name) => lbl = Some(name.as_str().to_string()),
+                TypedefClause::Namespace(ns) => {
+                    self.basic(&mut meta, "hasOBONamespace", curie(ns.as_ref()))
+                }
+                TypedefClause::AltId(alt) => {
+                    self.basic(&mut meta, "hasAlternativeId", curie(alt))
+                }
+                TypedefClause::Def(desc, xrefs) => self.definition(&mut meta, desc, xrefs),
+                TypedefClause::Comment(c) => meta.comments.push(c.as_str().to_string()),
+                TypedefClause::Subset(subset) => meta.subsets.push(self.iri(subset.as_ref())),
+                TypedefClause::Synonym(syn) => self.synonym(&mut meta, syn),
+                TypedefClause::Xref(xref) => self.xref(&mut meta, xref),
+                TypedefClause::PropertyValue(pv) => self.property_value(&mut meta, pv),
+                TypedefClause::IsA(rel) => {
+                    let obj = self.relation_iri(rel);
+                    self.push_edge(&id, "subPropertyOf".to_string(), obj);
+                }
+                TypedefClause::InverseOf(rel) => {
+                    let obj = self.relation_iri(rel);
+                    self.push_edge(&id, "inverseOf".to_string(), obj);
+                }
+                TypedefClause::EquivalentTo(rel) => {
+                    let other = self.relation_iri(rel);
+                    self.push_equivalent(&id, other);
+                }
+                TypedefClause::Relationship(rel, obj) => {
+                    let pred = self.relation_iri(rel);
+                    let obj = self.relation_iri(obj);
+                    self.push_edge(&id, pred, obj);
+                }
+                TypedefClause::IsObsolete(obsolete) => meta.deprecated = *obsolete,
+                TypedefClause::ReplacedBy(rel) => {
+                    let val = self.relation_iri(rel);
+                    meta.basic_property_values.push(BasicPropertyValue {
+                        pred: format!("{}IAO_0100001", OBO),
+                        val,
+                    });
+                }
+                TypedefClause::Consider(id) => self.basic(&mut meta, "consider", curie(id)),
+                TypedefClause::CreatedBy(by) => self.basic(&mut meta, "created_by", by.as_str()),
+                TypedefClause::CreationDate(date) => self.basic(&mut meta, "creation_date", date),
+                _ => (),
+            }
+        }
+
+        self.graph.nodes.push(Node {
+            id,
+            lbl,
+            ty: Some(NodeType::Property),
+            meta: non_empty(meta),
+        });
+    }
+
+    /// Add an `oboInOwl` annotation to `meta`.
+    fn basic<V: std::fmt::Display>(&self, meta: &mut Meta, pred: &str, val: V) {
+        meta.basic_property_values.push(BasicPropertyValue {
+            pred: format!("{}{}", OBO_IN_OWL, pred),
+            val: val.to_string(),
+        })
+    }
+
+    fn definition(&self, meta: &mut Meta, desc: &QuotedString, xrefs: &XrefList) {
+        meta.definition = Some(DefinitionPropertyValue {
+            val: desc.as_str().to_string(),
+            xrefs: xrefs.iter().map(|x| curie(&x.id)).collect(),
+        });
+    }
+
+    fn synonym(&self, meta: &mut Meta, syn: &Synonym) {
+        meta.synonyms.push(SynonymPropertyValue {
+            pred: synonym_pred(&syn.scope),
+            val: syn.desc.as_str().to_string(),
+            xrefs: syn.xrefs.iter().map(|x| curie(&x.id)).collect(),
+            synonym_type: syn.ty.as_ref().map(|ty| self.iri(ty.as_ref())),
+        });
+    }
+
+    fn xref(&self, meta: &mut Meta, xref: &Xref) {
+        meta.xrefs.push(XrefPropertyValue {
+            val: curie(&xref.id),
+        });
+    }
+
+    fn property_value(&self, meta: &mut Meta, pv: &PropertyValue) {
+        let (pred, val) = match pv {
+            PropertyValue::Identified(rel, id) => (self.relation_iri(rel), self.iri(id)),
+            PropertyValue::Typed(rel, desc, _) => {
+                (self.relation_iri(rel), desc.as_str().to_string())
+            }
+        };
+        meta.basic_property_values.push(BasicPropertyValue { pred, val });
+    }
+
+    fn edge(&mut self, sub: &str, pred: String, obj: &Ident) {
+        let obj = self.iri(obj);
+        self.push_edge(sub, pred, obj);
+    }
+
+    fn push_edge(&mut self, sub: &str, pred: String, obj: String) {
+        self.graph.edges.push(Edge {
+            sub: sub.to_string(),
+            pred,
+            obj,
+            meta: None,
+        });
+    }
+
+    fn equivalent(&mut self, id: &str, other: &Ident) {
+        let other = self.iri(other);
+        self.push_equivalent(id, other);
+    }
+
+    fn push_equivalent(&mut self, id: &str, other: String) {
+        self.graph.equivalent_nodes_sets.push(EquivalentNodesSet {
+            representative_node_id: None,
+            node_ids: vec![id.to_string(), other],
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+
+    const DOC: &str = "format-version: 1.4
+data-version: releases/2019-06-13
+ontology: tst
+idspace: EX http://example.com/ex#
+
+[Term]
+id: TST:001
+name: cell
+def: \"A cell.\" [GOC:go_curators, PMID:123]
+synonym: \"cellule\" EXACT LANG []
+xref: CL:0000000
+subset: goslim
+property_value: IAO:0000589 \"cell\" xsd:string
+
+[Term]
+id: TST:002
+name: nucleated cell
+is_a: TST:001
+intersection_of: TST:001
+intersection_of: part_of EX:nucleus
+relationship: part_of EX:tissue
+equivalent_to: CL:0000003
+is_obsolete: true
+
+[Typedef]
+id: part_of
+name: part of
+xref: BFO:0000050
+is_transitive: true
+";
+
+    #[test]
+    fn graph() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let graphs = GraphDocument::from(&doc);
+        assert_eq!(graphs.graphs.len(), 1);
+
+        let graph = &graphs.graphs[0];
+        assert_eq!(graph.id.as_ref().unwrap(), "http://purl.obolibrary.org/obo/tst.owl");
+        assert_eq!(
+            graph.meta.as_ref().unwrap().version.as_ref().unwrap(),
+            "releases/2019-06-13"
+        );
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.nodes[2].id, "http://purl.obolibrary.org/obo/BFO_0000050");
+        assert_eq!(graph.nodes[2].ty, Some(NodeType::Property));
+    }
+
+    #[test]
+    fn meta() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let graphs = GraphDocument::from(&doc);
+        let node = &graphs.graphs[0].nodes[0];
+        assert_eq!(node.id, "http://purl.obolibrary.org/obo/TST_001");
+        assert_eq!(node.lbl.as_ref().unwrap(), "cell");
+        assert_eq!(node.ty, Some(NodeType::Class));
+
+        let meta = node.meta.as_ref().unwrap();
+        let def = meta.definition.as_ref().unwrap();
+        assert_eq!(def.val, "A cell.");
+        assert_eq!(def.xrefs, vec!["GOC:go_curators", "PMID:123"]);
+        assert_eq!(meta.synonyms[0].pred, format!("{}hasExactSynonym", OBO_IN_OWL));
+        assert_eq!(meta.synonyms[0].val, "cellule");
+        assert_eq!(
+            meta.synonyms[0].synonym_type.as_ref().unwrap(),
+            "http://purl.obolibrary.org/obo/tst#LANG"
+        );
+        assert_eq!(meta.xrefs[0].val, "CL:0000000");
+        assert_eq!(meta.subsets, vec!["http://purl.obolibrary.org/obo/tst#goslim"]);
+        assert_eq!(meta.basic_property_values[0].pred, "http://purl.obolibrary.org/obo/IAO_0000589");
+        assert_eq!(meta.basic_property_values[0].val, "cell");
+        assert!(!meta.deprecated);
+    }
+
+    #[test]
+    fn edges_and_axioms() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let graph = &GraphDocument::from(&doc).graphs[0];
+        let sub = "http://purl.obolibrary.org/obo/TST_002";
+
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[0].sub, sub);
+        assert_eq!(graph.edges[0].pred, "is_a");
+        assert_eq!(graph.edges[0].obj, "http://purl.obolibrary.org/obo/TST_001");
+        assert_eq!(graph.edges[1].pred, "http://purl.obolibrary.org/obo/BFO_0000050");
+        assert_eq!(graph.edges[1].obj, "http://example.com/ex#tissue");
+
+        let axiom = &graph.logical_definition_axioms[0];
+        assert_eq!(axiom.defined_class_id, sub);
+        assert_eq!(axiom.genus_ids, vec!["http://purl.obolibrary.org/obo/TST_001"]);
+        assert_eq!(axiom.restrictions[0].filler_id, "http://example.com/ex#nucleus");
+
+        let set = &graph.equivalent_nodes_sets[0];
+        assert_eq!(set.node_ids, vec![sub, "http://purl.obolibrary.org/obo/CL_0000003"]);
+        assert!(graph.nodes[1].meta.as_ref().unwrap().deprecated);
+    }
+
+    #[test]
+    fn escaped_identifiers() {
+        let doc = OboDoc::from_str(
+            "[Term]\nid: TST:003\nalt_id: TST:a\\ b\ndef: \"A term.\" [TST:c\\ d]\n\
+             synonym: \"term\" EXACT [TST:e\\ f]\nxref: TST:a\\ b\nconsider: TST:g\\:h\n",
+        )
+        .unwrap();
+        let graph = &GraphDocument::from(&doc).graphs[0];
+        let meta = graph.nodes[0].meta.as_ref().unwrap();
+        assert_eq!(meta.definition.as_ref().unwrap().xrefs, vec!["TST:c d"]);
+        assert_eq!(meta.synonyms[0].xrefs, vec!["TST:e f"]);
+        assert_eq!(meta.xrefs[0].val, "TST:a b");
+        let values = meta.basic_property_values.iter().map(|pv| pv.val.as_str());
+        assert_eq!(values.collect::<Vec<_>>(), vec!["TST:a b", "TST:g:h"]);
+    }
+
+    #[test]
+    fn typedef_replaced_by() {
+        let doc = OboDoc::from_str(
+            "[Typedef]\nid: TST:has_part\nis_obsolete: true\nreplaced_by: TST:part_of\n",
+        )
+        .unwrap();
+        let node = &GraphDocument::from(&doc).graphs[0].nodes[0];
+        let pv = &node.meta.as_ref().unwrap().basic_property_values[0];
+        assert_eq!(pv.pred, "http://purl.obolibrary.org/obo/IAO_0100001");
+        assert_eq!(pv.val, "http://purl.obolibrary.org/obo/TST_part_of");
+    }
+
+    #[test]
+    fn json() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let graphs = GraphDocument::from(&doc);
+
+        let mut json = Vec::new();
+        graphs.to_writer(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.starts_with("{\"graphs\":[{\"id\":\"http://purl.obolibrary.org/obo/tst.owl\""));
+        assert!(json.contains("\"type\":\"CLASS\""));
+        assert!(json.contains("\"basicPropertyValues\":["));
+        assert!(json.contains("\"logicalDefinitionAxioms\":[{\"definedClassId\":"));
+        assert!(!json.contains("\"deprecated\":false"));
+
+        let graphs2: GraphDocument = serde_json::from_str(&json).unwrap();
+        assert_eq!(graphs, graphs2);
+    }
+}
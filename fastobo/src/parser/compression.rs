@@ -0,0 +1,179 @@
+use std::io::BufRead;
+use std::io::Error as IOError;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::Result;
+
+/// A compression format that can be detected when loading an OBO document.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Codec {
+    Gzip,
+    Bzip2,
+    Xz,
+}
+
+impl Codec {
+    /// Detect the compression format from the extension of a path.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "gz" => Some(Codec::Gzip),
+            "bz2" => Some(Codec::Bzip2),
+            "xz" => Some(Codec::Xz),
+            _ => None,
+        }
+    }
+
+    /// Detect the compression format from the first bytes of a stream.
+    pub(crate) fn from_magic(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x1f\x8b") {
+            Some(Codec::Gzip)
+        } else if bytes.starts_with(b"BZh") {
+            Some(Codec::Bzip2)
+        } else if bytes.starts_with(b"\xfd7zXZ\x00") {
+            Some(Codec::Xz)
+        } else {
+            None
+        }
+    }
+
+    /// Detect the compression format of a buffered stream without consuming it.
+    pub(crate) fn from_stream<B>(stream: &mut B) -> Result<Option<Self>>
+    where
+        B: BufRead,
+    {
+        Ok(Self::from_magic(stream.fill_buf()?))
+    }
+
+    /// Get the name of the cargo feature enabling support for the format.
+    fn feature(self) -> &'static str {
+        match self {
+            Codec::Gzip => "gzip",
+            Codec::Bzip2 => "bz2",
+            Codec::Xz => "xz",
+        }
+    }
+
+    /// Wrap a compressed stream into a decoder for the format.
+    ///
+    /// Returns an error of kind `InvalidData` if support for the format
+    /// was not enabled at compile time.
+    pub(crate) fn decoder<'r, R>(self, reader: R) -> Result<Box<dyn Read + 'r>>
+    where
+        R: Read + 'r,
+    {
+        match self {
+            #[cfg(feature = "flate2")]
+            Codec::Gzip => Ok(Box::new(flate2::read::MultiGzDecoder::new(reader))),
+            #[cfg(feature = "bzip2")]
+            Codec::Bzip2 => Ok(Box::new(bzip2::read::BzDecoder::new(reader))),
+            #[cfg(feature = "xz2")]
+            Codec::Xz => Ok(Box::new(xz2::read::XzDecoder::new(reader))),
+            #[allow(unreachable_patterns)]
+            codec => {
+                drop(reader);
+                let msg = format!(
+                    "{:?} compressed stream (enable the `{}` feature to decompress it)",
+                    codec,
+                    codec.feature()
+                );
+                Err(IOError::new(ErrorKind::InvalidData, msg).into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::BufReader;
+    use std::io::Write;
+
+    use super::*;
+    use crate::ast::OboDoc;
+    use crate::error::Error;
+
+    const DOC: &str = "format-version: 1.4\n\n[Term]\nid: TST:001\nname: test\n";
+    const INVALID: &str = "format-version: 1.4\n\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\nis_a: \n";
+
+    #[test]
+    fn from_path() {
+        assert_eq!(Codec::from_path(Path::new("go.obo.gz")), Some(Codec::Gzip));
+        assert_eq!(Codec::from_path(Path::new("go.obo.bz2")), Some(Codec::Bzip2));
+        assert_eq!(Codec::from_path(Path::new("go.obo.xz")), Some(Codec::Xz));
+        assert_eq!(Codec::from_path(Path::new("go.obo")), None);
+    }
+
+    #[test]
+    fn from_magic() {
+        assert_eq!(Codec::from_magic(b"\x1f\x8b\x08\x00"), Some(Codec::Gzip));
+        assert_eq!(Codec::from_magic(b"BZh91AY&SY"), Some(Codec::Bzip2));
+        assert_eq!(Codec::from_magic(b"\xfd7zXZ\x00\x00"), Some(Codec::Xz));
+        assert_eq!(Codec::from_magic(DOC.as_bytes()), None);
+        assert_eq!(Codec::from_magic(b""), None);
+    }
+
+    /// Check a compressed document is decompressed, and errors are located.
+    fn check_roundtrip(extension: &str, compress: fn(&str) -> Vec<u8>) {
+        let data = compress(DOC);
+        let doc = OboDoc::from_stream(&mut BufReader::new(&data[..])).unwrap();
+        assert_eq!(doc, DOC.parse().unwrap());
+
+        let name = format!("fastobo-compression-{}.obo.{}", std::process::id(), extension);
+        let path = std::env::temp_dir().join(name);
+        std::fs::File::create(&path).unwrap().write_all(&compress(INVALID)).unwrap();
+        let err = OboDoc::from_file(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        match err {
+            Error::ParserError { error } => {
+                assert_eq!(error.path(), Some(path.to_string_lossy().as_ref()));
+                match error.line_col {
+                    pest::error::LineColLocation::Pos((l, _)) => assert_eq!(l, 8),
+                    pest::error::LineColLocation::Span((l, _), _) => assert_eq!(l, 8),
+                }
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn gzip() {
+        check_roundtrip("gz", |text| {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), Default::default());
+            encoder.write_all(text.as_bytes()).unwrap();
+            encoder.finish().unwrap()
+        });
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    fn bzip2() {
+        check_roundtrip("bz2", |text| {
+            let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::Default);
+            encoder.write_all(text.as_bytes()).unwrap();
+            encoder.finish().unwrap()
+        });
+    }
+
+    #[cfg(feature = "xz2")]
+    #[test]
+    fn xz() {
+        check_roundtrip("xz", |text| {
+            let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+            encoder.write_all(text.as_bytes()).unwrap();
+            encoder.finish().unwrap()
+        });
+    }
+
+    #[cfg(not(feature = "flate2"))]
+    #[test]
+    fn disabled() {
+        let mut stream = &b"\x1f\x8b\x08\x00"[..];
+        match OboDoc::from_stream(&mut stream).unwrap_err() {
+            Error::IOError { error } => assert_eq!(error.kind(), ErrorKind::InvalidData),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+}
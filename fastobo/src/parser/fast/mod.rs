@@ -0,0 +1,328 @@
+//! Hand-written parser for the most common OBO constructs.
+//!
+//! Building a `pest` token tree for every frame is the dominant cost when
+//! loading large ontologies, although most of their lines only use a few
+//! simple clauses. This module parses those lines directly into the syntax
+//! tree, and hands everything else over to the `pest` parser:
+//!
+//! * term frames are parsed line by line, and each identifier or clause line
+//!   that is not supported (because it uses escapes, qualifiers, or a clause
+//!   with a complex value such as `property_value`) is parsed with `pest`
+//!   alone;
+//! * typedef and instance frames, as well as any frame that fails to parse,
+//!   are parsed entirely by `pest`, which also guarantees that errors are
+//!   exactly the same as without this module.
+//!
+//! The produced syntax tree, including the source locations of each line,
+//! is the same as the one produced by `pest`.
+
+mod scanner;
+
+use pest::Parser;
+
+use self::scanner::Scanner;
+use crate::ast::*;
+use crate::parser::FromPair;
+use crate::parser::OboParser;
+use crate::parser::Rule;
+
+/// Parse the text of a single entity frame.
+///
+/// Returns `None` if the frame should be parsed by `pest` instead.
+pub fn parse_entity_frame(text: &str) -> Option<EntityFrame> {
+    let mut lines = Lines::new(text);
+
+    // Only term frames are handled by this parser.
+    let (_, _, header) = lines.next()?;
+    if !header.starts_with("[Term]") || !is_blank(&header["[Term]".len()..]) {
+        return None;
+    }
+
+    // Parse the frame identifier.
+    let (idx, start, line) = lines.next()?;
+    let id = match term_id_line(line, idx, start) {
+        Some(id) => id,
+        None => pest_id_line(&text[..start + line.len()])?,
+    };
+
+    // Parse the clauses until the first blank line.
+    let mut clauses = Vec::new();
+    while let Some((idx, start, line)) = lines.next() {
+        if is_blank(line) {
+            if lines.all(|(_, _, l)| is_blank(l)) {
+                break;
+            } else {
+                return None;
+            }
+        }
+        let location = SourceLocation::new(start, start + line.len(), idx + 1, 1);
+        let clause = match Scanner::new(line).and_then(|mut s| term_clause_line(&mut s)) {
+            Some(clause) => clause,
+            None => pest_clause_line(line)?,
+        };
+        clauses.push(clause.and_location(location));
+    }
+
+    Some(EntityFrame::from(TermFrame::with_clauses(id, clauses)))
+}
+
+/// Parse the identifier line of a term frame, including its line terminator.
+///
+/// `idx` and `start` give the index and offset of the line in the frame.
+fn term_id_line(line: &str, idx: usize, start: usize) -> Option<Line<ClassIdent>> {
+    let mut s = Scanner::new(line)?;
+    s.ws();
+    if !s.literal("id:") || !s.ws() {
+        return None;
+    }
+    let pos = s.pos();
+    let id = class_id(&mut s)?;
+    let eol = s.eol()?;
+    let column = line[..pos].chars().count() + 1;
+    let location = SourceLocation::new(start + pos, start + line.len(), idx + 1, column);
+    Some(eol.and_inner(id).and_location(location))
+}
+
+/// Parse a term clause line, including its line terminator.
+fn term_clause_line(s: &mut Scanner) -> Option<Line<TermClause>> {
+    s.ws();
+    let tag = s.tag()?;
+    if !s.ws() {
+        return None;
+    }
+
+    let clause = match tag {
+        "is_anonymous" => TermClause::IsAnonymous(s.boolean()?),
+        "name" => TermClause::Name(s.unquoted()?),
+        "namespace" => TermClause::Namespace(NamespaceIdent::from(s.ident()?)),
+        "alt_id" => TermClause::AltId(s.ident()?),
+        "def" => {
+            let desc = s.quoted()?;
+            if !s.ws() {
+                return None;
+            }
+            TermClause::Def(desc, s.xref_list()?)
+        }
+        "comment" => TermClause::Comment(s.unquoted()?),
+        "subset" => TermClause::Subset(SubsetIdent::from(s.ident()?)),
+        "synonym" => TermClause::Synonym(s.synonym()?),
+        "xref" => TermClause::Xref(s.xref()?),
+        "builtin" => TermClause::Builtin(s.boolean()?),
+        "is_a" => TermClause::IsA(class_id(s)?),
+        "intersection_of" => {
+            // Try `RelationId ws ClassId` first, then `ClassId` alone.
+            let braced = s.next_is(b'{');
+            let first = s.ident()?;
+            if s.ws() {
+                let start = s.pos();
+                match s.ident_not_starting_with(b'{') {
+                    Some(id) => {
+                        let rel = RelationIdent::from(first);
+                        let clause = TermClause::IntersectionOf(Some(rel), ClassIdent::from(id));
+                        return Some(s.eol()?.and_inner(clause));
+                    }
+                    None if s.pos() != start => return None,
+                    None => (),
+                }
+            }
+            if braced {
+                return None;
+            }
+            TermClause::IntersectionOf(None, ClassIdent::from(first))
+        }
+        "union_of" => TermClause::UnionOf(class_id(s)?),
+        "equivalent_to" => TermClause::EquivalentTo(class_id(s)?),
+        "disjoint_from" => TermClause::DisjointFrom(class_id(s)?),
+        "relationship" => {
+            let rel = RelationIdent::from(s.ident()?);
+            if !s.ws() {
+                return None;
+            }
+            TermClause::Relationship(rel, class_id(s)?)
+        }
+        "is_obsolete" => TermClause::IsObsolete(s.boolean()?),
+        "replaced_by" => TermClause::ReplacedBy(class_id(s)?),
+        "consider" => TermClause::Consider(class_id(s)?),
+        "created_by" => TermClause::CreatedBy(s.unquoted()?),
+        _ => return None,
+    };
+
+    Some(s.eol()?.and_inner(clause))
+}
+
+/// Parse a class identifier.
+fn class_id(s: &mut Scanner) -> Option<ClassIdent> {
+    s.ident_not_starting_with(b'{').map(ClassIdent::from)
+}
+
+/// Parse a term clause line with `pest`, checking the whole line is consumed.
+fn pest_clause_line(line: &str) -> Option<Line<TermClause>> {
+    let pair = OboParser::parse(Rule::TermClauseLine, line).ok()?.next()?;
+    if pair.as_span().end() != line.len() {
+        return None;
+    }
+    unsafe { Line::<TermClause>::from_pair_unchecked(pair).ok() }
+}
+
+/// Parse the identifier line of a term frame with `pest`.
+///
+/// `text` must only contain the frame header and identifier lines, so that
+/// the location of the identifier is the same as in the whole frame.
+fn pest_id_line(text: &str) -> Option<Line<ClassIdent>> {
+    let pair = OboParser::parse(Rule::EntitySingle, text).ok()?.next()?;
+    match unsafe { EntityFrame::from_pair_unchecked(pair).ok()? } {
+        EntityFrame::Term(frame) => Some(frame.id().clone()),
+        _ => None,
+    }
+}
+
+/// Check whether a line only contains whitespace and a line terminator.
+fn is_blank(line: &str) -> bool {
+    line.ends_with('\n') && line.bytes().all(|b| b == b' ' || b == b'\t' || b == b'\n')
+}
+
+/// An iterator over the lines of a text, with their index and offset.
+struct Lines<'i> {
+    text: &'i str,
+    idx: usize,
+    offset: usize,
+}
+
+impl<'i> Lines<'i> {
+    fn new(text: &'i str) -> Self {
+        Self {
+            text,
+            idx: 0,
+            offset: 0,
+        }
+    }
+}
+
+impl<'i> Iterator for Lines<'i> {
+    type Item = (usize, usize, &'i str);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset == self.text.len() {
+            return None;
+        }
+        let rest = &self.text[self.offset..];
+        let len = rest.find('\n').map(|i| i + 1).unwrap_or_else(|| rest.len());
+        let item = (self.idx, self.offset, &rest[..len]);
+        self.idx += 1;
+        self.offset += len;
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Parse a frame with `pest` alone.
+    fn pest_entity_frame(text: &str) -> EntityFrame {
+        let pair = OboParser::parse(Rule::EntitySingle, text).unwrap().next().unwrap();
+        unsafe { EntityFrame::from_pair_unchecked(pair).unwrap() }
+    }
+
+    /// Check the fast parser gives the same frame as `pest`, if any.
+    ///
+    /// `Debug` outputs are compared, since they also include the source
+    /// locations and the canonical flags of identifiers.
+    fn assert_same(text: &str) -> bool {
+        match parse_entity_frame(text) {
+            Some(frame) => {
+                let expected = pest_entity_frame(text);
+                assert_eq!(format!("{:?}", frame), format!("{:?}", expected));
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[test]
+    fn ms() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/ms.obo");
+        let text = std::fs::read_to_string(path).unwrap();
+        let start = text.find("\n[").unwrap() + 1;
+
+        let mut chunks = Vec::new();
+        let mut chunk = String::new();
+        for line in text[start..].split_inclusive('\n') {
+            if line.trim_start().starts_with('[') && !chunk.is_empty() {
+                chunks.push(std::mem::take(&mut chunk));
+            }
+            chunk.push_str(line);
+        }
+        chunks.push(chunk);
+
+        // Every term frame is parsed without falling back to `pest` entirely.
+        for chunk in chunks.iter().filter(|c| c.starts_with("[Term]")) {
+            assert!(assert_same(chunk), "frame not parsed:\n{}", chunk);
+        }
+    }
+
+    #[test]
+    fn conformance() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/conformance/valid");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let text = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            let starts = crate::parser::frame_starts(&text);
+            for w in starts.windows(2) {
+                let chunk = &text[w[0].1..w[1].1];
+                if chunk.starts_with("[Term]") {
+                    assert!(assert_same(chunk), "frame not parsed:\n{}", chunk);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fallback_lines() {
+        assert!(assert_same(
+            "[Term]\nid: TST:001\nname: with \\\"escapes\\\"\n\
+             is_a: TST:002 {source=\"TST:003\"} ! qualified\n\
+             property_value: creation_date \"2019\" xsd:string\n"
+        ));
+    }
+
+    #[test]
+    fn intersection_of() {
+        assert!(assert_same(
+            "[Term]\nid: TST:001\nintersection_of: TST:002\nintersection_of: part_of TST:003\n"
+        ));
+    }
+
+    #[test]
+    fn identifiers() {
+        assert!(assert_same(
+            "[Term]\nid: TST:001\nis_a: TST:002! no space\nis_a: TST:0a3\n\
+             xref: https://example.com/x\nxref: http://example.com/x \"desc\"\n\
+             consider: under_score\n"
+        ));
+        assert!(assert_same("[Term]\nid: TST:\nis_a: TST:001\n"));
+        assert!(assert_same("[Term]\nid: TST:escaped\\ id ! comment\nis_a: TST:001\n"));
+    }
+
+    #[test]
+    fn comments() {
+        assert!(assert_same(
+            "[Term]\nid: TST:001\nname: spaced \t ! comment\ncomment: unspaced! comment\n\
+             created_by: no comment \n"
+        ));
+    }
+
+    #[test]
+    fn xref_lists() {
+        assert!(assert_same(
+            "[Term]\nid: TST:001\ndef: \"a\" [, TST:002]\ndef: \"b\" [TST:002 \"x\", TST:003]\n\
+             synonym: \"c\" EXACT []\nsynonym: \"d\" RELATED TYPE [TST:004]\n"
+        ));
+    }
+
+    #[test]
+    fn unsupported_frames() {
+        assert!(parse_entity_frame("[Typedef]\nid: part_of\n").is_none());
+        assert!(parse_entity_frame("[Term]\nid: TST:001\n\nname: detached\n").is_none());
+        assert!(parse_entity_frame("[Term]\nid: TST:001\nis_a: \n").is_none());
+    }
+}
@@ -0,0 +1,285 @@
+use std::str::FromStr;
+
+use url::Url;
+
+use crate::ast::*;
+
+/// A cursor over the text of a single clause line.
+///
+/// The scanner only handles the subset of the syntax that does not require
+/// escaping, qualifiers or non-`\n` line terminators: lines containing any
+/// of those are rejected by [`Scanner::new`] and left to the `pest` parser.
+pub struct Scanner<'i> {
+    line: &'i str,
+    pos: usize,
+}
+
+impl<'i> Scanner<'i> {
+    /// Create a new scanner over a line, including its line terminator.
+    ///
+    /// Returns `None` if the line contains escaped characters, or
+    /// characters that the scanner does not handle.
+    pub fn new(line: &'i str) -> Option<Self> {
+        if line.bytes().any(|b| b == b'\\' || b == b'\r' || b == b'\x0c') {
+            None
+        } else {
+            Some(Self { line, pos: 0 })
+        }
+    }
+
+    /// Get the current position of the scanner, in bytes.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Get the text of the line that was not consumed yet.
+    fn rest(&self) -> &'i str {
+        &self.line[self.pos..]
+    }
+
+    /// Get the next byte of the line, if any.
+    fn peek(&self) -> Option<u8> {
+        self.line.as_bytes().get(self.pos).cloned()
+    }
+
+    /// Check whether the next byte of the line is `b`.
+    pub fn next_is(&self, b: u8) -> bool {
+        self.peek() == Some(b)
+    }
+
+    /// Consume `literal` if the line continues with it.
+    pub fn literal(&mut self, literal: &str) -> bool {
+        if self.rest().starts_with(literal) {
+            self.pos += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume whitespace characters, returning `true` if any were found.
+    pub fn ws(&mut self) -> bool {
+        let start = self.pos;
+        while let Some(b' ') | Some(b'\t') = self.peek() {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    /// Consume the tag of a clause, up to and including its colon.
+    pub fn tag(&mut self) -> Option<&'i str> {
+        let end = self.rest().find(':')?;
+        let tag = &self.rest()[..end];
+        self.pos += end + 1;
+        Some(tag)
+    }
+
+    /// Consume a boolean value.
+    pub fn boolean(&mut self) -> Option<bool> {
+        if self.literal("true") {
+            Some(true)
+        } else if self.literal("false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Consume an unquoted string, up to a comment or the end of the line.
+    ///
+    /// The whitespace before a comment is not part of the string.
+    pub fn unquoted(&mut self) -> Option<UnquotedString> {
+        let mut end = self.rest().find(&['!', '\n'][..])?;
+        if self.rest().as_bytes()[end] == b'!' {
+            end = self.rest()[..end].trim_end_matches(&[' ', '\t'][..]).len();
+        }
+        if end == 0 {
+            return None;
+        }
+        let s = &self.rest()[..end];
+        self.pos += end;
+        Some(UnquotedString::new(s))
+    }
+
+    /// Consume a quoted string.
+    pub fn quoted(&mut self) -> Option<QuotedString> {
+        if !self.next_is(b'"') {
+            return None;
+        }
+        let end = 1 + self.rest()[1..].find(&['"', '\n'][..])?;
+        if self.rest().as_bytes()[end] != b'"' {
+            return None;
+        }
+        let s = &self.rest()[1..end];
+        self.pos += end + 1;
+        Some(QuotedString::new(s))
+    }
+
+    /// Consume the characters accepted by `f` that are not whitespace.
+    fn word<F>(&mut self, f: F) -> Option<&'i str>
+    where
+        F: Fn(u8) -> bool,
+    {
+        let len = self
+            .rest()
+            .bytes()
+            .take_while(|&b| b != b' ' && b != b'\t' && b != b'\n' && b != b'!' && f(b))
+            .count();
+        if len == 0 {
+            return None;
+        }
+        let word = &self.rest()[..len];
+        self.pos += len;
+        Some(word)
+    }
+
+    /// Consume an identifier.
+    pub fn ident(&mut self) -> Option<Ident> {
+        let word = self.word(|_| true)?;
+        ident(word)
+    }
+
+    /// Consume an identifier that does not start with `c`.
+    pub fn ident_not_starting_with(&mut self, c: u8) -> Option<Ident> {
+        if self.next_is(c) {
+            None
+        } else {
+            self.ident()
+        }
+    }
+
+    /// Consume a cross-reference, without surrounding brackets.
+    pub fn xref(&mut self) -> Option<Xref> {
+        let id = self.ident()?;
+        let start = self.pos;
+        if self.ws() {
+            if let Some(desc) = self.quoted() {
+                return Some(Xref::with_desc(id, desc));
+            }
+        }
+        self.pos = start;
+        Some(Xref::new(id))
+    }
+
+    /// Consume a bracketed list of cross-references.
+    pub fn xref_list(&mut self) -> Option<XrefList> {
+        if !self.literal("[") {
+            return None;
+        }
+
+        // The first item is optional, but must be valid if present.
+        let mut xrefs = Vec::new();
+        let start = self.pos;
+        match self.xref_list_item() {
+            Some(xref) => {
+                xrefs.push(xref);
+                self.ws();
+            }
+            None if self.pos != start => return None,
+            None => (),
+        }
+
+        while self.literal(",") {
+            if !self.ws() {
+                return None;
+            }
+            xrefs.push(self.xref_list_item()?);
+        }
+
+        self.ws();
+        if self.literal("]") {
+            Some(XrefList::new(xrefs))
+        } else {
+            None
+        }
+    }
+
+    /// Consume an item of a cross-reference list.
+    fn xref_list_item(&mut self) -> Option<Xref> {
+        let word = self.word(|b| b != b',' && b != b']')?;
+        let id = ident(word)?;
+        let start = self.pos;
+        if self.ws() {
+            if let Some(desc) = self.quoted() {
+                return Some(Xref::with_desc(id, desc));
+            }
+        }
+        self.pos = start;
+        Some(Xref::new(id))
+    }
+
+    /// Consume a synonym.
+    pub fn synonym(&mut self) -> Option<Synonym> {
+        let desc = self.quoted()?;
+        if !self.ws() {
+            return None;
+        }
+        let scope = if self.literal("EXACT") {
+            SynonymScope::Exact
+        } else if self.literal("BROAD") {
+            SynonymScope::Broad
+        } else if self.literal("NARROW") {
+            SynonymScope::Narrow
+        } else if self.literal("RELATED") {
+            SynonymScope::Related
+        } else {
+            return None;
+        };
+        if !self.ws() {
+            return None;
+        }
+        let ty = match self.ident_not_starting_with(b'[') {
+            Some(id) if self.ws() => Some(SynonymTypeIdent::from(id)),
+            Some(_) => return None,
+            None => None,
+        };
+        let xrefs = self.xref_list()?;
+        Some(Synonym {
+            desc,
+            scope,
+            ty,
+            xrefs,
+        })
+    }
+
+    /// Consume the end of the line, with an optional comment.
+    ///
+    /// Returns `None` if the line contains qualifiers, or if there is some
+    /// unexpected content before the end of the line.
+    pub fn eol(&mut self) -> Option<Eol> {
+        self.ws();
+        let eol = if self.next_is(b'!') {
+            let end = self.rest().find('\n')?;
+            let comment = Comment::new(self.rest()[1..end].trim().to_string());
+            self.pos += end;
+            Eol::with_comment(comment)
+        } else {
+            Eol::new()
+        };
+        if self.rest() == "\n" {
+            self.pos += 1;
+            Some(eol)
+        } else {
+            None
+        }
+    }
+}
+
+/// Build an identifier from a word made of non-whitespace characters.
+///
+/// Canonical flags are derived by the component constructors, like for
+/// identifiers parsed with `pest`.
+fn ident(word: &str) -> Option<Ident> {
+    if word.starts_with("http:") || word.starts_with("https:") {
+        return Url::from_str(word).ok().map(Ident::Url);
+    }
+
+    match word.find(':') {
+        Some(i) => {
+            let prefix = IdentPrefix::new(&word[..i]);
+            let local = IdentLocal::new(&word[i + 1..]);
+            Some(Ident::from(PrefixedIdent::new(prefix, local)))
+        }
+        None => Some(Ident::from(UnprefixedIdent::new(word))),
+    }
+}
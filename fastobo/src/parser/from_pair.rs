@@ -1,10 +1,9 @@
-use pest::error::ErrorVariant;
-use pest::error::Error as PestError;
 use pest::iterators::Pair;
 use url::Url;
 
 use std::str::FromStr;
 
+use crate::ast::SourceLocation;
 use crate::error::Error;
 use crate::parser::Rule;
 
@@ -47,15 +46,10 @@ impl<'i> FromPair<'i> for bool {
 impl<'i> FromPair<'i> for Url {
     const RULE: Rule = Rule::Iri;
     unsafe fn from_pair_unchecked(pair: Pair<'i, Rule>) -> Result<Self, Error> {
-        Url::parse(pair.as_str()).map_err(|e| {
-            Error::from(
-                PestError::new_from_span(
-                    ErrorVariant::CustomError {
-                        message: format!("could not parse URL: {}", e)
-                    },
-                    pair.as_span(),
-                )
-            )
+        Url::parse(pair.as_str()).map_err(|error| Error::InvalidIri {
+            value: pair.as_str().to_string(),
+            location: SourceLocation::from(&pair),
+            error,
         })
     }
 }
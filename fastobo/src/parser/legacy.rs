@@ -0,0 +1,187 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+/// The synonym scopes defined in OBO 1.4.
+const SCOPES: [&str; 4] = ["EXACT", "BROAD", "NARROW", "RELATED"];
+
+/// A clause line written with the OBO 1.2 syntax and upgraded to OBO 1.4.
+///
+/// Rewrites are recorded by a [`FrameReader`] in legacy mode, so that the
+/// changes made to a document can be reviewed when migrating it.
+///
+/// [`FrameReader`]: ./struct.FrameReader.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Rewrite {
+    line: usize,
+    original: String,
+    upgraded: String,
+}
+
+impl Rewrite {
+    /// Create a new rewrite of the given line.
+    pub(crate) fn new<S1, S2>(line: usize, original: S1, upgraded: S2) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        Self {
+            line,
+            original: original.into(),
+            upgraded: upgraded.into(),
+        }
+    }
+
+    /// Get the line number of the rewritten clause, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the original text of the clause, without its line terminator.
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// Get the upgraded text of the clause, without its line terminator.
+    pub fn upgraded(&self) -> &str {
+        &self.upgraded
+    }
+}
+
+impl Display for Rewrite {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "line {}: `{}` -> `{}`", self.line, self.original, self.upgraded)
+    }
+}
+
+/// Upgrade a clause line written with the OBO 1.2 syntax.
+///
+/// Returns `None` if the line is already valid OBO 1.4, or if it does not
+/// use any of the supported legacy constructs:
+/// * `exact_synonym`, `narrow_synonym`, `broad_synonym` and `related_synonym`
+///   clauses become `synonym` clauses with the matching scope.
+/// * `synonym` clauses without a scope get the `RELATED` scope.
+/// * `xref_analog` and `xref_unknown` clauses become `xref` clauses.
+/// * `use_term` clauses become `consider` clauses.
+/// * `def` and `synonym` clauses without a cross-reference list get an
+///   empty one.
+pub(crate) fn upgrade_line(line: &str) -> Option<String> {
+    let body = line.trim_start();
+    let indent = &line[..line.len() - body.len()];
+    let colon = body.find(':')?;
+    let (tag, value) = (&body[..colon], &body[colon + 1..]);
+
+    let upgraded = match tag {
+        "exact_synonym" => upgrade_quoted("synonym", value, Some("EXACT"))?,
+        "narrow_synonym" => upgrade_quoted("synonym", value, Some("NARROW"))?,
+        "broad_synonym" => upgrade_quoted("synonym", value, Some("BROAD"))?,
+        "related_synonym" => upgrade_quoted("synonym", value, Some("RELATED"))?,
+        "synonym" => upgrade_quoted("synonym", value, None)?,
+        "def" => upgrade_quoted("def", value, None)?,
+        "xref_analog" | "xref_unknown" => format!("xref:{}", value),
+        "use_term" => format!("consider:{}", value),
+        _ => return None,
+    };
+
+    if upgraded.as_str() == body {
+        None
+    } else {
+        Some(format!("{}{}", indent, upgraded))
+    }
+}
+
+/// Upgrade the value of a `def` or `synonym` clause starting with a quoted string.
+///
+/// `scope` is the scope implied by a legacy synonym tag; for `synonym`
+/// clauses the scope is kept if present and defaults to `RELATED` otherwise.
+fn upgrade_quoted(tag: &str, value: &str, scope: Option<&str>) -> Option<String> {
+    let start = value.find('"')?;
+    if !value[..start].trim().is_empty() {
+        return None;
+    }
+
+    // Find the end of the quoted string, skipping escaped characters.
+    let mut escaped = false;
+    let end = value[start + 1..].char_indices().find_map(|(i, c)| match c {
+        _ if escaped => {
+            escaped = false;
+            None
+        }
+        '\\' => {
+            escaped = true;
+            None
+        }
+        '"' => Some(start + i + 2),
+        _ => None,
+    })?;
+
+    // Split the rest of the line into the clause value and its trailer
+    // (qualifiers, comment and line terminator).
+    let rest = &value[end..];
+    let tail = rest.find(&['!', '{', '\r', '\n'][..]).unwrap_or(rest.len());
+    let body = rest[..tail].trim_end();
+    let trailer = &rest[body.len()..];
+
+    let mut upgraded = format!("{}:{}", tag, &value[..end]);
+    if tag == "synonym" {
+        let first = body.split_whitespace().next().unwrap_or("");
+        match scope {
+            Some(scope) => upgraded.push_str(&format!(" {}", scope)),
+            None if !SCOPES.contains(&first) => upgraded.push_str(" RELATED"),
+            None => (),
+        }
+    }
+    upgraded.push_str(body);
+    if !body.contains('[') {
+        upgraded.push_str(" []");
+    }
+    upgraded.push_str(trailer);
+    Some(upgraded)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn synonyms() {
+        assert_eq!(
+            upgrade_line("exact_synonym: \"cell\" [GOC:go]\n").unwrap(),
+            "synonym: \"cell\" EXACT [GOC:go]\n"
+        );
+        assert_eq!(
+            upgrade_line("narrow_synonym: \"a \\\"b\\\" c\" []\n").unwrap(),
+            "synonym: \"a \\\"b\\\" c\" NARROW []\n"
+        );
+        assert_eq!(
+            upgrade_line("related_synonym: \"cell\" ! legacy\n").unwrap(),
+            "synonym: \"cell\" RELATED [] ! legacy\n"
+        );
+        assert_eq!(
+            upgrade_line("synonym: \"cell\" UK_SPELLING []\n").unwrap(),
+            "synonym: \"cell\" RELATED UK_SPELLING []\n"
+        );
+        assert_eq!(upgrade_line("synonym: \"cell\" BROAD [GOC:go]\n"), None);
+    }
+
+    #[test]
+    fn tags() {
+        assert_eq!(
+            upgrade_line("xref_analog: CAS:7732-18-5\n").unwrap(),
+            "xref: CAS:7732-18-5\n"
+        );
+        assert_eq!(upgrade_line("xref_unknown: Wikipedia:Water").unwrap(), "xref: Wikipedia:Water");
+        assert_eq!(upgrade_line("use_term: GO:0005575\n").unwrap(), "consider: GO:0005575\n");
+        assert_eq!(upgrade_line("is_a: GO:0005575\n"), None);
+    }
+
+    #[test]
+    fn definitions() {
+        assert_eq!(
+            upgrade_line("def: \"A cell.\"\n").unwrap(),
+            "def: \"A cell.\" []\n"
+        );
+        assert_eq!(upgrade_line("def: \"A cell.\" [GOC:go]\n"), None);
+    }
+}
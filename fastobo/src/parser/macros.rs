@@ -18,7 +18,6 @@ macro_rules! impl_fromstr {
             fn from_str(s: &str) -> $crate::error::Result<Self> {
                 use $crate::error::Error;
                 use $crate::parser::OboParser;
-                use $crate::pest::error::ErrorVariant;
                 use $crate::pest::Parser;
                 use $crate::pest::Position;
 
@@ -31,10 +30,10 @@ macro_rules! impl_fromstr {
                         .as_span()
                         .end_pos()
                         .span(&Position::new(s, s.len()).unwrap());
-                    let variant = ErrorVariant::CustomError {
-                        message: "remaining input".to_string(),
-                    };
-                    Err($crate::pest::error::Error::new_from_span(variant, span).into())
+                    Err(Error::RemainingInput {
+                        value: span.as_str().to_string(),
+                        location: $crate::ast::SourceLocation::from_span(span),
+                    })
                 } else {
                     unsafe { <Self as FromPair>::from_pair_unchecked(pair) }
                 }
@@ -52,7 +51,6 @@ macro_rules! impl_fromslice {
             fn from_slice(s: &$life str) -> $crate::error::Result<Self> {
                 use $crate::error::Error;
                 use $crate::parser::OboParser;
-                use $crate::pest::error::ErrorVariant;
                 use $crate::pest::Parser;
                 use $crate::pest::Position;
 
@@ -65,10 +63,10 @@ macro_rules! impl_fromslice {
                         .as_span()
                         .end_pos()
                         .span(&Position::new(s, s.len()).unwrap());
-                    let variant = ErrorVariant::CustomError {
-                        message: "remaining input".to_string(),
-                    };
-                    Err($crate::pest::error::Error::new_from_span(variant, span).into())
+                    Err(Error::RemainingInput {
+                        value: span.as_str().to_string(),
+                        location: $crate::ast::SourceLocation::from_span(span),
+                    })
                 } else {
                     unsafe { <Self as FromPair>::from_pair_unchecked(pair) }
                 }
@@ -0,0 +1,328 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use memmap::Mmap;
+use pest::Parser;
+
+use crate::ast::check_unique_clause;
+use crate::ast::check_unique_clause_ref;
+use crate::ast::EntityFrameRef;
+use crate::ast::HeaderClauseRef;
+use crate::ast::HeaderFrame;
+use crate::ast::HeaderFrameRef;
+use crate::ast::Line;
+use crate::ast::OboDoc;
+use crate::ast::OboDocRef;
+use crate::ast::SourceLocation;
+use crate::error::Error;
+use crate::error::Result;
+use crate::parser::frame_starts;
+use crate::parser::parse_frame;
+use crate::parser::parse_header_clause;
+use crate::parser::relocate;
+use crate::parser::utf8_error;
+use crate::parser::FromPair;
+use crate::parser::OboParser;
+use crate::parser::Rule;
+
+/// An OBO file mapped in memory.
+///
+/// The file is parsed directly from the mapped pages, without copying its
+/// lines into a buffer first: the kernel loads the pages of the file when
+/// they are accessed, so only the syntax tree is allocated on the heap. The
+/// text can also be parsed into an [`OboDocRef`] that borrows its strings
+/// and identifiers from the mapping.
+///
+/// The content of the mapping may change if the file is modified by another
+/// process while it is mapped, which will cause the parser to see invalid
+/// data. The file must not be modified until the `MappedFile` is dropped.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use fastobo::parser::MappedFile;
+/// let file = MappedFile::open("../tests/data/ms.obo").unwrap();
+///
+/// let doc = file.parse_ref().unwrap();
+/// assert_eq!(doc.entities.len(), file.parse().unwrap().entities.len());
+/// ```
+///
+/// [`OboDocRef`]: ../ast/struct.OboDocRef.html
+pub struct MappedFile {
+    mmap: Option<Mmap>,
+    path: Arc<Path>,
+}
+
+impl MappedFile {
+    /// Map the file located at the given path in memory.
+    pub fn open<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path.as_ref())?;
+        // Empty files cannot be mapped, but are valid (empty) OBO documents.
+        let mmap = if file.metadata()?.len() == 0 {
+            None
+        } else {
+            Some(unsafe { Mmap::map(&file)? })
+        };
+        Ok(Self {
+            mmap,
+            path: Arc::from(path.as_ref()),
+        })
+    }
+
+    /// Get the path of the mapped file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the content of the mapped file as raw bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.mmap.as_ref().map(|m| &m[..]).unwrap_or(&[])
+    }
+
+    /// Get the content of the mapped file as a string slice.
+    ///
+    /// Returns a parser error located at the first invalid byte if the file
+    /// is not valid UTF-8.
+    pub fn as_str(&self) -> Result<&str> {
+        let bytes = self.as_bytes();
+        std::str::from_utf8(bytes).map_err(|e| {
+            let valid = e.valid_up_to();
+            let start = bytes[..valid]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map(|i| i + 1)
+                .unwrap_or(0);
+            let end = bytes[valid..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|i| valid + i + 1)
+                .unwrap_or_else(|| bytes.len());
+            let line_offset = bytes[..start].iter().filter(|&&b| b == b'\n').count();
+            utf8_error(&bytes[start..end], valid - start, line_offset, start)
+        })
+    }
+
+    /// Parse the mapped file into an owned OBO document.
+    ///
+    /// Frames are parsed one at a time, exactly like with a `FrameReader`,
+    /// and the path of the file is recorded in the location of each line.
+    pub fn parse(&self) -> Result<OboDoc> {
+        // Skip the byte order mark, but keep counting it in the offsets.
+        let text = self.as_str()?;
+        let bom = if text.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+        let text = &text[bom..];
+        let starts = frame_starts(text);
+
+        let mut clauses = Vec::new();
+        let mut offset = 0;
+        for (line_offset, line) in text[..starts[0].1].split_terminator('\n').enumerate() {
+            let len = (line.len() + 1).min(starts[0].1 - offset);
+            if !line.trim().is_empty() {
+                let line = &text[offset..offset + len];
+                let mut clause = parse_header_clause(line, line_offset, bom + offset)?;
+                if let Some(location) = clause.location_mut() {
+                    location.path = Some(self.path.clone());
+                }
+                check_unique_clause(&clauses, &clause)?;
+                clauses.push(clause);
+            }
+            offset += len;
+        }
+
+        let entities = starts
+            .windows(2)
+            .map(|w| {
+                let ((line_offset, start), (_, end)) = (w[0], w[1]);
+                let mut frame = parse_frame(&text[start..end], line_offset, bom + start)?;
+                relocate(&mut frame, |loc| loc.path = Some(self.path.clone()));
+                Ok(frame)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(OboDoc::with_entities(HeaderFrame::new(clauses), entities))
+    }
+
+    /// Parse the mapped file into a borrowed OBO document.
+    ///
+    /// The strings and identifiers of the document borrow from the mapping
+    /// whenever possible, so the document cannot outlive the `MappedFile`.
+    /// Frames are parsed one at a time, like with [`parse`](#method.parse).
+    pub fn parse_ref(&self) -> Result<OboDocRef<'_>> {
+        let text = self.as_str()?;
+        let bom = if text.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+        let text = &text[bom..];
+        let starts = frame_starts(text);
+
+        let mut clauses = Vec::new();
+        let mut offset = 0;
+        for (line_offset, line) in text[..starts[0].1].split_terminator('\n').enumerate() {
+            let len = (line.len() + 1).min(starts[0].1 - offset);
+            if !line.trim().is_empty() {
+                let line = &text[offset..offset + len];
+                let mut clause = parse_header_clause_ref(line, line_offset, bom + offset)?;
+                if let Some(location) = clause.location_mut() {
+                    location.path = Some(self.path.clone());
+                }
+                check_unique_clause_ref(&clauses, &clause)?;
+                clauses.push(clause);
+            }
+            offset += len;
+        }
+
+        let entities = starts
+            .windows(2)
+            .map(|w| {
+                let ((line_offset, start), (_, end)) = (w[0], w[1]);
+                let mut frame = parse_frame_ref(&text[start..end], line_offset, bom + start)?;
+                relocate_ref(&mut frame, |loc| loc.path = Some(self.path.clone()));
+                Ok(frame)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(OboDocRef::with_entities(HeaderFrameRef::new(clauses), entities))
+    }
+}
+
+/// Parse a borrowed header clause line, reporting errors at their position in the file.
+fn parse_header_clause_ref(
+    line: &str,
+    line_offset: usize,
+    offset: usize,
+) -> Result<Line<HeaderClauseRef<'_>>> {
+    let mut clause = unsafe {
+        OboParser::parse(Rule::HeaderClauseLine, line)
+            .map_err(Error::from)
+            .and_then(|mut p| Line::<HeaderClauseRef>::from_pair_unchecked(p.next().unwrap()))
+            .map_err(|e| e.with_offsets(line_offset, offset))?
+    };
+    if let Some(location) = clause.location_mut() {
+        location.shift(line_offset, offset);
+    }
+    Ok(clause)
+}
+
+/// Parse the text of a borrowed entity frame, reporting errors at their position in the file.
+fn parse_frame_ref(text: &str, line_offset: usize, offset: usize) -> Result<EntityFrameRef<'_>> {
+    let mut frame = unsafe {
+        OboParser::parse(Rule::EntitySingle, text)
+            .map_err(Error::from)
+            .and_then(|mut p| EntityFrameRef::from_pair_unchecked(p.next().unwrap()))
+            .map_err(|e| e.with_offsets(line_offset, offset))?
+    };
+    relocate_ref(&mut frame, |loc| loc.shift(line_offset, offset));
+    Ok(frame)
+}
+
+/// Apply `f` to the location of every line of a borrowed entity frame.
+fn relocate_ref<F>(frame: &mut EntityFrameRef, mut f: F)
+where
+    F: FnMut(&mut SourceLocation),
+{
+    macro_rules! relocate_frame {
+        ($frame:ident) => {{
+            if let Some(location) = $frame.id_mut().location_mut() {
+                f(location);
+            }
+            for line in $frame.iter_mut() {
+                if let Some(location) = line.location_mut() {
+                    f(location);
+                }
+            }
+        }};
+    }
+
+    match frame {
+        EntityFrameRef::Term(frame) => relocate_frame!(frame),
+        EntityFrameRef::Typedef(frame) => relocate_frame!(frame),
+        EntityFrameRef::Instance(frame) => relocate_frame!(frame),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::share::Redeem;
+
+    fn data(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data").join(name)
+    }
+
+    #[test]
+    fn parse() {
+        let file = MappedFile::open(data("ms.obo")).unwrap();
+        let doc = file.parse().unwrap();
+        let expected = OboDoc::from_file(data("ms.obo")).unwrap();
+        // Compare `Debug` outputs to check line locations as well.
+        assert_eq!(format!("{:?}", doc), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn parse_ref() {
+        let file = MappedFile::open(data("ms.obo")).unwrap();
+        let doc = file.parse_ref().unwrap();
+        // Compare `Debug` outputs to check line locations as well.
+        assert_eq!(format!("{:?}", doc.redeem()), format!("{:?}", file.parse().unwrap()));
+    }
+
+    #[test]
+    fn parse_ref_errors() {
+        let path = std::env::temp_dir().join("fastobo-mmap-ref-errors.obo");
+        std::fs::write(&path, "ontology: a\nontology: b\n\n[Term]\nid: TST:001\n").unwrap();
+        let file = MappedFile::open(&path).unwrap();
+        let duplicate = file.parse_ref().unwrap_err();
+        assert_eq!(duplicate.location().map(|l| l.line), Some(2));
+        assert_eq!(duplicate.to_string(), file.parse().unwrap_err().to_string());
+
+        std::fs::write(&path, "ontology: a\n\n[Term]\nid: TST:001\n\n[Term]\nis_a: \n").unwrap();
+        let file = MappedFile::open(&path).unwrap();
+        let syntax = file.parse_ref().unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(syntax.to_string(), file.parse().unwrap_err().to_string());
+        match syntax {
+            crate::error::Error::ParserError { error } => match error.line_col {
+                pest::error::LineColLocation::Pos((l, _)) => assert_eq!(l, 6),
+                pest::error::LineColLocation::Span((l, _), _) => assert_eq!(l, 6),
+            },
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn bom() {
+        let path = std::env::temp_dir().join("fastobo-mmap-bom.obo");
+        std::fs::write(&path, "\u{feff}format-version: 1.4\n\n[Term]\nid: TST:001\n").unwrap();
+        let doc = MappedFile::open(&path).unwrap().parse().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(doc.header.len(), 1);
+        assert_eq!(doc.entities.len(), 1);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let path = std::env::temp_dir().join("fastobo-mmap-latin1.obo");
+        std::fs::write(&path, &b"format-version: 1.4\n\n[Term]\nid: TST:001\nname: caf\xe9\n"[..]).unwrap();
+        let err = MappedFile::open(&path).unwrap().parse().unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        match err {
+            crate::error::Error::ParserError { error } => {
+                assert_eq!(error.line_col, pest::error::LineColLocation::Pos((5, 10)));
+                assert_eq!(error.location, pest::error::InputLocation::Pos(49));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let path = std::env::temp_dir().join("fastobo-mmap-empty.obo");
+        File::create(&path).unwrap();
+        let file = MappedFile::open(&path).unwrap();
+        assert_eq!(file.parse().unwrap(), OboDoc::default());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -9,8 +9,17 @@ use crate::error::Error;
 #[macro_use]
 mod macros;
 mod quickfind;
+mod compression;
 mod from_pair;
 mod from_slice;
+mod legacy;
+mod reader;
+#[cfg(feature = "fast-parser")]
+mod fast;
+#[cfg(feature = "memmap")]
+mod mmap;
+#[cfg(feature = "async")]
+mod stream;
 
 #[doc(inline)]
 pub use fastobo_syntax::OboParser;
@@ -20,3 +29,18 @@ pub use fastobo_syntax::Rule;
 pub use self::quickfind::QuickFind;
 pub use self::from_pair::FromPair;
 pub use self::from_slice::FromSlice;
+pub use self::legacy::Rewrite;
+pub use self::reader::FrameReader;
+#[cfg(feature = "memmap")]
+pub use self::mmap::MappedFile;
+#[cfg(feature = "async")]
+pub use self::stream::AsyncFrameReader;
+pub(crate) use self::compression::Codec;
+pub(crate) use self::legacy::upgrade_line;
+pub(crate) use self::reader::decode_line;
+pub(crate) use self::reader::frame_starts;
+pub(crate) use self::reader::parse_frame;
+pub(crate) use self::reader::parse_header_clause;
+pub(crate) use self::reader::relocate;
+pub(crate) use self::reader::utf8_error;
+pub(crate) use self::reader::FrameChunk;
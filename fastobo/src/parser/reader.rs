@@ -0,0 +1,805 @@
+use std::io::BufRead;
+#[cfg(feature = "encoding_rs")]
+use std::io::BufReader;
+#[cfg(feature = "encoding_rs")]
+use std::io::Read;
+use std::iter::Iterator;
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "encoding_rs")]
+use encoding_rs::Encoding;
+#[cfg(feature = "encoding_rs")]
+use encoding_rs_io::DecodeReaderBytes;
+#[cfg(feature = "encoding_rs")]
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use pest::error::Error as PestError;
+use pest::error::ErrorVariant;
+use pest::error::LineColLocation;
+use pest::Parser;
+use pest::Position;
+
+use crate::ast::check_unique_clause;
+use crate::ast::EntityFrame;
+use crate::ast::HeaderClause;
+use crate::ast::HeaderFrame;
+use crate::ast::Line;
+use crate::ast::SourceLocation;
+use crate::ast::UnquotedString;
+use crate::error::Error;
+use crate::error::Result;
+use crate::parser::FromPair;
+use crate::parser::OboParser;
+use crate::parser::Rewrite;
+use crate::parser::Rule;
+use crate::parser::upgrade_line;
+
+/// An iterator reading entity frames contained in an OBO stream.
+///
+/// The header frame is read eagerly when the reader is created, and can be
+/// accessed with the [`header`](#method.header) method. Entity frames are then
+/// read lazily, one at a time, which allows processing arbitrarily large
+/// documents in constant memory.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use std::fs::File;
+/// # use std::io::BufReader;
+/// # use fastobo::parser::FrameReader;
+/// let file = File::open("../tests/data/ms.obo").unwrap();
+/// let mut reader = FrameReader::new(BufReader::new(file)).unwrap();
+/// assert!(!reader.header().is_empty());
+///
+/// let frame = reader.next().unwrap().unwrap();
+/// assert_eq!(frame.to_string().lines().next(), Some("[Term]"));
+/// ```
+pub struct FrameReader<B: BufRead> {
+    stream: B,
+    line: String,
+    offset: usize,
+    line_offset: usize,
+    header: HeaderFrame,
+    path: Option<Arc<Path>>,
+    rewrites: Option<Vec<Rewrite>>,
+}
+
+impl<B: BufRead> FrameReader<B> {
+    /// Create a new `FrameReader` and consume the header frame of the stream.
+    pub fn new(stream: B) -> Result<Self> {
+        Self::with_errors(stream, None)
+    }
+
+    /// Create a new `FrameReader`, skipping and recording invalid header clauses.
+    pub(crate) fn new_lenient(stream: B, errors: &mut Vec<Error>) -> Result<Self> {
+        Self::with_errors(stream, Some(errors))
+    }
+
+    fn with_errors(mut stream: B, mut errors: Option<&mut Vec<Error>>) -> Result<Self> {
+        let mut line = String::new();
+        let mut offset = 0;
+        let mut line_offset = 0;
+        let mut clauses = Vec::new();
+
+        loop {
+            // Read the next line
+            read_line(&mut stream, &mut line, line_offset, &mut offset)?;
+            let l = line.trim();
+
+            // Bail out if we reached EOF or first frame.
+            if l.starts_with('[') || line.is_empty() {
+                break;
+            }
+
+            // Parse header as long as we didn't reach EOF or first frame.
+            if !l.is_empty() {
+                let clause = parse_header_clause(&line, line_offset, offset)
+                    .and_then(|clause| check_unique_clause(&clauses, &clause).map(|_| clause));
+                match (clause, errors.as_mut()) {
+                    (Ok(clause), _) => clauses.push(clause),
+                    (Err(e), Some(errs)) => errs.push(e),
+                    (Err(e), None) => return Err(e),
+                }
+            }
+
+            // Update offsets
+            line_offset += 1;
+            offset += line.len();
+        }
+
+        Ok(Self {
+            stream,
+            line,
+            offset,
+            line_offset,
+            header: HeaderFrame::new(clauses),
+            path: None,
+            rewrites: None,
+        })
+    }
+
+    /// Get a reference to the header frame of the OBO stream.
+    pub fn header(&self) -> &HeaderFrame {
+        &self.header
+    }
+
+    /// Set the path of the source file, recorded in the frame line locations.
+    pub fn and_path<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path: Arc<Path> = Arc::from(path.as_ref());
+        for clause in self.header.iter_mut() {
+            if let Some(location) = clause.location_mut() {
+                location.path = Some(path.clone());
+            }
+        }
+        Self {
+            path: Some(path),
+            ..self
+        }
+    }
+
+    /// Enable or disable the OBO 1.2 legacy syntax for the entity frames.
+    ///
+    /// In legacy mode, clauses using OBO 1.2 constructs (such as
+    /// `exact_synonym`, `xref_analog` or `use_term`) are upgraded to their
+    /// OBO 1.4 equivalent before being parsed, and a `format-version: 1.2`
+    /// header clause is upgraded to `1.4`. Each upgrade is recorded as a
+    /// [`Rewrite`](./struct.Rewrite.html), which can be retrieved with
+    /// [`take_rewrites`](#method.take_rewrites). Columns of syntax errors in
+    /// an upgraded line refer to the upgraded text.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate fastobo;
+    /// # use fastobo::parser::FrameReader;
+    /// let doc = "format-version: 1.2\n\n[Term]\nid: TST:001\nexact_synonym: \"test\" []\n";
+    /// let mut reader = FrameReader::new(doc.as_bytes()).unwrap().and_legacy(true);
+    /// let frame = reader.next().unwrap().unwrap();
+    /// assert!(frame.to_string().contains("synonym: \"test\" EXACT []"));
+    ///
+    /// let rewrites = reader.take_rewrites();
+    /// assert_eq!(rewrites.len(), 2);
+    /// assert_eq!(rewrites[1].line(), 5);
+    /// ```
+    pub fn and_legacy(mut self, legacy: bool) -> Self {
+        if !legacy {
+            self.rewrites = None;
+            return self;
+        }
+
+        let mut rewrites = self.rewrites.take().unwrap_or_default();
+        for line in self.header.iter_mut() {
+            if let HeaderClause::FormatVersion(version) = &**line {
+                if version.as_str() == "1.2" {
+                    let original = line.to_string();
+                    **line = HeaderClause::FormatVersion(UnquotedString::new("1.4"));
+                    let lineno = line.location().map(|l| l.line).unwrap_or(0);
+                    rewrites.push(Rewrite::new(lineno, original.trim_end(), line.to_string().trim_end()));
+                }
+            }
+        }
+
+        self.rewrites = Some(rewrites);
+        self
+    }
+
+    /// Take the legacy rewrites recorded since the last call.
+    ///
+    /// Returns an empty vector if the legacy mode is not enabled.
+    pub fn take_rewrites(&mut self) -> Vec<Rewrite> {
+        self.rewrites.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Consume the reader and extract the header frame of the OBO stream.
+    pub fn into_header(self) -> HeaderFrame {
+        self.header
+    }
+}
+
+#[cfg(feature = "encoding_rs")]
+impl<R: Read> FrameReader<BufReader<DecodeReaderBytes<R, Vec<u8>>>> {
+    /// Create a new `FrameReader` transcoding a stream from the given encoding.
+    ///
+    /// The encoding is taken from the byte order mark of the stream if it
+    /// has one, and from `encoding` otherwise. Without a byte order mark or
+    /// a declared encoding, the stream is read as UTF-8. Invalid sequences
+    /// in a declared encoding are replaced with `U+FFFD`, while invalid UTF-8
+    /// is still reported as an error.
+    ///
+    /// Offsets of errors and line locations are counted in the transcoded
+    /// text, which may differ from the raw offsets in the stream.
+    ///
+    /// # Example
+    /// ```rust
+    /// # extern crate encoding_rs;
+    /// # extern crate fastobo;
+    /// # use fastobo::ast::*;
+    /// # use fastobo::parser::FrameReader;
+    /// let stream = &b"format-version: 1.4\n\n[Term]\nid: TST:001\nname: caf\xe9\n"[..];
+    /// let mut reader = FrameReader::with_encoding(stream, Some(encoding_rs::WINDOWS_1252)).unwrap();
+    /// let frame = reader.next().unwrap().unwrap();
+    /// assert!(frame.to_string().ends_with("name: café\n"));
+    /// ```
+    pub fn with_encoding(stream: R, encoding: Option<&'static Encoding>) -> Result<Self> {
+        let decoder = DecodeReaderBytesBuilder::new()
+            .encoding(encoding)
+            .bom_override(true)
+            .utf8_passthru(true)
+            .build(stream);
+        Self::new(BufReader::new(decoder))
+    }
+}
+
+impl<B: BufRead> FrameReader<B> {
+    /// Read the raw text of the next entity frame, without parsing it.
+    pub(crate) fn next_chunk(&mut self) -> Option<Result<FrameChunk>> {
+        // Bail out if we reached EOF.
+        if self.line.is_empty() {
+            return None;
+        }
+
+        // Record the position of the frame start.
+        let mut chunk = FrameChunk {
+            text: String::new(),
+            line_offset: self.line_offset,
+            offset: self.offset,
+        };
+
+        // Read lines until the next frame start or EOF.
+        loop {
+            chunk.text.push_str(&self.line);
+            self.line_offset += 1;
+            self.offset += self.line.len();
+
+            if let Err(e) = read_line(
+                &mut self.stream,
+                &mut self.line,
+                self.line_offset,
+                &mut self.offset,
+            ) {
+                self.line.clear();
+                return Some(Err(e));
+            }
+
+            if self.line.trim_start().starts_with('[') || self.line.is_empty() {
+                break;
+            }
+        }
+
+        Some(Ok(chunk))
+    }
+}
+
+impl<B: BufRead> Iterator for FrameReader<B> {
+    type Item = Result<EntityFrame>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.next_chunk()?;
+        let path = self.path.as_ref();
+        let rewrites = self.rewrites.as_mut();
+        Some(chunk.and_then(|chunk| {
+            let mut frame = match rewrites {
+                Some(rewrites) => chunk.parse_legacy(rewrites)?,
+                None => chunk.parse()?,
+            };
+            if let Some(path) = path {
+                relocate(&mut frame, |loc| loc.path = Some(path.clone()));
+            }
+            Ok(frame)
+        }))
+    }
+}
+
+/// The unparsed text of an entity frame, with its position in the stream.
+pub(crate) struct FrameChunk {
+    text: String,
+    line_offset: usize,
+    offset: usize,
+}
+
+impl FrameChunk {
+    /// Create a new chunk from the text of a frame and its position in the stream.
+    pub(crate) fn new(text: String, line_offset: usize, offset: usize) -> Self {
+        Self {
+            text,
+            line_offset,
+            offset,
+        }
+    }
+
+    /// Parse the entity frame, reporting errors at their position in the stream.
+    pub(crate) fn parse(&self) -> Result<EntityFrame> {
+        parse_frame(&self.text, self.line_offset, self.offset)
+    }
+
+    /// Parse the entity frame, upgrading OBO 1.2 clauses and recording the rewrites.
+    pub(crate) fn parse_legacy(&self, rewrites: &mut Vec<Rewrite>) -> Result<EntityFrame> {
+        let lines = self.lines();
+        let upgraded = lines
+            .iter()
+            .map(|(_, _, l)| upgrade_line(l))
+            .collect::<Vec<_>>();
+        if upgraded.iter().all(Option::is_none) {
+            return self.parse();
+        }
+
+        let upgraded_lines = lines
+            .iter()
+            .zip(upgraded.iter())
+            .map(|(&(idx, start, l), u)| (idx, start, u.as_deref().unwrap_or(l)))
+            .collect::<Vec<_>>();
+        let mut frame = self.parse_lines(&upgraded_lines)?;
+
+        // Make the locations of upgraded lines span their original text.
+        relocate(&mut frame, |loc| {
+            if let Some(idx) = (loc.line - 1).checked_sub(self.line_offset) {
+                if upgraded.get(idx).map(Option::is_some).unwrap_or(false) {
+                    loc.end = loc.start + lines[idx].2.len();
+                }
+            }
+        });
+
+        for ((idx, _, original), upgraded) in lines.iter().zip(upgraded.iter()) {
+            if let Some(upgraded) = upgraded {
+                rewrites.push(Rewrite::new(
+                    self.line_offset + idx + 1,
+                    original.trim_end(),
+                    upgraded.trim_end(),
+                ));
+            }
+        }
+
+        Ok(frame)
+    }
+
+    /// Parse the entity frame, skipping and recording invalid clauses.
+    ///
+    /// Each time the parser fails on a clause line, the error is recorded
+    /// and the frame is parsed again without the offending line. The frame
+    /// is skipped entirely if its header or its `id` clause are invalid.
+    pub(crate) fn parse_lenient(&self, errors: &mut Vec<Error>) -> Option<EntityFrame> {
+        let mut lines = self.lines();
+        loop {
+            let err = match self.parse_lines(&lines) {
+                Ok(frame) => return Some(frame),
+                Err(err) => err,
+            };
+
+            // Find the offending line from the position of the error.
+            let idx = err.line().unwrap_or(0);
+            let idx = lines
+                .iter()
+                .position(|(orig, _, _)| self.line_offset + orig + 1 == idx);
+            errors.push(err);
+
+            // Give up if the frame header or identifier are invalid.
+            match idx {
+                Some(idx) if idx > 0 && !lines[idx].2.trim_start().starts_with("id:") => {
+                    lines.remove(idx);
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Parse the entity frame, ignoring the lines for which `skip` returns `true`.
+    pub(crate) fn parse_skipping<F>(&self, skip: F) -> Result<EntityFrame>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut lines = self.lines();
+        lines.retain(|(_, _, l)| !skip(l));
+        self.parse_lines(&lines)
+    }
+
+    /// Index the lines of the frame with their position in the chunk.
+    pub(crate) fn lines(&self) -> Vec<(usize, usize, &str)> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for (idx, line) in self.text.split_terminator('\n').enumerate() {
+            let len = (line.len() + 1).min(self.text.len() - start);
+            lines.push((idx, start, &self.text[start..start + len]));
+            start += len;
+        }
+        lines
+    }
+
+    /// Parse a frame made of a subset of the chunk lines.
+    ///
+    /// Locations and errors are reported at their position in the original
+    /// stream.
+    fn parse_lines(&self, lines: &[(usize, usize, &str)]) -> Result<EntityFrame> {
+        // Record where each line starts in the text given to the parser.
+        let mut starts = Vec::with_capacity(lines.len());
+        let mut text = String::with_capacity(self.text.len());
+        for (_, _, l) in lines.iter() {
+            starts.push(text.len());
+            text.push_str(l);
+        }
+
+        let err = match parse_entity_frame(&text) {
+            Ok(mut frame) => {
+                // Map the locations back to the lines of the original chunk.
+                relocate(&mut frame, |loc| {
+                    let idx = (loc.line - 1).min(lines.len() - 1);
+                    let (orig, orig_start, _) = lines[idx];
+                    loc.shift(
+                        self.line_offset + orig - idx,
+                        self.offset + orig_start - starts[idx],
+                    );
+                });
+                return Ok(frame);
+            }
+            Err(e) => e,
+        };
+
+        // Find the line of the error in the parsed text.
+        let idx = match err.line() {
+            Some(l) => l - 1,
+            None => return Err(err.with_offsets(self.line_offset, self.offset)),
+        };
+        let idx = idx.min(lines.len().saturating_sub(1));
+
+        // Report the error at its position in the original stream.
+        match lines.get(idx) {
+            Some(&(orig, orig_start, _)) => Err(err.with_offsets(
+                self.line_offset + orig - idx,
+                self.offset + orig_start - starts[idx],
+            )),
+            None => Err(err.with_offsets(self.line_offset, self.offset)),
+        }
+    }
+}
+
+/// Parse the text of a single entity frame.
+///
+/// With the `fast-parser` feature, the hand-written parser is tried first,
+/// and `pest` is only used for the frames it does not support.
+fn parse_entity_frame(text: &str) -> Result<EntityFrame> {
+    #[cfg(feature = "fast-parser")]
+    {
+        if let Some(frame) = super::fast::parse_entity_frame(text) {
+            return Ok(frame);
+        }
+    }
+    unsafe {
+        OboParser::parse(Rule::EntitySingle, text)
+            .map_err(Error::from)
+            .and_then(|mut p| EntityFrame::from_pair_unchecked(p.next().unwrap()))
+    }
+}
+
+/// The byte order mark that may start a UTF-8 encoded stream.
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Read the next line of `stream` into `line`, replacing its content.
+fn read_line<B>(stream: &mut B, line: &mut String, line_offset: usize, offset: &mut usize) -> Result<()>
+where
+    B: BufRead,
+{
+    let mut bytes = std::mem::take(line).into_bytes();
+    bytes.clear();
+    stream.read_until(b'\n', &mut bytes)?;
+    *line = decode_line(bytes, line_offset, offset)?;
+    Ok(())
+}
+
+/// Decode a line read from a stream, starting at the given position.
+///
+/// The byte order mark starting a stream is removed, in which case `offset`
+/// is moved past it. Invalid UTF-8 is reported as a parser error located at
+/// the first invalid byte, rather than as an opaque IO error.
+pub(crate) fn decode_line(mut bytes: Vec<u8>, line_offset: usize, offset: &mut usize) -> Result<String> {
+    if *offset == 0 && bytes.starts_with(BOM) {
+        bytes.drain(..BOM.len());
+        *offset = BOM.len();
+    }
+    String::from_utf8(bytes).map_err(|e| {
+        let valid = e.utf8_error().valid_up_to();
+        utf8_error(e.as_bytes(), valid, line_offset, *offset)
+    })
+}
+
+/// Create a parser error for an invalid UTF-8 sequence in a line.
+///
+/// `valid` is the length of the valid prefix of `line`, and `line_offset`
+/// and `offset` give the position of the line in the stream.
+pub(crate) fn utf8_error(line: &[u8], valid: usize, line_offset: usize, offset: usize) -> Error {
+    let text = String::from_utf8_lossy(line);
+    let invalid = line[valid..].iter().take(4).map(|b| format!("\\x{:02x}", b));
+    let variant = ErrorVariant::CustomError {
+        message: format!("invalid UTF-8 sequence starting with {}", invalid.collect::<String>()),
+    };
+    // The lossy conversion keeps the valid prefix unchanged.
+    let pos = Position::new(&text, valid).unwrap();
+    Error::from(PestError::new_from_pos(variant, pos)).with_offsets(line_offset, offset)
+}
+
+/// Parse the text of an entity frame, reporting errors at their position in the stream.
+pub(crate) fn parse_frame(text: &str, line_offset: usize, offset: usize) -> Result<EntityFrame> {
+    let mut frame = parse_entity_frame(text).map_err(|e| e.with_offsets(line_offset, offset))?;
+    relocate(&mut frame, |loc| loc.shift(line_offset, offset));
+    Ok(frame)
+}
+
+/// Find the position of every entity frame in the text of an OBO document.
+///
+/// Frames are delimited exactly like in a `FrameReader`, and returned as
+/// the index of their first line and their offset in the text. The end of
+/// the text is added as a last position, so that consecutive pairs give the
+/// bounds of each frame, and the first position gives the end of the header.
+pub(crate) fn frame_starts(text: &str) -> Vec<(usize, usize)> {
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    for (idx, line) in text.split_terminator('\n').enumerate() {
+        if line.trim_start().starts_with('[') {
+            starts.push((idx, offset));
+        }
+        offset += (line.len() + 1).min(text.len() - offset);
+    }
+    starts.push((0, text.len()));
+    starts
+}
+
+/// Parse a header clause line, reporting errors at their position in the stream.
+pub(crate) fn parse_header_clause(
+    line: &str,
+    line_offset: usize,
+    offset: usize,
+) -> Result<Line<HeaderClause>> {
+    let mut clause = unsafe {
+        OboParser::parse(Rule::HeaderClauseLine, line)
+            .map_err(Error::from)
+            .and_then(|mut p| Line::<HeaderClause>::from_pair_unchecked(p.next().unwrap()))
+            .map_err(|e| e.with_offsets(line_offset, offset))?
+    };
+    if let Some(location) = clause.location_mut() {
+        location.shift(line_offset, offset);
+    }
+    Ok(clause)
+}
+
+/// Apply `f` to the location of every line of an entity frame.
+pub(crate) fn relocate<F>(frame: &mut EntityFrame, mut f: F)
+where
+    F: FnMut(&mut SourceLocation),
+{
+    macro_rules! relocate_frame {
+        ($frame:ident) => {{
+            if let Some(location) = $frame.id_mut().location_mut() {
+                f(location);
+            }
+            for line in $frame.iter_mut() {
+                if let Some(location) = line.location_mut() {
+                    f(location);
+                }
+            }
+        }};
+    }
+
+    match frame {
+        EntityFrame::Term(frame) => relocate_frame!(frame),
+        EntityFrame::Typedef(frame) => relocate_frame!(frame),
+        EntityFrame::Instance(frame) => relocate_frame!(frame),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ast::*;
+
+    const DOC: &str = "format-version: 1.4
+ontology: test
+
+[Term]
+id: TST:001
+name: first term
+
+[Term]
+id: TST:002
+name: second term
+is_a: TST:001 ! first term
+
+[Typedef]
+id: part_of
+";
+
+    #[test]
+    fn header() {
+        let reader = FrameReader::new(DOC.as_bytes()).unwrap();
+        let header = reader.header();
+        assert_eq!(header.len(), 2);
+        assert_eq!(
+            *header[1],
+            HeaderClause::Ontology(UnquotedString::new("test"))
+        );
+    }
+
+    #[test]
+    fn iter() {
+        let reader = FrameReader::new(DOC.as_bytes()).unwrap();
+        let frames = reader.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(frames.len(), 3);
+        match &frames[1] {
+            EntityFrame::Term(frame) => assert_eq!(
+                frame.id().as_ref(),
+                &ClassIdent::from(PrefixedIdent::new("TST", "002"))
+            ),
+            other => panic!("unexpected frame: {:?}", other),
+        }
+        match &frames[2] {
+            EntityFrame::Typedef(_) => (),
+            other => panic!("unexpected frame: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn error_offsets() {
+        let doc = "format-version: 1.4\n\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\nis_a: \n";
+        let mut reader = FrameReader::new(doc.as_bytes()).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next().unwrap().unwrap_err() {
+            Error::ParserError { error } => match error.line_col {
+                pest::error::LineColLocation::Pos((l, _)) => assert_eq!(l, 8),
+                pest::error::LineColLocation::Span((l, _), _) => assert_eq!(l, 8),
+            },
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let doc = "format-version: 1.4\n\n[Term]\nid: TST:001\nis_a: \nname: first\nxref: \n";
+        let mut errors = Vec::new();
+        let mut reader = FrameReader::new_lenient(doc.as_bytes(), &mut errors).unwrap();
+        let chunk = reader.next_chunk().unwrap().unwrap();
+        let frame = chunk.parse_lenient(&mut errors).unwrap();
+        assert_eq!(frame.to_string(), "[Term]\nid: TST:001\nname: first\n");
+        let lines = errors
+            .iter()
+            .map(|e| match e {
+                Error::ParserError { error } => match error.line_col {
+                    LineColLocation::Pos((l, _)) => l,
+                    LineColLocation::Span((l, _), _) => l,
+                },
+                e => panic!("unexpected error: {:?}", e),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![5, 7]);
+    }
+
+    #[test]
+    fn legacy() {
+        let doc = "format-version: 1.2\n\n[Term]\nid: TST:001\n\
+                   exact_synonym: \"first\" [TST:xref]\n\
+                   xref_analog: TST:002\n\
+                   is_a: TST:000\n";
+        let mut reader = FrameReader::new(doc.as_bytes()).unwrap().and_legacy(true);
+        assert_eq!(
+            *reader.header()[0],
+            HeaderClause::FormatVersion(UnquotedString::new("1.4"))
+        );
+
+        let frame = reader.next().unwrap().unwrap();
+        let expected = "[Term]\nid: TST:001\n\
+                        synonym: \"first\" EXACT [TST:xref]\n\
+                        xref: TST:002\n\
+                        is_a: TST:000\n";
+        assert_eq!(frame.to_string(), expected);
+
+        // Locations refer to the original text of the lines.
+        match &frame {
+            EntityFrame::Term(frame) => {
+                let syn = frame.clauses()[0].location().unwrap();
+                assert_eq!(&doc[syn.start..syn.end], "exact_synonym: \"first\" [TST:xref]\n");
+                let is_a = frame.clauses()[2].location().unwrap();
+                assert_eq!(&doc[is_a.start..is_a.end], "is_a: TST:000\n");
+            }
+            other => panic!("unexpected frame: {:?}", other),
+        }
+
+        let rewrites = reader.take_rewrites();
+        let lines = rewrites.iter().map(Rewrite::line).collect::<Vec<_>>();
+        assert_eq!(lines, vec![1, 5, 6]);
+        assert_eq!(rewrites[2].upgraded(), "xref: TST:002");
+        assert!(reader.take_rewrites().is_empty());
+    }
+
+    #[test]
+    fn locations() {
+        let reader = FrameReader::new(DOC.as_bytes()).unwrap().and_path("test.obo");
+        let frames = reader.collect::<Result<Vec<_>>>().unwrap();
+        match &frames[1] {
+            EntityFrame::Term(frame) => {
+                let id = frame.id().location().unwrap();
+                assert_eq!((id.line, id.column), (9, 5));
+                assert_eq!(&DOC[id.start..id.end], "TST:002\n");
+                let clause = frame.clauses()[1].location().unwrap();
+                assert_eq!((clause.line, clause.column), (11, 1));
+                assert_eq!(&DOC[clause.start..clause.end], "is_a: TST:001 ! first term\n");
+                assert_eq!(clause.path.as_ref().unwrap().to_str(), Some("test.obo"));
+            }
+            other => panic!("unexpected frame: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bom() {
+        let doc = format!("\u{feff}{}", DOC);
+        let mut reader = FrameReader::new(doc.as_bytes()).unwrap();
+        assert_eq!(reader.header().len(), 2);
+        match reader.next().unwrap().unwrap() {
+            EntityFrame::Term(frame) => {
+                let id = frame.id().location().unwrap();
+                assert_eq!(&doc[id.start..id.end], "TST:001\n");
+            }
+            other => panic!("unexpected frame: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let doc = &b"format-version: 1.4\n\n[Term]\nid: TST:001\nname: caf\xe9\n"[..];
+        let mut reader = FrameReader::new(doc).unwrap();
+        match reader.next().unwrap().unwrap_err() {
+            Error::ParserError { error } => {
+                assert_eq!(error.line_col, LineColLocation::Pos((5, 10)));
+                assert_eq!(error.location, pest::error::InputLocation::Pos(49));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(reader.next().is_none());
+
+        let doc = &b"format-version: 1.4\nremark: caf\xe9\n"[..];
+        match FrameReader::new(doc).err().unwrap() {
+            Error::ParserError { error } => {
+                assert_eq!(error.line_col, LineColLocation::Pos((2, 12)));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn with_encoding() {
+        let doc = &b"format-version: 1.4\nremark: \x93quoted\x94\n"[..];
+        let reader = FrameReader::with_encoding(doc, Some(encoding_rs::WINDOWS_1252)).unwrap();
+        assert_eq!(
+            *reader.header()[1],
+            HeaderClause::Remark(UnquotedString::new("\u{201c}quoted\u{201d}"))
+        );
+
+        let utf16 = std::iter::once('\u{feff}')
+            .chain(DOC.chars())
+            .flat_map(|c| c.encode_utf16(&mut [0; 2]).to_vec())
+            .flat_map(|u| u.to_le_bytes().to_vec())
+            .collect::<Vec<u8>>();
+        let reader = FrameReader::with_encoding(&utf16[..], None).unwrap();
+        let expected = FrameReader::new(DOC.as_bytes()).unwrap();
+        assert_eq!(reader.header(), expected.header());
+        assert_eq!(
+            reader.collect::<Result<Vec<_>>>().unwrap(),
+            expected.collect::<Result<Vec<_>>>().unwrap(),
+        );
+    }
+
+    #[test]
+    fn locations_lenient() {
+        let doc = "format-version: 1.4\n\n[Term]\nid: TST:001\nis_a: \nname: first\n";
+        let mut errors = Vec::new();
+        let mut reader = FrameReader::new_lenient(doc.as_bytes(), &mut errors).unwrap();
+        let chunk = reader.next_chunk().unwrap().unwrap();
+        match chunk.parse_lenient(&mut errors).unwrap() {
+            EntityFrame::Term(frame) => {
+                let clause = frame.clauses()[0].location().unwrap();
+                assert_eq!(clause.line, 6);
+                assert_eq!(&doc[clause.start..clause.end], "name: first\n");
+            }
+            other => panic!("unexpected frame: {:?}", other),
+        }
+    }
+}
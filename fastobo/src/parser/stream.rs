@@ -0,0 +1,267 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use futures::io::AsyncBufRead;
+use futures::io::AsyncBufReadExt;
+use futures::stream::Stream;
+
+use crate::ast::check_unique_clause;
+use crate::ast::EntityFrame;
+use crate::ast::HeaderFrame;
+use crate::error::Error;
+use crate::error::Result;
+use crate::parser::decode_line;
+use crate::parser::parse_header_clause;
+use crate::parser::relocate;
+use crate::parser::FrameChunk;
+
+/// An asynchronous reader for the entity frames contained in an OBO stream.
+///
+/// This is the asynchronous counterpart of [`FrameReader`]: the header frame
+/// is read when the reader is created, and entity frames are then read one
+/// at a time, either with [`next_frame`] or through the [`Stream`] returned
+/// by [`into_stream`]. Each frame is parsed with the same logic as the
+/// blocking reader once its text has been read, so the executor is only ever
+/// busy parsing a single frame.
+///
+/// Readers from other runtimes (such as `tokio`) can be used through a
+/// compatibility layer implementing the `futures` IO traits.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # extern crate futures;
+/// # use fastobo::parser::AsyncFrameReader;
+/// let stream = &b"format-version: 1.4\n\n[Term]\nid: TST:001\n"[..];
+/// futures::executor::block_on(async {
+///     let mut reader = AsyncFrameReader::new(stream).await.unwrap();
+///     assert_eq!(reader.header().len(), 1);
+///
+///     let frame = reader.next_frame().await.unwrap().unwrap();
+///     assert_eq!(frame.to_string(), "[Term]\nid: TST:001\n");
+///     assert!(reader.next_frame().await.is_none());
+/// });
+/// ```
+///
+/// [`FrameReader`]: ./struct.FrameReader.html
+/// [`next_frame`]: #method.next_frame
+/// [`into_stream`]: #method.into_stream
+/// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+pub struct AsyncFrameReader<B: AsyncBufRead + Unpin> {
+    stream: B,
+    line: String,
+    offset: usize,
+    line_offset: usize,
+    header: HeaderFrame,
+    path: Option<Arc<Path>>,
+}
+
+impl<B: AsyncBufRead + Unpin> AsyncFrameReader<B> {
+    /// Create a new `AsyncFrameReader` and consume the header frame of the stream.
+    pub async fn new(mut stream: B) -> Result<Self> {
+        let mut line = String::new();
+        let mut offset = 0;
+        let mut line_offset = 0;
+        let mut clauses = Vec::new();
+
+        loop {
+            // Read the next line
+            read_line(&mut stream, &mut line, line_offset, &mut offset).await?;
+            let l = line.trim();
+
+            // Bail out if we reached EOF or first frame.
+            if l.starts_with('[') || line.is_empty() {
+                break;
+            }
+
+            // Parse header as long as we didn't reach EOF or first frame.
+            if !l.is_empty() {
+                let clause = parse_header_clause(&line, line_offset, offset)?;
+                check_unique_clause(&clauses, &clause)?;
+                clauses.push(clause);
+            }
+
+            // Update offsets
+            line_offset += 1;
+            offset += line.len();
+        }
+
+        Ok(Self {
+            stream,
+            line,
+            offset,
+            line_offset,
+            header: HeaderFrame::new(clauses),
+            path: None,
+        })
+    }
+
+    /// Get a reference to the header frame of the OBO stream.
+    pub fn header(&self) -> &HeaderFrame {
+        &self.header
+    }
+
+    /// Set the path of the source file, recorded in the frame line locations.
+    pub fn and_path<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path: Arc<Path> = Arc::from(path.as_ref());
+        for clause in self.header.iter_mut() {
+            if let Some(location) = clause.location_mut() {
+                location.path = Some(path.clone());
+            }
+        }
+        Self {
+            path: Some(path),
+            ..self
+        }
+    }
+
+    /// Consume the reader and extract the header frame of the OBO stream.
+    pub fn into_header(self) -> HeaderFrame {
+        self.header
+    }
+
+    /// Read and parse the next entity frame of the OBO stream.
+    pub async fn next_frame(&mut self) -> Option<Result<EntityFrame>> {
+        let chunk = self.next_chunk().await?;
+        let path = self.path.as_ref();
+        Some(chunk.and_then(|chunk| {
+            let mut frame = chunk.parse()?;
+            if let Some(path) = path {
+                relocate(&mut frame, |loc| loc.path = Some(path.clone()));
+            }
+            Ok(frame)
+        }))
+    }
+
+    /// Convert the reader into a `Stream` of entity frames.
+    pub fn into_stream(self) -> impl Stream<Item = Result<EntityFrame>> {
+        futures::stream::unfold(self, |mut reader| {
+            async move {
+                let frame = reader.next_frame().await?;
+                Some((frame, reader))
+            }
+        })
+    }
+
+    /// Read the raw text of the next entity frame, without parsing it.
+    async fn next_chunk(&mut self) -> Option<Result<FrameChunk>> {
+        // Bail out if we reached EOF.
+        if self.line.is_empty() {
+            return None;
+        }
+
+        // Record the position of the frame start.
+        let mut text = String::new();
+        let line_offset = self.line_offset;
+        let offset = self.offset;
+
+        // Read lines until the next frame start or EOF.
+        loop {
+            text.push_str(&self.line);
+            self.line_offset += 1;
+            self.offset += self.line.len();
+
+            let result = read_line(
+                &mut self.stream,
+                &mut self.line,
+                self.line_offset,
+                &mut self.offset,
+            );
+            if let Err(e) = result.await {
+                self.line.clear();
+                return Some(Err(e));
+            }
+
+            if self.line.trim_start().starts_with('[') || self.line.is_empty() {
+                break;
+            }
+        }
+
+        Some(Ok(FrameChunk::new(text, line_offset, offset)))
+    }
+}
+
+/// Read the next line of `stream` into `line`, replacing its content.
+async fn read_line<B>(
+    stream: &mut B,
+    line: &mut String,
+    line_offset: usize,
+    offset: &mut usize,
+) -> Result<()>
+where
+    B: AsyncBufRead + Unpin,
+{
+    let mut bytes = std::mem::take(line).into_bytes();
+    bytes.clear();
+    stream.read_until(b'\n', &mut bytes).await?;
+    *line = decode_line(bytes, line_offset, offset)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use futures::executor::block_on;
+    use futures::stream::StreamExt;
+    use futures::stream::TryStreamExt;
+
+    use super::*;
+    use crate::ast::*;
+
+    const DOC: &str = "format-version: 1.4
+ontology: test
+
+[Term]
+id: TST:001
+name: first term
+
+[Term]
+id: TST:002
+name: second term
+is_a: TST:001 ! first term
+
+[Typedef]
+id: part_of
+";
+
+    #[test]
+    fn header() {
+        let reader = block_on(AsyncFrameReader::new(DOC.as_bytes())).unwrap();
+        let header = reader.header();
+        assert_eq!(header.len(), 2);
+        assert_eq!(
+            *header[1],
+            HeaderClause::Ontology(UnquotedString::new("test"))
+        );
+    }
+
+    #[test]
+    fn stream() {
+        let reader = block_on(AsyncFrameReader::new(DOC.as_bytes())).unwrap();
+        let frames = block_on(reader.into_stream().try_collect::<Vec<_>>()).unwrap();
+        let expected = crate::parser::FrameReader::new(DOC.as_bytes())
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(frames, expected);
+    }
+
+    #[test]
+    fn error_offsets() {
+        let doc = "format-version: 1.4\n\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\nis_a: \n";
+        let reader = block_on(AsyncFrameReader::new(doc.as_bytes())).unwrap();
+        let mut frames = Box::pin(reader.into_stream());
+        assert!(block_on(frames.next()).unwrap().is_ok());
+        match block_on(frames.next()).unwrap().unwrap_err() {
+            Error::ParserError { error } => match error.line_col {
+                pest::error::LineColLocation::Pos((l, _)) => assert_eq!(l, 8),
+                pest::error::LineColLocation::Span((l, _), _) => assert_eq!(l, 8),
+            },
+            e => panic!("unexpected error: {:?}", e),
+        }
+        assert!(block_on(frames.next()).is_none());
+    }
+}
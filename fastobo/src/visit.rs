@@ -132,8 +132,11 @@ pub trait Visit<'ast> {
     }
 
     fn visit_header_frame(&mut self, header: &'ast HeaderFrame) {
-        for clause in header.iter() {
-            self.visit_header_clause(clause)
+        for line in header.iter() {
+            self.visit_header_clause(line.as_ref());
+            if let Some(qualifiers) = line.qualifiers() {
+                self.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -179,7 +182,7 @@ pub trait Visit<'ast> {
             InstanceOf(id) => self.visit_class_ident(id),
             Relationship(r, id) => {
                 self.visit_relation_ident(r);
-                self.visit_ident(id);
+                self.visit_instance_ident(id);
             }
             CreatedBy(s) => self.visit_unquoted_string(s),
             CreationDate(dt) => self.visit_iso_date(dt),
@@ -191,8 +194,11 @@ pub trait Visit<'ast> {
 
     fn visit_instance_frame(&mut self, frame: &'ast InstanceFrame) {
         self.visit_instance_ident(frame.id());
-        for clause in frame.iter() {
-            self.visit_instance_clause(clause);
+        for line in frame.iter() {
+            self.visit_instance_clause(line.as_ref());
+            if let Some(qualifiers) = line.qualifiers() {
+                self.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -230,6 +236,17 @@ pub trait Visit<'ast> {
         self.visit_ident_local(&id.local);
     }
 
+    fn visit_qualifier(&mut self, qualifier: &'ast Qualifier) {
+        self.visit_relation_ident(qualifier.key());
+        self.visit_quoted_string(qualifier.value());
+    }
+
+    fn visit_qualifier_list(&mut self, qualifiers: &'ast QualifierList) {
+        for qualifier in qualifiers.iter() {
+            self.visit_qualifier(qualifier)
+        }
+    }
+
     #[allow(unused_variables)]
     fn visit_quoted_string(&mut self, string: &'ast QuotedString) {}
 
@@ -293,13 +310,21 @@ pub trait Visit<'ast> {
             Consider(id) => self.visit_class_ident(id),
             CreatedBy(s) => self.visit_unquoted_string(s),
             CreationDate(dt) => self.visit_iso_date(dt),
+            ExpandAssertionTo(s, xrefs) | ExpandExpressionTo(s, xrefs) => {
+                self.visit_quoted_string(s);
+                self.visit_xref_list(xrefs);
+            }
+            IsMetadataTag(_) | IsClassLevel(_) => (),
         }
     }
 
     fn visit_term_frame(&mut self, frame: &'ast TermFrame) {
         self.visit_class_ident(frame.id());
-        for clause in frame.iter() {
-            self.visit_term_clause(clause);
+        for line in frame.iter() {
+            self.visit_term_clause(line.as_ref());
+            if let Some(qualifiers) = line.qualifiers() {
+                self.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -359,8 +384,11 @@ pub trait Visit<'ast> {
 
     fn visit_typedef_frame(&mut self, frame: &'ast TypedefFrame) {
         self.visit_relation_ident(frame.id());
-        for clause in frame.iter() {
-            self.visit_typedef_clause(clause);
+        for line in frame.iter() {
+            self.visit_typedef_clause(line.as_ref());
+            if let Some(qualifiers) = line.qualifiers() {
+                self.visit_qualifier_list(qualifiers);
+            }
         }
     }
 
@@ -0,0 +1,568 @@
+//! Configurable serializer for OBO documents.
+//!
+//! `Display` writes an OBO document exactly as it is stored in memory. An
+//! [`OboWriter`] can instead reorder the document following the canonical
+//! serialization of the reference OBO tools, skip qualifiers and comments,
+//! use Windows line endings, or update the `date` and `saved-by` header
+//! clauses of the written document.
+//!
+//! Frames can also be streamed with [`OboWriter::write_header`] and
+//! [`OboWriter::write_frame`], or [`OboWriter::write_frames`], so that an
+//! ontology can be transformed and written without ever being loaded
+//! completely in memory. The output is always buffered.
+//!
+//! # Example
+//! ```rust
+//! extern crate fastobo;
+//!
+//! use std::str::FromStr;
+//! use fastobo::ast::*;
+//! use fastobo::writer::OboWriter;
+//!
+//! let doc = OboDoc::from_str(
+//!     "format-version: 1.4\n\n[Term]\nid: TST:002\nis_a: TST:001\nname: b\n\n[Term]\nid: TST:001\n"
+//! ).unwrap();
+//!
+//! let mut writer = OboWriter::new(Vec::new()).and_canonical(true);
+//! writer.write_doc(&doc).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+//!     "format-version: 1.4\n\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\nname: b\nis_a: TST:001\n"
+//! );
+//! ```
+//!
+//! [`OboWriter`]: ./struct.OboWriter.html
+//! [`OboWriter::write_header`]: ./struct.OboWriter.html#method.write_header
+//! [`OboWriter::write_frame`]: ./struct.OboWriter.html#method.write_frame
+//! [`OboWriter::write_frames`]: ./struct.OboWriter.html#method.write_frames
+
+use std::borrow::Borrow;
+use std::fmt::Display;
+use std::io::BufWriter;
+use std::io::Write;
+
+use crate::ast::*;
+use crate::error::Error;
+use crate::error::Result;
+
+/// The canonical order of header clause tags.
+///
+/// This is the order used by the reference OBO tools, such as OBO-Edit.
+/// Unreserved tags are written after all the tags of this list.
+const HEADER_TAGS: &[&str] = &[
+    "format-version",
+    "data-version",
+    "date",
+    "saved-by",
+    "auto-generated-by",
+    "subsetdef",
+    "synonymtypedef",
+    "default-namespace",
+    "namespace-id-rule",
+    "idspace",
+    "treat-xrefs-as-equivalent",
+    "treat-xrefs-as-genus-differentia",
+    "treat-xrefs-as-reverse-genus-differentia",
+    "treat-xrefs-as-relationship",
+    "treat-xrefs-as-is_a",
+    "treat-xrefs-as-has-subclass",
+    "remark",
+    "import",
+    "ontology",
+    "property_value",
+    "owl-axioms",
+];
+
+/// The canonical order of entity clause tags, shared by all frame kinds.
+const ENTITY_TAGS: &[&str] = &[
+    "is_anonymous",
+    "name",
+    "namespace",
+    "alt_id",
+    "def",
+    "comment",
+    "subset",
+    "synonym",
+    "xref",
+    "builtin",
+    "instance_of",
+    "domain",
+    "range",
+    "holds_over_chain",
+    "is_anti_symmetric",
+    "is_cyclic",
+    "is_reflexive",
+    "is_symmetric",
+    "is_transitive",
+    "is_functional",
+    "is_inverse_functional",
+    "is_a",
+    "intersection_of",
+    "union_of",
+    "equivalent_to",
+    "disjoint_from",
+    "inverse_of",
+    "transitive_over",
+    "equivalent_to_chain",
+    "disjoint_over",
+    "relationship",
+    "expand_assertion_to",
+    "expand_expression_to",
+    "is_metadata_tag",
+    "is_class_level",
+    "property_value",
+    "created_by",
+    "creation_date",
+    "is_obsolete",
+    "replaced_by",
+    "consider",
+];
+
+/// Get the rank of the tag of a serialized clause in a canonical tag order.
+fn rank(tags: &[&str], clause: &str) -> usize {
+    let tag = clause.split(':').next().unwrap_or(clause);
+    tags.iter().position(|t| *t == tag).unwrap_or(tags.len())
+}
+
+/// The line terminator used by an `OboWriter`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum LineEnding {
+    /// Unix line endings (`\n`).
+    #[default]
+    Lf,
+    /// Windows line endings (`\r\n`).
+    CrLf,
+}
+
+impl LineEnding {
+    /// Get the line terminator as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// A configurable serializer writing OBO documents to an `io::Write`.
+///
+/// With the default options, the output is the same as the `Display`
+/// implementation of the written document.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo;
+/// # use std::str::FromStr;
+/// # use fastobo::ast::*;
+/// # use fastobo::writer::OboWriter;
+/// let doc = OboDoc::from_str("[Term]\nid: TST:001\n\n[Term]\nid: TST:002\n").unwrap();
+///
+/// // frames can come from any iterator, and are written as they are produced
+/// let mut writer = OboWriter::new(Vec::new());
+/// writer.write_header(doc.header()).unwrap();
+/// writer.write_frames(doc.entities).unwrap();
+/// assert_eq!(
+///     String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+///     "\n[Term]\nid: TST:001\n\n[Term]\nid: TST:002\n",
+/// );
+/// ```
+#[derive(Debug)]
+pub struct OboWriter<W: Write> {
+    writer: BufWriter<W>,
+    frames: usize,
+    canonical: bool,
+    qualifiers: bool,
+    comments: bool,
+    line_ending: LineEnding,
+    date: Option<NaiveDateTime>,
+    saved_by: Option<UnquotedString>,
+}
+
+impl<W: Write> OboWriter<W> {
+    /// Create a new `OboWriter` with the default options.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+            frames: 0,
+            canonical: false,
+            qualifiers: true,
+            comments: true,
+            line_ending: LineEnding::default(),
+            date: None,
+            saved_by: None,
+        }
+    }
+
+    /// Enable or disable the canonical ordering of the reference OBO tools.
+    ///
+    /// In canonical mode, header clauses and entity clauses are sorted by
+    /// tag following the order used by OBO-Edit, then by value compared
+    /// case-insensitively. Entity frames are written as term frames, then
+    /// typedef frames, then instance frames, each sorted by identifier.
+    pub fn and_canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// Enable or disable writing the qualifiers of each line.
+    pub fn and_qualifiers(mut self, qualifiers: bool) -> Self {
+        self.qualifiers = qualifiers;
+        self
+    }
+
+    /// Enable or disable writing the comments of each line.
+    pub fn and_comments(mut self, comments: bool) -> Self {
+        self.comments = comments;
+        self
+    }
+
+    /// Set the line terminator of the written lines.
+    pub fn and_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Set the value of the `date` header clause of the written documents.
+    ///
+    /// An existing `date` clause is replaced, otherwise a new clause is
+    /// added at its canonical position.
+    pub fn and_date<D>(mut self, date: D) -> Self
+    where
+        D: Into<Option<NaiveDateTime>>,
+    {
+        self.date = date.into();
+        self
+    }
+
+    /// Set the value of the `saved-by` header clause of the written documents.
+    ///
+    /// An existing `saved-by` clause is replaced, otherwise a new clause is
+    /// added at its canonical position.
+    pub fn and_saved_by<S>(mut self, saved_by: S) -> Self
+    where
+        S: Into<Option<UnquotedString>>,
+    {
+        self.saved_by = saved_by.into();
+        self
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.writer.get_ref()
+    }
+
+    /// Flush the buffered output and return the underlying writer.
+    pub fn into_inner(self) -> Result<W> {
+        self.writer
+            .into_inner()
+            .map_err(|e| Error::from(e.into_error()))
+    }
+
+    /// Flush the buffered output to the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(Error::from)
+    }
+
+    /// Write an OBO document.
+    ///
+    /// In canonical mode, the entity frames of the document are sorted
+    /// before being written.
+    pub fn write_doc(&mut self, doc: &OboDoc) -> Result<()> {
+        self.write_header(doc.header())?;
+
+        let mut entities = doc.entities.iter().collect::<Vec<_>>();
+        if self.canonical {
+            let mut keyed = entities
+                .into_iter()
+                .map(|frame| (frame_key(frame), frame))
+                .collect::<Vec<_>>();
+            keyed.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+            entities = keyed.into_iter().map(|(_, frame)| frame).collect();
+        }
+
+        self.write_frames(entities)
+    }
+
+    /// Write a header frame, followed by an empty line.
+    ///
+    /// This starts a new document: the next frame is written right after
+    /// the header, without an additional separator.
+    pub fn write_header(&mut self, header: &HeaderFrame) -> Result<()> {
+        self.frames = 0;
+
+        let mut clauses = header.iter().cloned().collect::<Vec<_>>();
+        if let Some(ref date) = self.date {
+            update_clause(&mut clauses, HeaderClause::Date(date.clone()));
+        }
+        if let Some(ref saved_by) = self.saved_by {
+            update_clause(&mut clauses, HeaderClause::SavedBy(saved_by.clone()));
+        }
+
+        let lines = self.sort_lines(HEADER_TAGS, clauses.iter());
+        for (text, line) in lines {
+            self.write_line(&text, line)?;
+        }
+        self.write_eol()
+    }
+
+    /// Write all the entity frames of an iterator, then flush the output.
+    ///
+    /// Frames are written in the order they are produced, even in canonical
+    /// mode, so that the frames never have to be collected in memory.
+    pub fn write_frames<I>(&mut self, frames: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<EntityFrame>,
+    {
+        for frame in frames {
+            self.write_frame(frame.borrow())?;
+        }
+        self.flush()
+    }
+
+    /// Write an entity frame, separated from the previous one by an empty line.
+    pub fn write_frame(&mut self, frame: &EntityFrame) -> Result<()> {
+        if self.frames > 0 {
+            self.write_eol()?;
+        }
+        self.frames += 1;
+
+        match frame {
+            EntityFrame::Term(frame) => {
+                self.write_frame_id("[Term]", frame.id())?;
+                for (text, line) in self.sort_lines(ENTITY_TAGS, frame.clauses().iter()) {
+                    self.write_line(&text, line)?;
+                }
+            }
+            EntityFrame::Typedef(frame) => {
+                self.write_frame_id("[Typedef]", frame.id())?;
+                for (text, line) in self.sort_lines(ENTITY_TAGS, frame.clauses().iter()) {
+                    self.write_line(&text, line)?;
+                }
+            }
+            EntityFrame::Instance(frame) => {
+                self.write_frame_id("[Instance]", frame.id())?;
+                for (text, line) in self.sort_lines(ENTITY_TAGS, frame.clauses().iter()) {
+                    self.write_line(&text, line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the header line and the `id` clause of an entity frame.
+    fn write_frame_id<T: Display>(&mut self, header: &str, id: &Line<T>) -> Result<()> {
+        self.writer.write_all(header.as_bytes())?;
+        self.write_eol()?;
+        self.write_line(&format!("id: {}", id.as_ref()), id)
+    }
+
+    /// Serialize the clauses of some lines, sorting them in canonical mode.
+    fn sort_lines<'a, T, I>(&self, tags: &[&str], lines: I) -> Vec<(String, &'a Line<T>)>
+    where
+        T: Display + 'a,
+        I: IntoIterator<Item = &'a Line<T>>,
+    {
+        let mut lines = lines
+            .into_iter()
+            .map(|line| (line.as_ref().to_string(), line))
+            .collect::<Vec<_>>();
+        if self.canonical {
+            lines.sort_by_cached_key(|(text, _)| {
+                (rank(tags, text), text.to_lowercase(), text.clone())
+            });
+        }
+        lines
+    }
+
+    /// Write a serialized clause with the qualifiers and comment of its line.
+    fn write_line<T>(&mut self, text: &str, line: &Line<T>) -> Result<()> {
+        self.writer.write_all(text.as_bytes())?;
+        if let Some(qualifiers) = line.qualifiers().filter(|_| self.qualifiers) {
+            write!(self.writer, " {}", qualifiers)?;
+        }
+        if let Some(comment) = line.comment().filter(|_| self.comments) {
+            write!(self.writer, " {}", comment)?;
+        }
+        self.write_eol()
+    }
+
+    /// Write a line terminator.
+    fn write_eol(&mut self) -> Result<()> {
+        self.writer.write_all(self.line_ending.as_str().as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Get the key used to sort entity frames in canonical mode.
+fn frame_key(frame: &EntityFrame) -> (u8, String) {
+    match frame {
+        EntityFrame::Term(frame) => (0, frame.id().as_ref().to_string()),
+        EntityFrame::Typedef(frame) => (1, frame.id().as_ref().to_string()),
+        EntityFrame::Instance(frame) => (2, frame.id().as_ref().to_string()),
+    }
+}
+
+/// Replace the clause with the same tag as `clause`, or add it at its canonical position.
+fn update_clause(clauses: &mut Vec<Line<HeaderClause>>, clause: HeaderClause) {
+    let kind = std::mem::discriminant(&clause);
+    match clauses.iter().position(|line| std::mem::discriminant(line.as_ref()) == kind) {
+        Some(index) => clauses[index] = Line::from(clause),
+        None => {
+            let r = rank(HEADER_TAGS, &clause.to_string());
+            let index = clauses
+                .iter()
+                .position(|line| rank(HEADER_TAGS, &line.as_ref().to_string()) > r)
+                .unwrap_or(clauses.len());
+            clauses.insert(index, Line::from(clause));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+
+    const DOC: &str = "format-version: 1.4
+remark: test
+data-version: 2019-06-13
+
+[Typedef]
+id: part_of
+name: part of
+
+[Term]
+id: TST:002
+is_a: TST:001 ! first
+name: second
+def: \"The second term.\" []
+relationship: part_of TST:001 {comment=\"qualified\"}
+
+[Term]
+id: TST:001
+name: first
+";
+
+    fn write(writer: OboWriter<Vec<u8>>, doc: &OboDoc) -> String {
+        let mut writer = writer;
+        writer.write_doc(doc).unwrap();
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn default() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        assert_eq!(write(OboWriter::new(Vec::new()), &doc), doc.to_string());
+    }
+
+    #[test]
+    fn canonical() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let expected = "format-version: 1.4
+data-version: 2019-06-13
+remark: test
+
+[Term]
+id: TST:001
+name: first
+
+[Term]
+id: TST:002
+name: second
+def: \"The second term.\" []
+is_a: TST:001 ! first
+relationship: part_of TST:001 {comment=\"qualified\"}
+
+[Typedef]
+id: part_of
+name: part of
+";
+        let written = write(OboWriter::new(Vec::new()).and_canonical(true), &doc);
+        assert_eq!(written, expected);
+        assert_eq!(OboDoc::from_str(&written).unwrap().entities.len(), 3);
+    }
+
+    #[test]
+    fn canonical_reference() {
+        // `ms.obo` was written by OBO-Edit, so it is already in canonical
+        // order and sorting it must not move any line.
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/ms.obo");
+        let doc = OboDoc::from_file(path).unwrap();
+        let written = write(OboWriter::new(Vec::new()).and_canonical(true), &doc);
+        for (expected, actual) in doc.to_string().lines().zip(written.lines()) {
+            assert_eq!(expected, actual);
+        }
+        assert_eq!(written, doc.to_string());
+    }
+
+    #[test]
+    fn qualifiers_and_comments() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let written = write(
+            OboWriter::new(Vec::new()).and_qualifiers(false).and_comments(false),
+            &doc,
+        );
+        assert!(written.contains("relationship: part_of TST:001\n"));
+        assert!(written.contains("is_a: TST:001\n"));
+    }
+
+    #[test]
+    fn line_ending() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let written = write(OboWriter::new(Vec::new()).and_line_ending(LineEnding::CrLf), &doc);
+        assert_eq!(written, doc.to_string().replace('\n', "\r\n"));
+    }
+
+    #[test]
+    fn date_and_saved_by() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let writer = OboWriter::new(Vec::new())
+            .and_date(NaiveDateTime::new(13, 6, 2019, 17, 30))
+            .and_saved_by(UnquotedString::new(String::from("fastobo")));
+        let written = write(writer, &doc);
+        assert!(written.starts_with(
+            "format-version: 1.4\ndate: 13:06:2019 17:30\nsaved-by: fastobo\n\
+             remark: test\ndata-version: 2019-06-13\n\n"
+        ));
+
+        let doc = OboDoc::from_str(&written).unwrap();
+        let writer = OboWriter::new(Vec::new()).and_date(NaiveDateTime::new(14, 6, 2019, 9, 0));
+        let written = write(writer, &doc);
+        assert!(written.contains("date: 14:06:2019 09:00\nsaved-by: fastobo\n"));
+        assert!(!written.contains("13:06:2019"));
+    }
+
+    #[test]
+    fn stream() {
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let mut writer = OboWriter::new(Vec::new());
+        writer.write_header(doc.header()).unwrap();
+        writer.write_frames(doc.entities.iter()).unwrap();
+        let written = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(written, doc.to_string());
+    }
+
+    #[test]
+    fn io_error() {
+        struct Failing;
+
+        impl Write for Failing {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(std::io::ErrorKind::Other, "failing"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let doc = OboDoc::from_str(DOC).unwrap();
+        let mut writer = OboWriter::new(Failing);
+        match writer.write_doc(&doc) {
+            Err(Error::IOError { error }) => assert_eq!(error.to_string(), "failing"),
+            other => panic!("expected an IO error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+extern crate fastobo;
+
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use fastobo::ast::OboDoc;
+
+/// Get the path to a document of the conformance suite.
+fn path(kind: &str, name: &str, ext: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/data/conformance")
+        .join(kind)
+        .join(name)
+        .with_extension(ext)
+}
+
+/// Read a file of the conformance suite, normalizing line endings.
+fn read(path: &Path) -> String {
+    fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("could not read {}: {}", path.display(), e))
+        .replace("\r\n", "\n")
+}
+
+/// Parse a document of the conformance suite without recording its path.
+fn parse(text: &str) -> fastobo::error::Result<OboDoc> {
+    OboDoc::from_stream(&mut BufReader::new(text.as_bytes()))
+}
+
+// A valid document must parse, serialize back to its canonical source,
+// and parse again to the same AST.
+macro_rules! validtest {
+    ( $(#[$attr:meta])* $name:ident) => (
+        $(#[$attr])*
+        #[test]
+        fn $name() {
+            let text = read(&path("valid", stringify!($name), "obo"));
+            let doc = parse(&text).unwrap_or_else(|e| panic!("{}", e));
+
+            let serialized = doc.to_string();
+            assert_eq!(serialized, text, "serialization is not canonical");
+
+            let reparsed = OboDoc::from_str(&serialized).unwrap_or_else(|e| panic!("{}", e));
+            assert_eq!(doc, reparsed);
+        }
+    )
+}
+
+// An invalid document must fail with the error stored in the `.err` file
+// next to it.
+macro_rules! invalidtest {
+    ( $(#[$attr:meta])* $name:ident) => (
+        $(#[$attr])*
+        #[test]
+        fn $name() {
+            let text = read(&path("invalid", stringify!($name), "obo"));
+            let expected = read(&path("invalid", stringify!($name), "err"));
+            match parse(&text) {
+                Ok(doc) => panic!("parsing succeeded:\n{}", doc),
+                Err(e) => assert_eq!(format!("{}\n", e), expected),
+            }
+        }
+    )
+}
+
+mod valid {
+
+    use super::*;
+
+    validtest!(empty);
+    validtest!(multiple_frames);
+    validtest!(identifiers);
+    validtest!(escapes);
+    validtest!(comments);
+
+    // --- Header clauses ----------------------------------------------------
+
+    validtest!(header_format_version);
+    validtest!(header_date);
+    validtest!(header_import);
+    validtest!(header_subsetdef);
+    validtest!(header_synonymtypedef);
+    validtest!(header_default_namespace);
+    validtest!(header_idspace);
+    validtest!(header_treat_xrefs_as_equivalent);
+    validtest!(header_treat_xrefs_as_genus_differentia);
+    validtest!(header_treat_xrefs_as_reverse_genus_differentia);
+    validtest!(header_treat_xrefs_as_relationship);
+    validtest!(header_treat_xrefs_as_is_a);
+    validtest!(header_treat_xrefs_as_has_subclass);
+    validtest!(header_property_value);
+    validtest!(header_remark);
+    validtest!(header_owl_axioms);
+    validtest!(header_unreserved);
+    validtest!(header_qualifiers);
+
+    // --- Term clauses ------------------------------------------------------
+
+    validtest!(term_metadata);
+    validtest!(term_def);
+    validtest!(term_synonym);
+    validtest!(term_xref);
+    validtest!(term_property_value);
+    validtest!(term_is_a);
+    validtest!(term_intersection_of);
+    validtest!(term_union_of);
+    validtest!(term_relationship);
+    validtest!(term_obsolete);
+    validtest!(term_expand);
+    validtest!(term_qualifiers);
+
+    // --- Typedef clauses ---------------------------------------------------
+
+    validtest!(typedef_metadata);
+    validtest!(typedef_properties);
+    validtest!(typedef_relations);
+    validtest!(typedef_obsolete);
+
+    // --- Instance clauses --------------------------------------------------
+
+    validtest!(instance);
+}
+
+mod invalid {
+
+    use super::*;
+
+    invalidtest!(frame_unknown);
+
+    // --- Header clauses ----------------------------------------------------
+
+    invalidtest!(header_bad_date);
+    invalidtest!(header_bad_date_day);
+    invalidtest!(header_bad_import_port);
+    invalidtest!(header_bad_synonymtypedef_scope);
+    invalidtest!(header_duplicate_format_version);
+
+    // --- Term clauses ------------------------------------------------------
+
+    invalidtest!(term_missing_id);
+    invalidtest!(term_missing_value);
+    invalidtest!(term_unclosed_quote);
+    invalidtest!(term_bad_synonym_scope);
+    invalidtest!(term_bad_boolean);
+    invalidtest!(term_bad_creation_date);
+    invalidtest!(term_bad_creation_date_month);
+    invalidtest!(term_bad_unicode_escape);
+    invalidtest!(term_unclosed_qualifiers);
+    invalidtest!(term_legacy_synonym);
+    invalidtest!(term_header_clause);
+
+    // --- Typedef clauses ---------------------------------------------------
+
+    invalidtest!(typedef_missing_id);
+    invalidtest!(typedef_bad_holds_over_chain);
+
+    // --- Instance clauses --------------------------------------------------
+
+    invalidtest!(instance_bad_relationship);
+}